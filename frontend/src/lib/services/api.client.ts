@@ -47,6 +47,8 @@ const baseFetch = async <T>(endpoint: string, options: RequestOptions = {}): Pro
 	};
 
 	const config: RequestInit = {
+		// Send the Twitch login session cookie along with API requests.
+		credentials: 'include',
 		...options,
 		headers: {
 			...defaultHeaders,
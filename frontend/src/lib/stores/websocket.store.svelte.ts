@@ -103,7 +103,11 @@ function createWebSocketStore() {
 		}
 
 		state.currentLobbyId = lobbyIdToConnect;
-		const wsUrl = `${PUBLIC_WS_BASE_URL}/ws`;
+		// An empty PUBLIC_WS_BASE_URL means the frontend is served by the game server itself.
+		const wsBaseUrl =
+			PUBLIC_WS_BASE_URL ||
+			`${window.location.protocol === 'https:' ? 'wss:' : 'ws:'}//${window.location.host}`;
+		const wsUrl = `${wsBaseUrl}/ws`;
 		info(`Attempting to connect to WebSocket: ${wsUrl} for lobby ${lobbyIdToConnect}`);
 		setStatus(
 			state.reconnectAttempts > 0 ? ConnectionStatus.RECONNECTING : ConnectionStatus.CONNECTING
@@ -120,7 +124,10 @@ function createWebSocketStore() {
 				);
 				setStatus(ConnectionStatus.AWAITING_CONNECT_ACK);
 				state.lastError = null;
-				const connectPayload: ConnectToLobbyPayload = { lobby_id: lobbyIdToConnect };
+				const connectPayload: ConnectToLobbyPayload = {
+					lobby_id: lobbyIdToConnect,
+					admin_token: lobbyStore.state.adminId ?? undefined
+				};
 				sendRawJsonMessage({ messageType: 'ConnectToLobby', payload: connectPayload });
 			};
 
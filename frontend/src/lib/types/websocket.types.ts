@@ -5,6 +5,7 @@
 // This payload is for the NEW ConnectToLobby message
 export interface ConnectToLobbyPayload {
 	lobby_id: string; // This is the lobby_id obtained from /api/create-lobby
+	admin_token?: string; // admin_id or a co-host token; omitted for read-only clients
 }
 
 // Old ConnectPayload is no longer needed if ConnectToLobby replaces it.
@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+use crate::lobby::unix_now_secs;
+
+/// Upper bound on live co-host tokens per lobby.
+pub const MAX_CO_HOST_TOKENS: usize = 20;
+
+const MAX_CO_HOST_LABEL_LENGTH: usize = 64;
+
+/// Actions a lobby host can perform. The lobby admin holds all of them; a co-host
+/// holds only the subset granted when their token was minted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HostPermission {
+    /// Start the game and move it forward (pass word/question, conclude voting).
+    ControlGame,
+    /// Remove individual guesses or queued clips.
+    Moderate,
+    /// Change game settings such as target points or time limits.
+    ChangeSettings,
+    /// Reset the game or clear the queue.
+    ResetGame,
+    /// Schedule or cancel an automatic game start.
+    ScheduleStart,
+}
+
+impl HostPermission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HostPermission::ControlGame => "control_game",
+            HostPermission::Moderate => "moderate",
+            HostPermission::ChangeSettings => "change_settings",
+            HostPermission::ResetGame => "reset_game",
+            HostPermission::ScheduleStart => "schedule_start",
+        }
+    }
+
+    /// Granted when the admin mints a co-host token without naming permissions.
+    pub const CO_HOST_DEFAULTS: [HostPermission; 2] =
+        [HostPermission::ControlGame, HostPermission::Moderate];
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientRole {
+    Admin,
+    CoHost { token: Uuid },
+    Viewer,
+}
+
+impl ClientRole {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ClientRole::Admin => "admin",
+            ClientRole::CoHost { .. } => "co_host",
+            ClientRole::Viewer => "viewer",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CoHostToken {
+    pub token: Uuid,
+    pub label: Option<String>,
+    pub permissions: BTreeSet<HostPermission>,
+    /// Unix timestamp (seconds) when the token was minted.
+    pub created_at: u64,
+}
+
+/// Request payload for minting a co-host token.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CoHostTokenRequest {
+    pub label: Option<String>,
+    pub permissions: Option<BTreeSet<HostPermission>>,
+}
+
+/// Co-host tokens minted for a single lobby. Owned by the lobby actor.
+#[derive(Debug, Default)]
+pub struct CoHostTokens {
+    tokens: HashMap<Uuid, CoHostToken>,
+}
+
+impl CoHostTokens {
+    pub fn mint(&mut self, request: CoHostTokenRequest) -> Result<CoHostToken, String> {
+        if self.tokens.len() >= MAX_CO_HOST_TOKENS {
+            return Err(format!(
+                "A lobby can have at most {} co-host tokens",
+                MAX_CO_HOST_TOKENS
+            ));
+        }
+
+        let label = request
+            .label
+            .map(|label| label.trim().to_string())
+            .filter(|label| !label.is_empty());
+        if label
+            .as_ref()
+            .is_some_and(|label| label.chars().count() > MAX_CO_HOST_LABEL_LENGTH)
+        {
+            return Err(format!(
+                "Co-host label must be at most {} characters",
+                MAX_CO_HOST_LABEL_LENGTH
+            ));
+        }
+
+        let permissions = request
+            .permissions
+            .unwrap_or_else(|| HostPermission::CO_HOST_DEFAULTS.into_iter().collect());
+        if permissions.is_empty() {
+            return Err("A co-host token needs at least one permission".to_string());
+        }
+
+        let token = CoHostToken {
+            token: Uuid::new_v4(),
+            label,
+            permissions,
+            created_at: unix_now_secs(),
+        };
+        self.tokens.insert(token.token, token.clone());
+        Ok(token)
+    }
+
+    pub fn revoke(&mut self, token: Uuid) -> Option<CoHostToken> {
+        self.tokens.remove(&token)
+    }
+
+    pub fn list(&self) -> Vec<CoHostToken> {
+        let mut tokens: Vec<CoHostToken> = self.tokens.values().cloned().collect();
+        tokens.sort_by_key(|token| token.created_at);
+        tokens
+    }
+
+    /// Maps the token presented on connect to a role. Returns `None` when a token
+    /// was presented but is neither the admin id nor a live co-host token.
+    pub fn resolve_role(&self, admin_id: Uuid, presented: Option<Uuid>) -> Option<ClientRole> {
+        match presented {
+            None => Some(ClientRole::Viewer),
+            Some(token) if token == admin_id => Some(ClientRole::Admin),
+            Some(token) if self.tokens.contains_key(&token) => Some(ClientRole::CoHost { token }),
+            Some(_) => None,
+        }
+    }
+
+    pub fn permissions_for(&self, role: &ClientRole) -> BTreeSet<HostPermission> {
+        match role {
+            ClientRole::Admin => [
+                HostPermission::ControlGame,
+                HostPermission::Moderate,
+                HostPermission::ChangeSettings,
+                HostPermission::ResetGame,
+                HostPermission::ScheduleStart,
+            ]
+            .into_iter()
+            .collect(),
+            ClientRole::CoHost { token } => self
+                .tokens
+                .get(token)
+                .map(|t| t.permissions.clone())
+                .unwrap_or_default(),
+            ClientRole::Viewer => BTreeSet::new(),
+        }
+    }
+
+    pub fn is_allowed(&self, role: &ClientRole, permission: HostPermission) -> bool {
+        match role {
+            ClientRole::Admin => true,
+            ClientRole::CoHost { token } => self
+                .tokens
+                .get(token)
+                .is_some_and(|t| t.permissions.contains(&permission)),
+            ClientRole::Viewer => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_co_host_token_lifecycle() {
+        let admin_id = Uuid::new_v4();
+        let mut tokens = CoHostTokens::default();
+
+        let minted = tokens
+            .mint(CoHostTokenRequest {
+                label: Some("  mod  ".to_string()),
+                permissions: None,
+            })
+            .unwrap();
+        assert_eq!(minted.label.as_deref(), Some("mod"));
+
+        let role = tokens.resolve_role(admin_id, Some(minted.token)).unwrap();
+        assert_eq!(
+            role,
+            ClientRole::CoHost {
+                token: minted.token
+            }
+        );
+        assert!(tokens.is_allowed(&role, HostPermission::Moderate));
+        assert!(!tokens.is_allowed(&role, HostPermission::ResetGame));
+
+        assert!(tokens.revoke(minted.token).is_some());
+        assert!(!tokens.is_allowed(&role, HostPermission::Moderate));
+        assert_eq!(tokens.resolve_role(admin_id, Some(minted.token)), None);
+    }
+
+    #[test]
+    fn test_resolve_role_for_admin_and_viewer() {
+        let admin_id = Uuid::new_v4();
+        let tokens = CoHostTokens::default();
+
+        assert_eq!(
+            tokens.resolve_role(admin_id, Some(admin_id)),
+            Some(ClientRole::Admin)
+        );
+        assert_eq!(
+            tokens.resolve_role(admin_id, None),
+            Some(ClientRole::Viewer)
+        );
+        assert!(!tokens.is_allowed(&ClientRole::Viewer, HostPermission::ControlGame));
+    }
+
+    #[test]
+    fn test_mint_rejects_empty_permissions() {
+        let mut tokens = CoHostTokens::default();
+        assert!(
+            tokens
+                .mint(CoHostTokenRequest {
+                    label: None,
+                    permissions: Some(BTreeSet::new()),
+                })
+                .is_err()
+        );
+    }
+}
@@ -1,14 +1,183 @@
 use crate::error::{ConfigError, Result as AppResult};
-use crate::game_logic::GameType;
-use config::{Config, Environment, Value, ValueKind};
+use crate::game_logic::{GameLifecycleEvent, GameType};
+use crate::game_logic::{clip_queue, deal_no_deal, settings as game_settings};
+use crate::webhooks::WebhookTarget;
+use config::{Config, Environment, File, Value, ValueKind};
+use http::HeaderValue;
 use serde::{Deserialize, Deserializer};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 
 #[derive(Deserialize, Clone)]
 pub struct ServerConfig {
     pub port: u16,
     pub cors_origins: Vec<String>,
+    /// Legacy single admin key, accepted as a key named `default` with every scope.
+    #[serde(default)]
     pub admin_api_key: String,
+    /// Named admin keys, each limited to the listed scopes.
+    #[serde(default)]
+    pub admin_keys: Vec<AdminKeyConfig>,
+    /// Upper bound on concurrently running lobbies. `None` means unlimited.
+    pub max_active_lobbies: Option<usize>,
+    /// Built frontend (`frontend/build`) to serve from the same origin as the API.
+    #[serde(default)]
+    pub static_dir: Option<String>,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    #[serde(default)]
+    pub websocket: WebSocketConfig,
+    /// Serve HTTPS/WSS directly instead of plain HTTP.
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct WebSocketConfig {
+    /// Largest message or frame a client may send; bigger ones close the
+    /// connection with code 1009.
+    pub max_message_bytes: usize,
+}
+
+/// Smallest accepted `max_message_bytes`; lobby commands need a few hundred bytes.
+pub const MIN_WEBSOCKET_MESSAGE_BYTES: usize = 1024;
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        Self {
+            max_message_bytes: 64 * 1024,
+        }
+    }
+}
+
+/// Per-IP limit for HTTP requests: one request replenished every `per_millisecond`,
+/// with up to `burst_size` requests available at once.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RateLimitConfig {
+    #[serde(default = "default_rate_limit_per_millisecond")]
+    pub per_millisecond: u64,
+    #[serde(default = "default_rate_limit_burst_size")]
+    pub burst_size: u32,
+    /// Additional per-IP limit on `/api/create-lobby`, on top of the one above.
+    #[serde(default)]
+    pub create_lobby: CreateLobbyRateLimit,
+    #[serde(default)]
+    pub websocket: WebSocketRateLimit,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct CreateLobbyRateLimit {
+    pub per_millisecond: u64,
+    pub burst_size: u32,
+}
+
+impl Default for CreateLobbyRateLimit {
+    fn default() -> Self {
+        Self {
+            per_millisecond: 10_000,
+            burst_size: 3,
+        }
+    }
+}
+
+/// Token bucket applied to the messages of each WebSocket connection. Messages
+/// over the limit are dropped, and a client that has `max_rejected_messages`
+/// messages dropped before one gets through again is disconnected.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct WebSocketRateLimit {
+    pub per_millisecond: u64,
+    pub burst_size: u32,
+    pub max_rejected_messages: u32,
+}
+
+impl Default for WebSocketRateLimit {
+    fn default() -> Self {
+        Self {
+            per_millisecond: 100,
+            burst_size: 20,
+            max_rejected_messages: 50,
+        }
+    }
+}
+
+fn default_rate_limit_per_millisecond() -> u64 {
+    500
+}
+
+fn default_rate_limit_burst_size() -> u32 {
+    30
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            per_millisecond: default_rate_limit_per_millisecond(),
+            burst_size: default_rate_limit_burst_size(),
+            create_lobby: CreateLobbyRateLimit::default(),
+            websocket: WebSocketRateLimit::default(),
+        }
+    }
+}
+
+/// What an admin key may do.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminScope {
+    /// Reloading game content (`/api/refresh-words`).
+    ContentRefresh,
+    /// Managing lobby presets.
+    LobbyManagement,
+    /// Adding, changing and disabling streamer accounts.
+    StreamerManagement,
+    /// Reading server metrics.
+    Metrics,
+}
+
+impl AdminScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdminScope::ContentRefresh => "content_refresh",
+            AdminScope::LobbyManagement => "lobby_management",
+            AdminScope::StreamerManagement => "streamer_management",
+            AdminScope::Metrics => "metrics",
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+pub struct AdminKeyConfig {
+    /// Identifies the key in audit logs.
+    pub name: String,
+    pub key: String,
+    pub scopes: HashSet<AdminScope>,
+}
+
+impl std::fmt::Debug for AdminKeyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminKeyConfig")
+            .field("name", &self.name)
+            .field("scopes", &self.scopes)
+            .finish()
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TlsConfig {
+    /// PEM certificate chain.
+    pub cert_path: String,
+    /// PEM private key.
+    pub key_path: String,
+    /// How often both files are checked for changes to reload them.
+    #[serde(default = "default_tls_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+}
+
+fn default_tls_reload_interval_secs() -> u64 {
+    60
 }
 
 impl std::fmt::Debug for ServerConfig {
@@ -16,6 +185,12 @@ impl std::fmt::Debug for ServerConfig {
         f.debug_struct("ServerConfig")
             .field("port", &self.port)
             .field("cors_origins", &self.cors_origins)
+            .field("admin_keys", &self.admin_keys)
+            .field("max_active_lobbies", &self.max_active_lobbies)
+            .field("static_dir", &self.static_dir)
+            .field("rate_limit", &self.rate_limit)
+            .field("websocket", &self.websocket)
+            .field("tls", &self.tls)
             .finish()
     }
 }
@@ -24,27 +199,238 @@ impl std::fmt::Debug for ServerConfig {
 pub struct TwitchConfig {
     pub client_id: String,
     pub client_secret: String,
+    /// Read chat as an anonymous `justinfan` user without app credentials.
+    /// Features that need a token, such as Twitch login, are unavailable.
+    #[serde(default)]
+    pub anonymous: bool,
     #[serde(default = "default_irc_server_url")]
     pub irc_server_url: String,
+    /// Base of the OAuth endpoints (`/oauth2/token`, `/oauth2/authorize`).
+    #[serde(default = "default_twitch_auth_base_url")]
+    pub auth_base_url: String,
+    /// Base of the Helix API (`/helix/users`).
+    #[serde(default = "default_twitch_api_base_url")]
+    pub api_base_url: String,
+    #[serde(default)]
+    pub login: TwitchLoginConfig,
+    #[serde(default)]
+    pub chat: TwitchChatConfig,
+}
+
+impl TwitchConfig {
+    /// Anonymous mode has neither app credentials nor a bot account, so Twitch
+    /// login and chat sending are switched off with a warning instead of refusing
+    /// to start.
+    fn disable_features_needing_credentials(&mut self) {
+        if !self.anonymous {
+            return;
+        }
+        if self.login.enabled {
+            tracing::warn!(
+                "twitch.login.enabled needs app credentials; Twitch login is disabled in anonymous mode"
+            );
+            self.login.enabled = false;
+        }
+        if self.chat.enabled {
+            tracing::warn!(
+                "twitch.chat.enabled needs a bot account; chat sending is disabled in anonymous mode"
+            );
+            self.chat.enabled = false;
+        }
+    }
 }
 
 fn default_irc_server_url() -> String {
     "irc.chat.twitch.tv:6667".to_string()
 }
 
+fn default_twitch_auth_base_url() -> String {
+    "https://id.twitch.tv".to_string()
+}
+
+fn default_twitch_api_base_url() -> String {
+    "https://api.twitch.tv".to_string()
+}
+
+/// "Log in with Twitch" for broadcasters. When enabled, creating a lobby for a
+/// channel requires being logged in as that channel.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct TwitchLoginConfig {
+    pub enabled: bool,
+    /// Callback registered with the Twitch application, pointing at
+    /// `/api/auth/twitch/callback` on this server.
+    pub redirect_url: String,
+    /// Where the browser goes once logged in.
+    pub post_login_redirect: String,
+    pub session_ttl_hours: u64,
+}
+
+impl Default for TwitchLoginConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            redirect_url: String::new(),
+            post_login_redirect: "/".to_string(),
+            session_ttl_hours: 12,
+        }
+    }
+}
+
+/// Bot account that posts game announcements to Twitch chat. When enabled, the
+/// IRC connection logs in as the bot instead of an anonymous user.
+#[derive(Clone, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TwitchChatConfig {
+    pub enabled: bool,
+    /// Twitch login of the bot account.
+    pub bot_login: String,
+    /// User refresh token of the bot account with the `chat:read` and `chat:edit`
+    /// scopes. Access tokens are derived from it and refreshed automatically.
+    pub refresh_token: String,
+    /// Messages the bot may send per channel every 30 seconds. Twitch allows 20,
+    /// or 100 in channels where the bot is a moderator.
+    pub messages_per_30_seconds: u32,
+    /// Message template per lifecycle event, e.g. `player_scored`. Events without
+    /// a template are not announced.
+    pub announcements: BTreeMap<String, String>,
+    /// Templates per game type (e.g. `dealnodeal`) layered over `announcements`.
+    /// They may also name the game's own announcements, such as `banker_offer`;
+    /// an empty template silences an event for that game.
+    pub games: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl TwitchChatConfig {
+    /// Announcement templates for lobbies of the game type with `primary_id`.
+    pub fn templates_for(&self, primary_id: &str) -> BTreeMap<String, String> {
+        let mut templates = self.announcements.clone();
+        if let Some(game_templates) = self.games.get(primary_id) {
+            templates.extend(game_templates.clone());
+        }
+        templates.retain(|_, template| !template.trim().is_empty());
+        templates
+    }
+}
+
+impl Default for TwitchChatConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bot_login: String::new(),
+            refresh_token: String::new(),
+            messages_per_30_seconds: 20,
+            announcements: BTreeMap::from([
+                (
+                    "game_started".to_string(),
+                    "A new round of {game} has started!".to_string(),
+                ),
+                (
+                    "player_scored".to_string(),
+                    "Correct! {player} has {points} points".to_string(),
+                ),
+                ("game_over".to_string(), "Game over! {result}".to_string()),
+            ]),
+            games: BTreeMap::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for TwitchChatConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TwitchChatConfig")
+            .field("enabled", &self.enabled)
+            .field("bot_login", &self.bot_login)
+            .field("messages_per_30_seconds", &self.messages_per_30_seconds)
+            .field("announcements", &self.announcements)
+            .field("games", &self.games)
+            .finish()
+    }
+}
+
 impl std::fmt::Debug for TwitchConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TwitchConfig")
             .field("client_id", &self.client_id)
+            .field("anonymous", &self.anonymous)
             .field("irc_server_url", &self.irc_server_url)
+            .field("auth_base_url", &self.auth_base_url)
+            .field("api_base_url", &self.api_base_url)
+            .field("login", &self.login)
+            .field("chat", &self.chat)
             .finish()
     }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct GamesConfig {
     #[serde(deserialize_with = "deserialize_string_or_list_to_set_lowercase")]
     pub enabled_types: HashSet<String>,
+    #[serde(default)]
+    pub medandraord: ScoringGameDefaults,
+    #[serde(default)]
+    pub quiz: ScoringGameDefaults,
+    #[serde(default)]
+    pub dealnodeal: DealNoDealDefaults,
+    #[serde(default)]
+    pub clipqueue: ClipQueueDefaults,
+}
+
+/// Starting values for the guessing games (Med Andra Ord and Quiz). Settings sent
+/// with `create-lobby` and admin commands still override them per lobby.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ScoringGameDefaults {
+    pub target_points: u32,
+    pub game_duration_seconds: u32,
+    pub point_limit_enabled: bool,
+    pub time_limit_enabled: bool,
+    /// How many recent correct guesses are kept for the host panel.
+    pub recent_guesses_limit: usize,
+}
+
+impl Default for ScoringGameDefaults {
+    fn default() -> Self {
+        Self {
+            target_points: 10,
+            game_duration_seconds: 300,
+            point_limit_enabled: true,
+            time_limit_enabled: false,
+            recent_guesses_limit: 5,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct DealNoDealDefaults {
+    /// One briefcase per value.
+    pub money_values: Vec<u64>,
+    /// Cases opened in each round; must add up to two fewer than the number of
+    /// cases (the player's case and the last one left for switch or keep).
+    pub round_schedule: Vec<u8>,
+}
+
+impl Default for DealNoDealDefaults {
+    fn default() -> Self {
+        Self {
+            money_values: deal_no_deal::DEFAULT_MONEY_VALUES.to_vec(),
+            round_schedule: deal_no_deal::DEFAULT_ROUND_SCHEDULE.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ClipQueueDefaults {
+    pub max_clip_duration_seconds: u32,
+}
+
+impl Default for ClipQueueDefaults {
+    fn default() -> Self {
+        Self {
+            max_clip_duration_seconds: 600,
+        }
+    }
 }
 
 impl Default for GamesConfig {
@@ -57,14 +443,29 @@ impl Default for GamesConfig {
             let mut enabled_types = HashSet::new();
             enabled_types.insert("dealnodeal".to_string());
             enabled_types.insert("medandraord".to_string());
-            return Self { enabled_types };
+            return Self {
+                enabled_types,
+                ..Self::with_enabled_types(HashSet::new())
+            };
         }
 
         let enabled_types = all_game_types
             .iter()
             .map(|game_type| game_type.primary_id().to_string())
             .collect();
-        Self { enabled_types }
+        Self::with_enabled_types(enabled_types)
+    }
+}
+
+impl GamesConfig {
+    pub fn with_enabled_types(enabled_types: HashSet<String>) -> Self {
+        Self {
+            enabled_types,
+            medandraord: ScoringGameDefaults::default(),
+            quiz: ScoringGameDefaults::default(),
+            dealnodeal: DealNoDealDefaults::default(),
+            clipqueue: ClipQueueDefaults::default(),
+        }
     }
 }
 
@@ -75,13 +476,91 @@ pub enum ContentSourceType {
     Http,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct DatabaseConfig {
     pub source_type: ContentSourceType,
     pub file_path: Option<String>,
     pub http_url: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PresetsConfig {
+    /// JSON file presets are persisted to. When unset, presets are kept in memory only.
+    pub file_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct StreamersConfig {
+    /// JSON file streamer accounts are persisted to. When unset, accounts are kept
+    /// in memory only.
+    pub file_path: Option<String>,
+    /// Lets channels without a streamer account create lobbies, as an empty
+    /// whitelist used to.
+    #[serde(default)]
+    pub allow_unregistered_channels: bool,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ObsSettings {
+    /// Allows lobbies to connect to an OBS WebSocket. Off by default because the
+    /// server then opens outbound connections to creator-supplied addresses.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct WebhooksSettings {
+    /// Targets that receive lifecycle events from every lobby.
+    #[serde(default)]
+    pub urls: Vec<String>,
+    /// Signs payloads sent to `urls`.
+    pub secret: Option<String>,
+    /// Lets lobby creators register their own targets. Off by default because the
+    /// server then posts to creator-supplied addresses.
+    #[serde(default)]
+    pub allow_lobby_targets: bool,
+    #[serde(default = "default_webhook_max_attempts")]
+    pub max_attempts: u32,
+}
+
+fn default_webhook_max_attempts() -> u32 {
+    5
+}
+
+impl Default for WebhooksSettings {
+    fn default() -> Self {
+        Self {
+            urls: Vec::new(),
+            secret: None,
+            allow_lobby_targets: false,
+            max_attempts: default_webhook_max_attempts(),
+        }
+    }
+}
+
+impl WebhooksSettings {
+    pub fn global_targets(&self) -> Vec<WebhookTarget> {
+        self.urls
+            .iter()
+            .map(|url| WebhookTarget {
+                url: url.clone(),
+                secret: self.secret.clone(),
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for WebhooksSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhooksSettings")
+            .field("urls", &self.urls)
+            .field("has_secret", &self.secret.is_some())
+            .field("allow_lobby_targets", &self.allow_lobby_targets)
+            .field("max_attempts", &self.max_attempts)
+            .finish()
+    }
+}
+
 #[derive(Clone, Deserialize)]
 pub struct YouTubeConfig {
     pub api_key: String,
@@ -100,10 +579,102 @@ pub struct AppSettings {
     pub games: GamesConfig,
     pub database: DatabaseConfig,
     pub youtube: Option<YouTubeConfig>,
+    #[serde(default)]
+    pub presets: PresetsConfig,
+    #[serde(default)]
+    pub streamers: StreamersConfig,
+    #[serde(default)]
+    pub obs: ObsSettings,
+    #[serde(default)]
+    pub webhooks: WebhooksSettings,
+}
+
+/// Command line options. Settings themselves come from the config file and the
+/// environment; the command line only says where to look and what to do.
+#[derive(Debug, Default, PartialEq)]
+pub struct CliOptions {
+    /// TOML, YAML or JSON file layered under the environment variables.
+    pub config_file: Option<PathBuf>,
+    /// Validate the configuration, report every problem and exit.
+    pub check_config: bool,
+}
+
+impl CliOptions {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, ConfigError> {
+        let mut options = CliOptions {
+            config_file: std::env::var_os("KOLMODIN_CONFIG_FILE").map(PathBuf::from),
+            check_config: false,
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--check-config" => options.check_config = true,
+                "--config" => {
+                    let path = args.next().ok_or_else(|| {
+                        ConfigError::InvalidValue("--config requires a file path".to_string())
+                    })?;
+                    options.config_file = Some(PathBuf::from(path));
+                }
+                other => {
+                    if let Some(path) = other.strip_prefix("--config=") {
+                        options.config_file = Some(PathBuf::from(path));
+                    } else {
+                        return Err(ConfigError::InvalidValue(format!(
+                            "Unknown argument '{}' (expected --config <path> or --check-config)",
+                            other
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// The running server's settings. Reloads swap in a new snapshot; readers keep
+/// whichever snapshot they already hold.
+#[derive(Debug, Clone)]
+pub struct SharedSettings {
+    current: Arc<RwLock<Arc<AppSettings>>>,
+}
+
+impl SharedSettings {
+    pub fn new(settings: AppSettings) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(settings))),
+        }
+    }
+
+    pub fn current(&self) -> Arc<AppSettings> {
+        match self.current.read() {
+            Ok(guard) => Arc::clone(&guard),
+            Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+        }
+    }
+
+    /// Applies the reload-safe fields of `reloaded` (CORS origins, rate limits,
+    /// WebSocket limits, game settings and the content source) and returns the new
+    /// snapshot.
+    /// Everything else keeps its startup value until the server restarts.
+    pub fn apply_reload(&self, reloaded: AppSettings) -> Arc<AppSettings> {
+        let mut next = (*self.current()).clone();
+        next.server.cors_origins = reloaded.server.cors_origins;
+        next.server.rate_limit = reloaded.server.rate_limit;
+        next.server.websocket = reloaded.server.websocket;
+        next.games = reloaded.games;
+        next.database = reloaded.database;
+
+        let next = Arc::new(next);
+        match self.current.write() {
+            Ok(mut guard) => *guard = Arc::clone(&next),
+            Err(poisoned) => *poisoned.into_inner() = Arc::clone(&next),
+        }
+        next
+    }
 }
 
 #[tracing::instrument]
-pub fn load_settings() -> AppResult<AppSettings> {
+pub fn load_settings(config_file: Option<&Path>) -> AppResult<AppSettings> {
     let all_game_types = GameType::all();
 
     if all_game_types.is_empty() {
@@ -118,13 +689,20 @@ pub fn load_settings() -> AppResult<AppSettings> {
         .map(|game_type| Value::new(None, ValueKind::String(game_type.primary_id().to_string())))
         .collect();
 
-    let settings_builder = Config::builder()
+    let mut settings_builder = Config::builder();
+    if let Some(path) = config_file {
+        settings_builder = settings_builder.add_source(File::from(path).required(true));
+    }
+    let settings_builder = settings_builder
         .add_source(
             Environment::with_prefix("KOLMODIN")
                 .separator("__")
                 .list_separator(",")
                 .with_list_parse_key("server.cors_origins")
                 .with_list_parse_key("games.enabled_types")
+                .with_list_parse_key("webhooks.urls")
+                .with_list_parse_key("games.dealnodeal.money_values")
+                .with_list_parse_key("games.dealnodeal.round_schedule")
                 .try_parsing(true),
         )
         .set_default("server.port", 8080)?
@@ -140,50 +718,271 @@ pub fn load_settings() -> AppResult<AppSettings> {
         .build()
         .map_err(|e| ConfigError::Load(e.to_string()))?;
 
-    let app_settings: AppSettings = settings.try_deserialize().map_err(|e| {
-        if e.to_string().contains("admin_api_key") {
-            ConfigError::Missing(
-                "server.admin_api_key (must be set via KOLMODIN_SERVER__ADMIN_API_KEY env var)"
+    let mut app_settings: AppSettings = settings
+        .try_deserialize()
+        .map_err(|e| ConfigError::Load(e.to_string()))?;
+    app_settings.twitch.disable_features_needing_credentials();
+
+    let problems = validate_settings(&app_settings);
+    if !problems.is_empty() {
+        return Err(ConfigError::Invalid(problems).into());
+    }
+
+    Ok(app_settings)
+}
+
+/// Checks the loaded settings and returns every problem found, so a broken
+/// configuration can be fixed in one pass.
+pub fn validate_settings(app_settings: &AppSettings) -> Vec<ConfigError> {
+    let mut problems = Vec::new();
+
+    if app_settings.server.admin_api_key.is_empty() && app_settings.server.admin_keys.is_empty() {
+        problems.push(ConfigError::InvalidValue(
+            "server.admin_api_key must not be empty unless server.admin_keys are configured"
+                .to_string(),
+        ));
+    }
+    let mut admin_key_names = HashSet::new();
+    for admin_key in &app_settings.server.admin_keys {
+        if admin_key.name.trim().is_empty() {
+            problems.push(ConfigError::InvalidValue(
+                "server.admin_keys entries must have a name".to_string(),
+            ));
+        } else if !admin_key_names.insert(admin_key.name.as_str()) || admin_key.name == "default" {
+            problems.push(ConfigError::InvalidValue(format!(
+                "server.admin_keys name '{}' is used more than once or reserved",
+                admin_key.name
+            )));
+        }
+        if admin_key.key.is_empty() {
+            problems.push(ConfigError::InvalidValue(format!(
+                "server.admin_keys '{}' has an empty key",
+                admin_key.name
+            )));
+        }
+        if admin_key.scopes.is_empty() {
+            problems.push(ConfigError::InvalidValue(format!(
+                "server.admin_keys '{}' has no scopes",
+                admin_key.name
+            )));
+        }
+    }
+    for origin in &app_settings.server.cors_origins {
+        if origin.parse::<HeaderValue>().is_err() {
+            problems.push(ConfigError::InvalidValue(format!(
+                "server.cors_origins contains an invalid origin '{}'",
+                origin
+            )));
+        }
+    }
+    let rate_limit = &app_settings.server.rate_limit;
+    for (name, per_millisecond, burst_size) in [
+        (
+            "server.rate_limit",
+            rate_limit.per_millisecond,
+            rate_limit.burst_size,
+        ),
+        (
+            "server.rate_limit.create_lobby",
+            rate_limit.create_lobby.per_millisecond,
+            rate_limit.create_lobby.burst_size,
+        ),
+        (
+            "server.rate_limit.websocket",
+            rate_limit.websocket.per_millisecond,
+            rate_limit.websocket.burst_size,
+        ),
+    ] {
+        if per_millisecond == 0 || burst_size == 0 {
+            problems.push(ConfigError::InvalidValue(format!(
+                "{}.per_millisecond and burst_size must be greater than zero",
+                name
+            )));
+        }
+    }
+    if app_settings.server.websocket.max_message_bytes < MIN_WEBSOCKET_MESSAGE_BYTES {
+        problems.push(ConfigError::InvalidValue(format!(
+            "server.websocket.max_message_bytes must be at least {}",
+            MIN_WEBSOCKET_MESSAGE_BYTES
+        )));
+    }
+    if let Some(static_dir) = app_settings.server.static_dir.as_deref()
+        && !Path::new(static_dir).is_dir()
+    {
+        problems.push(ConfigError::InvalidValue(format!(
+            "server.static_dir '{}' is not a directory",
+            static_dir
+        )));
+    }
+    if let Some(tls) = app_settings.server.tls.as_ref() {
+        for (field, path) in [("cert_path", &tls.cert_path), ("key_path", &tls.key_path)] {
+            if !Path::new(path).is_file() {
+                problems.push(ConfigError::InvalidValue(format!(
+                    "server.tls.{} '{}' does not exist",
+                    field, path
+                )));
+            }
+        }
+    }
+
+    let twitch = &app_settings.twitch;
+    if !twitch.anonymous {
+        if twitch.client_id.is_empty() {
+            problems.push(ConfigError::Missing("twitch.client_id".to_string()));
+        }
+        if twitch.client_secret.is_empty() {
+            problems.push(ConfigError::Missing("twitch.client_secret".to_string()));
+        }
+    }
+    for (name, url) in [
+        ("twitch.auth_base_url", twitch.auth_base_url.as_str()),
+        ("twitch.api_base_url", twitch.api_base_url.as_str()),
+    ] {
+        if !is_http_url(url) {
+            problems.push(ConfigError::InvalidValue(format!(
+                "{} '{}' is not an http(s) URL",
+                name, url
+            )));
+        }
+    }
+    if twitch.chat.enabled {
+        if twitch.chat.bot_login.is_empty() {
+            problems.push(ConfigError::Missing("twitch.chat.bot_login".to_string()));
+        }
+        if twitch.chat.refresh_token.is_empty() {
+            problems.push(ConfigError::Missing(
+                "twitch.chat.refresh_token".to_string(),
+            ));
+        }
+        if twitch.chat.messages_per_30_seconds == 0 {
+            problems.push(ConfigError::InvalidValue(
+                "twitch.chat.messages_per_30_seconds must be greater than zero".to_string(),
+            ));
+        }
+    }
+    for event in twitch.chat.announcements.keys() {
+        if !GameLifecycleEvent::NAMES.contains(&event.as_str()) {
+            problems.push(ConfigError::InvalidValue(format!(
+                "twitch.chat.announcements has unknown event '{}' (expected one of: {})",
+                event,
+                GameLifecycleEvent::NAMES.join(", ")
+            )));
+        }
+    }
+    for (game, templates) in &twitch.chat.games {
+        let Some(game_type) = GameType::all()
+            .into_iter()
+            .find(|game_type| game_type.primary_id() == game)
+        else {
+            problems.push(ConfigError::InvalidValue(format!(
+                "twitch.chat.games has unknown game type '{}'",
+                game
+            )));
+            continue;
+        };
+        let known: Vec<&str> = GameLifecycleEvent::NAMES
+            .iter()
+            .chain(game_type.announcement_names())
+            .copied()
+            .collect();
+        for event in templates.keys() {
+            if !known.contains(&event.as_str()) {
+                problems.push(ConfigError::InvalidValue(format!(
+                    "twitch.chat.games.{} has unknown event '{}' (expected one of: {})",
+                    game,
+                    event,
+                    known.join(", ")
+                )));
+            }
+        }
+    }
+    if twitch.login.enabled {
+        if !is_http_url(&twitch.login.redirect_url) {
+            problems.push(ConfigError::InvalidValue(
+                "twitch.login.redirect_url must be an http(s) URL when Twitch login is enabled"
                     .to_string(),
-            )
-        } else {
-            ConfigError::Load(e.to_string())
+            ));
+        }
+        if twitch.login.session_ttl_hours == 0 {
+            problems.push(ConfigError::InvalidValue(
+                "twitch.login.session_ttl_hours must be greater than zero".to_string(),
+            ));
         }
-    })?;
+    }
 
-    if app_settings.server.admin_api_key.is_empty() {
-        return Err(ConfigError::InvalidValue(
-            "server.admin_api_key must not be empty".to_string(),
-        )
-        .into());
+    let known_game_types: Vec<&str> = GameType::all().iter().map(GameType::primary_id).collect();
+    for game_type in &app_settings.games.enabled_types {
+        if !known_game_types.contains(&game_type.as_str()) {
+            problems.push(ConfigError::InvalidValue(format!(
+                "games.enabled_types contains unknown game type '{}' (expected one of: {})",
+                game_type,
+                known_game_types.join(", ")
+            )));
+        }
     }
-    if app_settings.twitch.client_id.is_empty() {
-        return Err(ConfigError::Missing("twitch.client_id".to_string()).into());
+
+    for (game, defaults) in [
+        ("medandraord", &app_settings.games.medandraord),
+        ("quiz", &app_settings.games.quiz),
+    ] {
+        let checks = [
+            game_settings::validate_target_points(defaults.target_points),
+            game_settings::validate_game_duration(defaults.game_duration_seconds),
+            game_settings::validate_recent_guesses_limit(defaults.recent_guesses_limit),
+        ];
+        for message in checks.into_iter().filter_map(Result::err) {
+            problems.push(ConfigError::InvalidValue(format!(
+                "games.{}: {}",
+                game, message
+            )));
+        }
+    }
+    if let Err(message) = deal_no_deal::validate_board(
+        &app_settings.games.dealnodeal.money_values,
+        &app_settings.games.dealnodeal.round_schedule,
+    ) {
+        problems.push(ConfigError::InvalidValue(format!(
+            "games.dealnodeal: {}",
+            message
+        )));
     }
-    if app_settings.twitch.client_secret.is_empty() {
-        return Err(ConfigError::Missing("twitch.client_secret".to_string()).into());
+    if let Err(message) = clip_queue::validate_max_clip_duration(
+        app_settings.games.clipqueue.max_clip_duration_seconds,
+    ) {
+        problems.push(ConfigError::InvalidValue(format!(
+            "games.clipqueue: {}",
+            message
+        )));
     }
 
     match app_settings.database.source_type {
         ContentSourceType::File => {
             if app_settings.database.file_path.is_none() {
-                return Err(ConfigError::Missing(
+                problems.push(ConfigError::Missing(
                     "database.file_path (for source_type 'file')".to_string(),
-                )
-                .into());
+                ));
             }
         }
         ContentSourceType::Http => {
             if app_settings.database.http_url.is_none() {
-                return Err(ConfigError::Missing(
+                problems.push(ConfigError::Missing(
                     "database.http_url (for source_type 'http')".to_string(),
-                )
-                .into());
+                ));
             }
         }
     }
 
-    Ok(app_settings)
+    for target in app_settings.webhooks.global_targets() {
+        if let Err(message) = target.validate() {
+            problems.push(ConfigError::InvalidValue(message));
+        }
+    }
+
+    problems
+}
+
+fn is_http_url(url: &str) -> bool {
+    reqwest::Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
 }
 
 fn deserialize_string_or_list_to_set_lowercase<'de, D>(
@@ -231,3 +1030,261 @@ where
 
     Ok(set)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_config_file(contents: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("kolmodin-config-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    const VALID_CONFIG: &str = r#"
+[server]
+admin_api_key = "secret"
+cors_origins = ["https://kolmodin.example"]
+
+[[server.admin_keys]]
+name = "stream-deck"
+key = "deck-key"
+scopes = ["lobby_management", "metrics"]
+
+[server.rate_limit]
+burst_size = 10
+
+[server.rate_limit.websocket]
+burst_size = 5
+
+[twitch]
+client_id = "id"
+client_secret = "client-secret"
+
+[database]
+source_type = "file"
+file_path = "data.json"
+
+[games]
+enabled_types = ["quiz", "medandraord"]
+
+[games.quiz]
+target_points = 20
+time_limit_enabled = true
+
+[games.dealnodeal]
+money_values = [1, 10, 100, 1000, 10000]
+round_schedule = [2, 1]
+"#;
+
+    #[test]
+    fn test_loads_settings_from_config_file() {
+        let path = write_config_file(VALID_CONFIG);
+        let settings = load_settings(Some(&path)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            settings.server.cors_origins,
+            vec!["https://kolmodin.example"]
+        );
+        assert_eq!(settings.server.rate_limit.burst_size, 10);
+        assert_eq!(settings.server.admin_keys[0].name, "stream-deck");
+        assert_eq!(
+            settings.server.admin_keys[0].scopes,
+            HashSet::from([AdminScope::LobbyManagement, AdminScope::Metrics])
+        );
+        assert_eq!(settings.server.rate_limit.per_millisecond, 500);
+        assert_eq!(settings.server.rate_limit.websocket.burst_size, 5);
+        assert_eq!(settings.server.rate_limit.websocket.per_millisecond, 100);
+        assert_eq!(
+            settings.server.rate_limit.create_lobby,
+            CreateLobbyRateLimit::default()
+        );
+        assert_eq!(
+            settings.games.enabled_types,
+            HashSet::from(["quiz".to_string(), "medandraord".to_string()])
+        );
+        assert_eq!(settings.games.quiz.target_points, 20);
+        assert!(settings.games.quiz.time_limit_enabled);
+        assert_eq!(settings.games.quiz.game_duration_seconds, 300);
+        assert_eq!(settings.games.medandraord, ScoringGameDefaults::default());
+        assert_eq!(settings.games.dealnodeal.round_schedule, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_reports_every_problem_at_once() {
+        let path = write_config_file(
+            r#"
+[server]
+admin_api_key = ""
+
+[[server.admin_keys]]
+name = "ci"
+key = "ci-key"
+scopes = []
+
+[server.rate_limit]
+burst_size = 0
+
+[twitch]
+client_id = "id"
+
+[database]
+source_type = "http"
+
+[games]
+enabled_types = "quiz,bingo"
+
+[games.dealnodeal]
+round_schedule = [6, 5]
+"#,
+        );
+        let result = load_settings(Some(&path));
+        std::fs::remove_file(&path).unwrap();
+
+        let Err(crate::error::AppError::Config(ConfigError::Invalid(problems))) = result else {
+            panic!("expected validation problems, got {:?}", result.map(|_| ()));
+        };
+        let messages: Vec<String> = problems.iter().map(ToString::to_string).collect();
+        assert_eq!(messages.len(), 6, "{messages:?}");
+        assert!(messages.iter().any(|m| m.contains("'ci' has no scopes")));
+        assert!(messages.iter().any(|m| m.contains("rate_limit")));
+        assert!(messages.iter().any(|m| m.contains("client_secret")));
+        assert!(messages.iter().any(|m| m.contains("http_url")));
+        assert!(messages.iter().any(|m| m.contains("'bingo'")));
+        assert!(messages.iter().any(|m| m.contains("games.dealnodeal")));
+    }
+
+    #[test]
+    fn test_anonymous_twitch_needs_no_credentials() {
+        let path = write_config_file(
+            r#"
+[server]
+admin_api_key = "secret"
+
+[twitch]
+anonymous = true
+
+[twitch.login]
+enabled = true
+redirect_url = "http://localhost:8080/api/auth/twitch/callback"
+
+[twitch.chat]
+enabled = true
+
+[database]
+source_type = "file"
+file_path = "data.json"
+"#,
+        );
+        let settings = load_settings(Some(&path)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(settings.twitch.anonymous);
+        assert!(settings.twitch.client_id.is_empty());
+        // Both need credentials anonymous mode lacks, so they are switched off.
+        assert!(!settings.twitch.login.enabled);
+        assert!(!settings.twitch.chat.enabled);
+    }
+
+    #[test]
+    fn test_chat_announcements_need_bot_and_known_events() {
+        let path = write_config_file(
+            r#"
+[server]
+admin_api_key = "secret"
+
+[twitch]
+client_id = "id"
+client_secret = "secret"
+
+[twitch.chat]
+enabled = true
+
+[twitch.chat.announcements]
+game_started = "Go!"
+round_won = "{player} won"
+
+[twitch.chat.games.dealnodeal]
+banker_offer = "The banker offers {offer}"
+question_changed = "{question}"
+
+[twitch.chat.games.chess]
+game_started = "Go!"
+
+[database]
+source_type = "file"
+file_path = "data.json"
+"#,
+        );
+        let result = load_settings(Some(&path));
+        std::fs::remove_file(&path).unwrap();
+
+        let Err(crate::error::AppError::Config(ConfigError::Invalid(problems))) = result else {
+            panic!("expected validation problems, got {:?}", result.map(|_| ()));
+        };
+        let messages: Vec<String> = problems.iter().map(ToString::to_string).collect();
+        assert_eq!(messages.len(), 5, "{messages:?}");
+        assert!(messages.iter().any(|m| m.contains("twitch.chat.bot_login")));
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.contains("twitch.chat.refresh_token"))
+        );
+        assert!(messages.iter().any(|m| m.contains("'round_won'")));
+        assert!(messages.iter().any(|m| m.contains("'question_changed'")));
+        assert!(messages.iter().any(|m| m.contains("'chess'")));
+    }
+
+    #[test]
+    fn test_reload_only_applies_safe_fields() {
+        let path = write_config_file(VALID_CONFIG);
+        let original = load_settings(Some(&path)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut reloaded = original.clone();
+        reloaded.server.port = 9999;
+        reloaded.server.admin_api_key = "changed".to_string();
+        reloaded.server.cors_origins = vec!["https://other.example".to_string()];
+        reloaded.games.enabled_types = HashSet::from(["quiz".to_string()]);
+        reloaded.server.websocket.max_message_bytes *= 2;
+        let reloaded_websocket_bytes = reloaded.server.websocket.max_message_bytes;
+
+        let shared = SharedSettings::new(original);
+        let current = shared.apply_reload(reloaded);
+
+        assert_eq!(current.server.cors_origins, vec!["https://other.example"]);
+        assert_eq!(
+            current.games.enabled_types,
+            HashSet::from(["quiz".to_string()])
+        );
+        assert_eq!(
+            current.server.websocket.max_message_bytes,
+            reloaded_websocket_bytes
+        );
+        assert_eq!(current.server.port, 8080);
+        assert_eq!(current.server.admin_api_key, "secret");
+        assert_eq!(
+            shared.current().server.cors_origins,
+            current.server.cors_origins
+        );
+    }
+
+    #[test]
+    fn test_parses_command_line_options() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        let options =
+            CliOptions::parse(args(&["--config", "kolmodin.toml", "--check-config"])).unwrap();
+        assert_eq!(options.config_file, Some(PathBuf::from("kolmodin.toml")));
+        assert!(options.check_config);
+
+        let options = CliOptions::parse(args(&["--config=kolmodin.yaml"])).unwrap();
+        assert_eq!(options.config_file, Some(PathBuf::from("kolmodin.yaml")));
+        assert!(!options.check_config);
+
+        assert!(CliOptions::parse(args(&["--config"])).is_err());
+        assert!(CliOptions::parse(args(&["--verbose"])).is_err());
+    }
+}
@@ -37,14 +37,17 @@ pub struct VemVetMestQuestion {
 }
 
 // Kolmodin legacy data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct KolmodinData {
+    /// Legacy channel list, only imported into the streamer accounts on first start.
+    #[serde(default)]
     pub twitch_whitelist: Vec<String>,
 }
 
 // Root data structure matching the JSON schema
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonGameContentSnapshot {
+    #[serde(default)]
     pub kolmodin: KolmodinData,
     pub medandraord_words: Vec<String>,
     pub trivial_pursuit: TrivialPursuitData,
@@ -155,7 +158,7 @@ pub struct GameContentCache {
     twitch_whitelist: RwLock<Arc<Vec<String>>>,
     trivial_pursuit_data: RwLock<Option<Arc<TrivialPursuitData>>>,
     vem_vet_mest_questions: RwLock<Arc<Vec<VemVetMestQuestion>>>,
-    content_config: DatabaseConfig,
+    content_config: RwLock<DatabaseConfig>,
 }
 
 impl GameContentCache {
@@ -185,14 +188,15 @@ impl GameContentCache {
             twitch_whitelist: RwLock::new(Arc::new(initial_data.twitch_whitelist)),
             trivial_pursuit_data: RwLock::new(Some(Arc::new(initial_data.trivial_pursuit))),
             vem_vet_mest_questions: RwLock::new(Arc::new(initial_data.vem_vet_mest)),
-            content_config: config,
+            content_config: RwLock::new(config),
         })
     }
 
     #[tracing::instrument(skip(self))]
     pub async fn refresh_all_content(&self) -> AppResult<()> {
         tracing::info!("Refreshing cached game content");
-        let new_data = load_content_snapshot_from_config(&self.content_config).await?;
+        let content_config = self.content_config.read().await.clone();
+        let new_data = load_content_snapshot_from_config(&content_config).await?;
 
         {
             let mut words_guard = self.medandraord_words.write().await;
@@ -236,6 +240,12 @@ impl GameContentCache {
         Ok(())
     }
 
+    /// Points later refreshes at a new content source. Cached content is kept
+    /// until the next `refresh_all_content`.
+    pub async fn set_source(&self, config: DatabaseConfig) {
+        *self.content_config.write().await = config;
+    }
+
     pub async fn medandraord_words(&self) -> Arc<Vec<String>> {
         self.medandraord_words.read().await.clone()
     }
@@ -253,24 +263,6 @@ impl GameContentCache {
     pub async fn vem_vet_mest_questions(&self) -> Arc<Vec<VemVetMestQuestion>> {
         self.vem_vet_mest_questions.read().await.clone()
     }
-
-    #[tracing::instrument(skip(self), fields(channel.name = %channel_name))]
-    pub async fn is_twitch_channel_allowed(&self, channel_name: &str) -> bool {
-        let whitelist = self.twitch_whitelist.read().await;
-        let is_empty = whitelist.is_empty();
-        let normalized_channel = channel_name.to_lowercase();
-        let is_allowed = is_empty || whitelist.contains(&normalized_channel);
-
-        tracing::trace!(
-            channel.normalized = %normalized_channel,
-            whitelist.empty = is_empty,
-            whitelist.count = whitelist.len(),
-            result = is_allowed,
-            "Checking if Twitch channel is allowed"
-        );
-
-        is_allowed
-    }
 }
 
 #[cfg(test)]
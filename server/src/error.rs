@@ -8,6 +8,16 @@ pub enum ConfigError {
     InvalidValue(String),
     #[error("Missing required configuration: {0}")]
     Missing(String),
+    #[error("{} configuration problem(s): {}", .0.len(), join_problems(.0))]
+    Invalid(Vec<ConfigError>),
+}
+
+fn join_problems(problems: &[ConfigError]) -> String {
+    problems
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 #[derive(Debug, Error)]
@@ -25,6 +35,48 @@ pub enum DbError {
     Parse(String),
 }
 
+#[derive(Debug, Error)]
+pub enum PresetError {
+    #[error("Invalid preset name: {0}")]
+    InvalidName(String),
+    #[error("Preset '{0}' not found")]
+    NotFound(String),
+    #[error("Failed to read preset file '{path}': {source}")]
+    FileRead {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Failed to write preset file '{path}': {source}")]
+    FileWrite {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse preset data: {0}")]
+    Parse(String),
+}
+
+#[derive(Debug, Error)]
+pub enum StreamerError {
+    #[error("Invalid Twitch channel name: {0}")]
+    InvalidChannel(String),
+    #[error("Unknown content pack '{0}'")]
+    UnknownContentPack(String),
+    #[error("Streamer '{0}' not found")]
+    NotFound(String),
+    #[error("Failed to read streamer file '{path}': {source}")]
+    FileRead {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Failed to write streamer file '{path}': {source}")]
+    FileWrite {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse streamer data: {0}")]
+    Parse(String),
+}
+
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("Configuration error: {0}")]
@@ -41,6 +93,10 @@ pub enum AppError {
     ConfigParsing(#[from] config::ConfigError),
     #[error("Database error: {0}")]
     Database(#[from] DbError),
+    #[error("Preset storage error: {0}")]
+    Preset(#[from] PresetError),
+    #[error("Streamer storage error: {0}")]
+    Streamer(#[from] StreamerError),
 }
 
 pub type Result<T, E = AppError> = std::result::Result<T, E>;
@@ -5,17 +5,23 @@ use std::collections::{HashMap, HashSet};
 use tokio::sync::mpsc::Sender as TokioMpscSender;
 use uuid::Uuid;
 
+use crate::cohost::HostPermission;
 use crate::config::AppSettings;
 use crate::game_logic::messages::{
     ClientToServerMessage as GenericClientToServerMessage,
     ServerToClientMessage as GenericServerToClientMessage,
 };
-use crate::game_logic::{EventHandlingResult, GameLogic};
-use crate::twitch::ParsedTwitchMessage;
+use crate::game_logic::settings::parse_lobby_settings;
+use crate::game_logic::{EventHandlingResult, GameLifecycleEvent, GameLogic};
+use crate::twitch::{ChatMessageRef, ChatModeration, ParsedTwitchMessage};
 use std::sync::Arc;
 use tracing::{error, info, warn};
 
 const GAME_TYPE_ID_CLIP_QUEUE: &str = "ClipQueue";
+const MAX_ALLOWED_CLIP_DURATION_SECONDS: u32 = 4 * 60 * 60;
+
+/// Names reported by `phase_name`: whether the queue accepts clips.
+pub const PHASES: [&str; 2] = ["SubmissionsOpen", "SubmissionsClosed"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClipInfo {
@@ -26,6 +32,10 @@ pub struct ClipInfo {
     pub thumbnail_url: String,
     pub submitted_by_username: String,
     pub submitted_at_timestamp: i64,
+    /// The `!clip` message that submitted it, so the clip leaves the queue if a
+    /// moderator removes the message.
+    #[serde(skip)]
+    pub submitted_message: Option<ChatMessageRef>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +55,15 @@ impl Default for ClipQueueSettings {
     }
 }
 
+/// Initial settings accepted by `create-lobby` for a ClipQueue lobby.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClipQueueLobbySettings {
+    pub submissions_open: Option<bool>,
+    pub allow_duplicates: Option<bool>,
+    pub max_clip_duration_seconds: Option<u32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClipQueueGameState {
     pub clip_queue: Vec<ClipInfo>,
@@ -93,6 +112,17 @@ pub struct ClipQueueGame {
     state: ClipQueueGameState,
     app_settings: Arc<AppSettings>,
     youtube_url_regex: Regex,
+    lifecycle_events: Vec<GameLifecycleEvent>,
+}
+
+pub fn validate_max_clip_duration(seconds: u32) -> Result<(), String> {
+    if seconds == 0 || seconds > MAX_ALLOWED_CLIP_DURATION_SECONDS {
+        return Err(format!(
+            "max_clip_duration_seconds must be between 1 and {}, got {}",
+            MAX_ALLOWED_CLIP_DURATION_SECONDS, seconds
+        ));
+    }
+    Ok(())
 }
 
 impl ClipQueueGame {
@@ -100,7 +130,10 @@ impl ClipQueueGame {
         let state = ClipQueueGameState {
             clip_queue: Vec::new(),
             removed_by_admin_clip_ids: HashSet::new(),
-            settings: ClipQueueSettings::default(),
+            settings: ClipQueueSettings {
+                max_clip_duration_seconds: app_settings.games.clipqueue.max_clip_duration_seconds,
+                ..ClipQueueSettings::default()
+            },
         };
 
         // Regex to extract YouTube video IDs from various URL formats
@@ -113,6 +146,7 @@ impl ClipQueueGame {
             state,
             app_settings,
             youtube_url_regex,
+            lifecycle_events: Vec::new(),
         }
     }
 
@@ -254,6 +288,7 @@ impl ClipQueueGame {
             thumbnail_url: thumbnail_url.to_string(),
             submitted_by_username: String::new(), // Will be set by caller
             submitted_at_timestamp: 0,            // Will be set by caller
+            submitted_message: None,              // Will be set by caller
         })
     }
 
@@ -297,6 +332,35 @@ impl ClipQueueGame {
         self.broadcast_event(&event).await;
     }
 
+    /// Takes clips submitted by messages `moderation` removed out of the queue,
+    /// returning their video ids.
+    fn remove_clips_from_removed_messages(&mut self, moderation: &ChatModeration) -> Vec<String> {
+        let mut removed_video_ids = Vec::new();
+        self.state.clip_queue.retain(|clip| {
+            let removed = clip
+                .submitted_message
+                .as_ref()
+                .is_some_and(|message| moderation.removes(message));
+            if removed {
+                removed_video_ids.push(clip.video_id.clone());
+            }
+            !removed
+        });
+        removed_video_ids
+    }
+
+    /// The queue has no phases, so every command applies at any time and only
+    /// invalid settings are rejected. Removing a clip that is not queued still
+    /// blocks it from being submitted again.
+    fn check_command(command: &AdminCommand) -> Result<(), String> {
+        match command {
+            AdminCommand::UpdateSettings { new_settings } => {
+                validate_max_clip_duration(new_settings.max_clip_duration_seconds)
+            }
+            AdminCommand::RemoveClipFromQueue { .. } | AdminCommand::ResetQueue => Ok(()),
+        }
+    }
+
     async fn handle_admin_command(&mut self, command: AdminCommand) {
         let mut events_to_broadcast = Vec::new();
 
@@ -391,16 +455,25 @@ impl GameLogic for ClipQueueGame {
                         "ClipQueue received command for wrong game type: {}",
                         game_type_id
                     );
-                    return EventHandlingResult::Handled;
+                    return EventHandlingResult::Rejected {
+                        reason: format!("Command is for game type '{}'", game_type_id),
+                    };
                 }
 
                 match serde_json::from_value::<AdminCommand>(command_data) {
                     Ok(command) => {
+                        if let Err(reason) = Self::check_command(&command) {
+                            warn!("ClipQueue: Rejected admin command: {}", reason);
+                            return EventHandlingResult::Rejected { reason };
+                        }
                         info!("ClipQueue: Processing admin command: {:?}", command);
                         self.handle_admin_command(command).await;
                     }
                     Err(e) => {
                         error!("ClipQueue: Failed to deserialize admin command: {:?}", e);
+                        return EventHandlingResult::Rejected {
+                            reason: format!("Invalid command: {}", e),
+                        };
                     }
                 }
             }
@@ -415,6 +488,22 @@ impl GameLogic for ClipQueueGame {
         EventHandlingResult::Handled
     }
 
+    fn apply_lobby_settings(&mut self, settings: Option<serde_json::Value>) -> Result<(), String> {
+        let settings: ClipQueueLobbySettings = parse_lobby_settings(settings)?;
+
+        if let Some(max_duration) = settings.max_clip_duration_seconds {
+            validate_max_clip_duration(max_duration)?;
+            self.state.settings.max_clip_duration_seconds = max_duration;
+        }
+        if let Some(open) = settings.submissions_open {
+            self.state.settings.submissions_open = open;
+        }
+        if let Some(allow) = settings.allow_duplicates {
+            self.state.settings.allow_duplicates = allow;
+        }
+        Ok(())
+    }
+
     async fn handle_twitch_message(&mut self, message: ParsedTwitchMessage) {
         // Check for !clip command
         if !message.text.starts_with("!clip ") {
@@ -485,9 +574,13 @@ impl GameLogic for ClipQueueGame {
                 // Set submission details
                 clip_info.submitted_by_username = message.sender_username.clone();
                 clip_info.submitted_at_timestamp = chrono::Utc::now().timestamp();
+                clip_info.submitted_message = Some(message.message_ref());
 
                 // Add to queue
                 self.state.clip_queue.push(clip_info.clone());
+                self.lifecycle_events.push(GameLifecycleEvent::ClipAdded {
+                    clip: clip_info.clone(),
+                });
 
                 let event = GameEvent::ClipAdded { clip: clip_info };
                 self.broadcast_event(&event).await;
@@ -504,6 +597,73 @@ impl GameLogic for ClipQueueGame {
         }
     }
 
+    async fn handle_chat_moderation(&mut self, moderation: ChatModeration) {
+        let removed_video_ids = self.remove_clips_from_removed_messages(&moderation);
+        if removed_video_ids.is_empty() {
+            return;
+        }
+        info!(
+            "ClipQueue: Removed {} clip(s) whose submissions a moderator deleted",
+            removed_video_ids.len()
+        );
+
+        for video_id in removed_video_ids {
+            let event = GameEvent::ClipRemoved {
+                video_id,
+                removed_by_admin: false,
+            };
+            self.broadcast_event(&event).await;
+        }
+        self.broadcast_full_state().await;
+    }
+
+    fn phase_name(&self) -> &'static str {
+        // The queue has no phases; whether it accepts clips is the closest analogue.
+        if self.state.settings.submissions_open {
+            PHASES[0]
+        } else {
+            PHASES[1]
+        }
+    }
+
+    fn drain_lifecycle_events(&mut self) -> Vec<GameLifecycleEvent> {
+        std::mem::take(&mut self.lifecycle_events)
+    }
+
+    fn state_snapshot(&self) -> serde_json::Value {
+        let event = GameEvent::FullStateUpdate {
+            state: self.state.clone(),
+        };
+        serde_json::to_value(&event).unwrap_or_else(|e| {
+            error!("Failed to serialize ClipQueue state snapshot: {:?}", e);
+            serde_json::Value::Null
+        })
+    }
+
+    fn redact_public_event(_event_data: &mut serde_json::Value) {
+        // Queue contents and settings are public.
+    }
+
+    fn command_permission(
+        &self,
+        command_data: &serde_json::Value,
+    ) -> Result<HostPermission, String> {
+        let command = serde_json::from_value::<AdminCommand>(command_data.clone())
+            .map_err(|e| format!("Invalid ClipQueue command: {}", e))?;
+        Ok(match command {
+            AdminCommand::RemoveClipFromQueue { .. } => HostPermission::Moderate,
+            AdminCommand::UpdateSettings { .. } => HostPermission::ChangeSettings,
+            AdminCommand::ResetQueue => HostPermission::ResetGame,
+        })
+    }
+
+    fn supports_scheduled_start(&self) -> bool {
+        // The clip queue is always live; there is no setup phase to leave.
+        false
+    }
+
+    async fn start_game(&mut self) {}
+
     fn is_empty(&self) -> bool {
         self.clients.is_empty()
     }
@@ -525,7 +685,9 @@ impl GameLogic for ClipQueueGame {
 mod tests {
     use super::*;
     use crate::config::{
-        ContentSourceType, DatabaseConfig, GamesConfig, ServerConfig, TwitchConfig, YouTubeConfig,
+        ContentSourceType, DatabaseConfig, GamesConfig, ObsSettings, PresetsConfig,
+        RateLimitConfig, ServerConfig, StreamersConfig, TwitchChatConfig, TwitchConfig,
+        TwitchLoginConfig, WebSocketConfig, WebhooksSettings, YouTubeConfig,
     };
 
     fn create_test_config() -> Arc<AppSettings> {
@@ -534,15 +696,24 @@ mod tests {
                 port: 3000,
                 cors_origins: vec!["http://localhost:5173".to_string()],
                 admin_api_key: "test_key".to_string(),
+                admin_keys: Vec::new(),
+                max_active_lobbies: None,
+                static_dir: None,
+                rate_limit: RateLimitConfig::default(),
+                websocket: WebSocketConfig::default(),
+                tls: None,
             },
             twitch: TwitchConfig {
                 client_id: "test_client_id".to_string(),
                 client_secret: "test_client_secret".to_string(),
+                anonymous: false,
                 irc_server_url: "irc.chat.twitch.tv:6667".to_string(),
+                auth_base_url: "https://id.twitch.tv".to_string(),
+                api_base_url: "https://api.twitch.tv".to_string(),
+                login: TwitchLoginConfig::default(),
+                chat: TwitchChatConfig::default(),
             },
-            games: GamesConfig {
-                enabled_types: std::collections::HashSet::new(),
-            },
+            games: GamesConfig::with_enabled_types(std::collections::HashSet::new()),
             database: DatabaseConfig {
                 source_type: ContentSourceType::File,
                 file_path: Some("/tmp/test.db".to_string()),
@@ -551,6 +722,10 @@ mod tests {
             youtube: Some(YouTubeConfig {
                 api_key: "test_youtube_api_key".to_string(),
             }),
+            presets: PresetsConfig::default(),
+            streamers: StreamersConfig::default(),
+            obs: ObsSettings::default(),
+            webhooks: WebhooksSettings::default(),
         })
     }
 
@@ -608,6 +783,7 @@ mod tests {
             thumbnail_url: "https://example.com/thumb.jpg".to_string(),
             submitted_by_username: "testuser".to_string(),
             submitted_at_timestamp: 0,
+            submitted_message: None,
         };
         game.state.clip_queue.push(clip);
 
@@ -616,6 +792,58 @@ mod tests {
         assert!(!game.is_duplicate("different_video_id"));
     }
 
+    #[tokio::test]
+    async fn test_moderation_removes_submitted_clips() {
+        let config = create_test_config();
+        let mut game = ClipQueueGame::new(config);
+        for (video_id, user_id, message_id) in [
+            ("aaaaaaaaaaa", "1", "m1"),
+            ("bbbbbbbbbbb", "2", "m2"),
+            ("ccccccccccc", "2", "m3"),
+        ] {
+            game.state.clip_queue.push(ClipInfo {
+                video_id: video_id.to_string(),
+                title: String::new(),
+                channel_title: String::new(),
+                duration_iso8601: "PT1M".to_string(),
+                thumbnail_url: String::new(),
+                submitted_by_username: format!("user{}", user_id),
+                submitted_at_timestamp: 0,
+                submitted_message: Some(ChatMessageRef {
+                    message_id: Some(message_id.to_string()),
+                    user_id: Some(user_id.to_string()),
+                    username: format!("user{}", user_id),
+                }),
+            });
+        }
+        let queued = |game: &ClipQueueGame| -> Vec<String> {
+            game.state
+                .clip_queue
+                .iter()
+                .map(|clip| clip.video_id.clone())
+                .collect()
+        };
+
+        game.handle_chat_moderation(ChatModeration::ChatCleared)
+            .await;
+        assert_eq!(queued(&game).len(), 3);
+
+        game.handle_chat_moderation(ChatModeration::MessageDeleted {
+            message_id: "m1".to_string(),
+            login: "user1".to_string(),
+        })
+        .await;
+        assert_eq!(queued(&game), ["bbbbbbbbbbb", "ccccccccccc"]);
+
+        game.handle_chat_moderation(ChatModeration::UserCleared {
+            user_id: Some("2".to_string()),
+            login: "user2".to_string(),
+            ban_duration_seconds: Some(600),
+        })
+        .await;
+        assert!(queued(&game).is_empty());
+    }
+
     #[test]
     fn test_default_settings() {
         let settings = ClipQueueSettings::default();
@@ -624,6 +852,28 @@ mod tests {
         assert_eq!(settings.max_clip_duration_seconds, 600);
     }
 
+    #[test]
+    fn test_apply_lobby_settings() {
+        let config = create_test_config();
+        let mut game = ClipQueueGame::new(config);
+
+        game.apply_lobby_settings(Some(serde_json::json!({
+            "allow_duplicates": true,
+            "max_clip_duration_seconds": 120
+        })))
+        .unwrap();
+        assert!(game.state.settings.allow_duplicates);
+        assert!(game.state.settings.submissions_open);
+        assert_eq!(game.state.settings.max_clip_duration_seconds, 120);
+
+        assert!(
+            game.apply_lobby_settings(Some(serde_json::json!({
+                "max_clip_duration_seconds": 0
+            })))
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_game_type_id() {
         let config = create_test_config();
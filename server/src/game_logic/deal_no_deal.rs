@@ -6,22 +6,56 @@ use std::collections::HashMap;
 use tokio::sync::mpsc::Sender as TokioMpscSender;
 use uuid::Uuid;
 
+use crate::cohost::HostPermission;
+use crate::config::DealNoDealDefaults;
 use crate::game_logic::messages::{
     ClientToServerMessage as GenericClientToServerMessage,
     ServerToClientMessage as GenericServerToClientMessage,
 };
-use crate::game_logic::{EventHandlingResult, GameLogic};
-use crate::twitch::ParsedTwitchMessage;
+use crate::game_logic::settings::parse_lobby_settings;
+use crate::game_logic::{EventHandlingResult, GameLifecycleEvent, GameLogic, GameResult};
+use crate::twitch::{ChatMessageRef, ChatModeration, ParsedTwitchMessage};
 
 const GAME_TYPE_ID_DND: &str = "DealNoDeal";
 
-const TOTAL_CASES: u8 = 26;
-const MONEY_VALUES: [u64; TOTAL_CASES as usize] = [
+pub const DEFAULT_MONEY_VALUES: [u64; 26] = [
     1, 3, 5, 10, 25, 50, 75, 100, 200, 300, 400, 500, 750, 1_000, 5_000, 10_000, 25_000, 50_000,
     75_000, 100_000, 200_000, 300_000, 400_000, 500_000, 750_000, 1_000_000,
 ];
 
-const ROUND_SCHEDULE: [u8; 9] = [6, 5, 4, 3, 2, 1, 1, 1, 1];
+pub const DEFAULT_ROUND_SCHEDULE: [u8; 9] = [6, 5, 4, 3, 2, 1, 1, 1, 1];
+
+const MIN_CASES: usize = 3;
+const MAX_CASES: usize = 99;
+
+/// Checks a configured board: case ids must fit the vote parser, and the rounds
+/// must open every case except the player's and the final one.
+pub fn validate_board(money_values: &[u64], round_schedule: &[u8]) -> Result<(), String> {
+    if !(MIN_CASES..=MAX_CASES).contains(&money_values.len()) {
+        return Err(format!(
+            "money_values must have between {} and {} entries, got {}",
+            MIN_CASES,
+            MAX_CASES,
+            money_values.len()
+        ));
+    }
+    if round_schedule.is_empty() || round_schedule.contains(&0) {
+        return Err(
+            "round_schedule must list at least one round, each opening at least one case"
+                .to_string(),
+        );
+    }
+    let cases_opened: usize = round_schedule.iter().map(|&cases| cases as usize).sum();
+    if cases_opened != money_values.len() - 2 {
+        return Err(format!(
+            "round_schedule opens {} cases but {} money values need exactly {}",
+            cases_opened,
+            money_values.len(),
+            money_values.len() - 2
+        ));
+    }
+    Ok(())
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "command")]
@@ -30,6 +64,32 @@ pub enum AdminCommand {
     ConcludeVotingAndProcess,
 }
 
+/// Chat announcements queued when a vote opens. `case_opening_opened` fills
+/// `{round}` and `{cases}`, `banker_offer` fills `{round}` and `{offer}`.
+pub const ANNOUNCEMENTS: [&str; 4] = [
+    "case_selection_opened",
+    "case_opening_opened",
+    "banker_offer",
+    "switch_or_keep_opened",
+];
+
+/// Names reported by `phase_name`, in play order.
+pub const PHASES: [&str; 7] = [
+    "Setup",
+    "PlayerCaseSelectionVoting",
+    "RoundCaseOpeningVoting",
+    "BankerOfferCalculation",
+    "DealOrNoDealVoting",
+    "SwitchOrKeepVoting",
+    "GameOver",
+];
+
+/// Initial settings accepted by `create-lobby` for a DealNoDeal lobby.
+/// The game has no tunable options yet, so only an empty object is accepted.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DealNoDealLobbySettings {}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "event_type", content = "data")]
 pub enum GameEvent {
@@ -85,16 +145,25 @@ pub struct DealNoDealGame {
     pub remaining_money_values_in_play: Vec<u64>,
     pub current_round_schedule_index: usize,
     current_votes_by_user: HashMap<String, String>,
+    /// The chat message behind each vote in `current_votes_by_user`.
+    #[serde(skip)]
+    vote_messages: HashMap<String, ChatMessageRef>,
 
     pub current_round_display_number: u8,
     pub cases_to_open_this_round_target: u8,
     pub cases_opened_in_current_round_segment: u8,
     pub banker_offer: Option<u64>,
     pub current_vote_tally: Option<HashMap<String, u32>>,
+    #[serde(skip)]
+    lifecycle_events: Vec<GameLifecycleEvent>,
+    #[serde(skip)]
+    money_values: Vec<u64>,
+    #[serde(skip)]
+    round_schedule: Vec<u8>,
 }
 
 impl DealNoDealGame {
-    pub fn new() -> Self {
+    pub fn new(defaults: &DealNoDealDefaults) -> Self {
         Self {
             clients: HashMap::new(),
             phase: GamePhase::Setup,
@@ -104,14 +173,22 @@ impl DealNoDealGame {
             remaining_money_values_in_play: Vec::new(),
             current_round_schedule_index: 0,
             current_votes_by_user: HashMap::new(),
+            vote_messages: HashMap::new(),
             current_round_display_number: 0,
             cases_to_open_this_round_target: 0,
             cases_opened_in_current_round_segment: 0,
             banker_offer: None,
             current_vote_tally: None,
+            lifecycle_events: Vec::new(),
+            money_values: defaults.money_values.clone(),
+            round_schedule: defaults.round_schedule.clone(),
         }
     }
 
+    fn case_count(&self) -> u8 {
+        self.money_values.len() as u8
+    }
+
     fn prepare_for_client_view(&mut self) {
         let (round_num, target, opened_segment, tally_opt, offer) = match &self.phase {
             GamePhase::Setup => (0, 0, 0, None, None),
@@ -137,8 +214,8 @@ impl DealNoDealGame {
                 let schedule_idx_of_completed_round = self.current_round_schedule_index;
 
                 let completed_round_target =
-                    if schedule_idx_of_completed_round < ROUND_SCHEDULE.len() {
-                        ROUND_SCHEDULE[schedule_idx_of_completed_round]
+                    if schedule_idx_of_completed_round < self.round_schedule.len() {
+                        self.round_schedule[schedule_idx_of_completed_round]
                     } else {
                         0
                     };
@@ -152,15 +229,19 @@ impl DealNoDealGame {
                 )
             }
             GamePhase::SwitchOrKeepVoting { .. } => (
-                ROUND_SCHEDULE.len() as u8 + 1,
+                self.round_schedule.len() as u8 + 1,
                 0,
                 0,
                 Some(self.tally_current_votes_internal()),
                 None,
             ),
-            GamePhase::GameOver { winnings, .. } => {
-                (ROUND_SCHEDULE.len() as u8 + 1, 0, 0, None, Some(*winnings))
-            }
+            GamePhase::GameOver { winnings, .. } => (
+                self.round_schedule.len() as u8 + 1,
+                0,
+                0,
+                None,
+                Some(*winnings),
+            ),
         };
         self.current_round_display_number = round_num;
         self.cases_to_open_this_round_target = target;
@@ -272,17 +353,18 @@ impl DealNoDealGame {
     }
 
     fn initialize_game_board(&mut self) {
-        let mut money_shuffled = MONEY_VALUES.to_vec();
+        let mut money_shuffled = self.money_values.clone();
         money_shuffled.shuffle(&mut thread_rng());
         self.briefcase_values = money_shuffled;
-        self.briefcase_is_opened = vec![false; TOTAL_CASES as usize];
-        self.remaining_money_values_in_play = MONEY_VALUES.to_vec();
+        self.briefcase_is_opened = vec![false; self.money_values.len()];
+        self.remaining_money_values_in_play = self.money_values.clone();
         self.remaining_money_values_in_play.sort_unstable();
         self.player_chosen_case_index = None;
         self.current_round_schedule_index = 0;
 
         self.phase = GamePhase::PlayerCaseSelectionVoting;
-        self.current_votes_by_user.clear();
+        self.clear_votes();
+        self.lifecycle_events.push(GameLifecycleEvent::GameStarted);
         tracing::info!(
             phase = "PlayerCaseSelectionVoting",
             "Game board initialized"
@@ -314,12 +396,34 @@ impl DealNoDealGame {
 
         let rounds_completed_for_progression = self.current_round_schedule_index + 1;
 
-        let prog_factor = rounds_completed_for_progression as f64 / ROUND_SCHEDULE.len() as f64;
+        let prog_factor =
+            rounds_completed_for_progression as f64 / self.round_schedule.len() as f64;
 
         let offer_perc = 0.10 + (prog_factor * 0.75);
         (avg_rem * offer_perc.min(0.85)).round().max(1.0) as u64
     }
 
+    fn clear_votes(&mut self) {
+        self.current_votes_by_user.clear();
+        self.vote_messages.clear();
+    }
+
+    /// Drops the votes cast by messages `moderation` removed, returning how many
+    /// were dropped. A vote replaced by a later message from the same user stays.
+    fn void_removed_votes(&mut self, moderation: &ChatModeration) -> usize {
+        let voided: Vec<String> = self
+            .vote_messages
+            .iter()
+            .filter(|(_, message)| moderation.removes(message))
+            .map(|(voter, _)| voter.clone())
+            .collect();
+        for voter in &voided {
+            self.current_votes_by_user.remove(voter);
+            self.vote_messages.remove(voter);
+        }
+        voided.len()
+    }
+
     fn tally_current_votes_internal(&self) -> HashMap<String, u32> {
         self.current_votes_by_user
             .values()
@@ -341,7 +445,7 @@ impl DealNoDealGame {
                     .ok()
                     .map(|id_1_based| (id_1_based, *count))
             })
-            .filter(|(id_1_based, _)| *id_1_based >= 1 && *id_1_based <= TOTAL_CASES)
+            .filter(|(id_1_based, _)| *id_1_based >= 1 && *id_1_based <= self.case_count())
             .map(|(id_1_based, count)| ((id_1_based - 1) as usize, count))
             .filter(|(idx, _)| {
                 *idx < self.briefcase_is_opened.len() && !self.briefcase_is_opened[*idx]
@@ -363,7 +467,7 @@ impl DealNoDealGame {
                     .ok()
                     .map(|id_1_based| (id_1_based, count))
             })
-            .filter(|(id_1_based, _)| *id_1_based >= 1 && *id_1_based <= TOTAL_CASES)
+            .filter(|(id_1_based, _)| *id_1_based >= 1 && *id_1_based <= self.case_count())
             .map(|(id_1_based, count)| ((id_1_based - 1) as usize, count))
             .filter(|(idx, _)| {
                 *idx < self.briefcase_is_opened.len()
@@ -400,6 +504,65 @@ impl DealNoDealGame {
         Some(switch_votes > keep_votes)
     }
 
+    /// A new game can start from setup or after the last one ended, and voting can
+    /// only be concluded while a vote is open.
+    fn check_command(&self, command: &AdminCommand) -> Result<(), String> {
+        match command {
+            AdminCommand::StartGame
+                if !matches!(self.phase, GamePhase::Setup | GamePhase::GameOver { .. }) =>
+            {
+                Err("A game is already in progress".to_string())
+            }
+            AdminCommand::ConcludeVotingAndProcess
+                if !matches!(
+                    self.phase,
+                    GamePhase::PlayerCaseSelectionVoting
+                        | GamePhase::RoundCaseOpeningVoting { .. }
+                        | GamePhase::DealOrNoDealVoting { .. }
+                        | GamePhase::SwitchOrKeepVoting { .. }
+                ) =>
+            {
+                Err("There is no vote to conclude right now".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Queues the chat announcement for the vote the game just moved to, if any.
+    fn announce_vote_opened(&mut self, previous_phase: &GamePhase) {
+        if std::mem::discriminant(previous_phase) == std::mem::discriminant(&self.phase) {
+            return;
+        }
+        let (name, values) = match &self.phase {
+            GamePhase::PlayerCaseSelectionVoting => (ANNOUNCEMENTS[0], Vec::new()),
+            GamePhase::RoundCaseOpeningVoting {
+                round_number,
+                total_to_open_for_round,
+                ..
+            } => (
+                ANNOUNCEMENTS[1],
+                vec![
+                    ("round", round_number.to_string()),
+                    ("cases", total_to_open_for_round.to_string()),
+                ],
+            ),
+            GamePhase::DealOrNoDealVoting {
+                round_number,
+                offer,
+            } => (
+                ANNOUNCEMENTS[2],
+                vec![
+                    ("round", round_number.to_string()),
+                    ("offer", offer.to_string()),
+                ],
+            ),
+            GamePhase::SwitchOrKeepVoting { .. } => (ANNOUNCEMENTS[3], Vec::new()),
+            _ => return,
+        };
+        self.lifecycle_events
+            .push(GameLifecycleEvent::Announcement { name, values });
+    }
+
     async fn admin_cmd_start_game(&mut self) {
         if matches!(self.phase, GamePhase::Setup | GamePhase::GameOver { .. }) {
             self.initialize_game_board();
@@ -423,7 +586,7 @@ impl DealNoDealGame {
                     self.player_chosen_case_index = Some(selected_idx);
                     self.current_round_schedule_index = 0;
 
-                    let cases_to_open_first_round = ROUND_SCHEDULE[0];
+                    let cases_to_open_first_round = self.round_schedule[0];
                     if cases_to_open_first_round == 0 {
                         self.phase = GamePhase::BankerOfferCalculation { round_number: 1 };
                         let offer = self.calculate_banker_offer();
@@ -435,17 +598,17 @@ impl DealNoDealGame {
                             round_number: 1,
                             offer,
                         };
-                        self.current_votes_by_user.clear();
+                        self.clear_votes();
                     } else {
                         self.phase = GamePhase::RoundCaseOpeningVoting {
                             round_number: 1,
                             total_to_open_for_round: cases_to_open_first_round,
                             opened_so_far_for_round: 0,
                         };
-                        self.current_votes_by_user.clear();
+                        self.clear_votes();
                     }
                 } else {
-                    self.current_votes_by_user.clear();
+                    self.clear_votes();
                     tracing::warn!(
                         "No valid player case selected. Awaiting more votes or re-concluding"
                     );
@@ -487,14 +650,14 @@ impl DealNoDealGame {
                         round_number,
                         offer,
                     };
-                    self.current_votes_by_user.clear();
+                    self.clear_votes();
                 } else {
                     self.phase = GamePhase::RoundCaseOpeningVoting {
                         round_number,
                         total_to_open_for_round,
                         opened_so_far_for_round,
                     };
-                    self.current_votes_by_user.clear();
+                    self.clear_votes();
                     tracing::warn!(
                         round.number = round_number,
                         cases.target = total_to_open_for_round,
@@ -539,11 +702,7 @@ impl DealNoDealGame {
                         p_case_idx + 1,
                         p_case_val
                     );
-                    self.phase = GamePhase::GameOver {
-                        summary,
-                        winnings: offer,
-                        player_case_original_value: p_case_val,
-                    };
+                    self.finish_game(summary, offer, p_case_val);
                 } else {
                     // NO DEAL
                     self.current_round_schedule_index += 1;
@@ -564,12 +723,13 @@ impl DealNoDealGame {
                         self.phase = GamePhase::SwitchOrKeepVoting {
                             final_case_index: final_case_idx,
                         };
-                        self.current_votes_by_user.clear();
-                    } else if self.current_round_schedule_index >= ROUND_SCHEDULE.len() {
+                        self.clear_votes();
+                    } else if self.current_round_schedule_index >= self.round_schedule.len() {
                         self.end_game_no_deal_final_case().await;
                     } else {
                         let next_display_r_num = round_number + 1;
-                        let cases_to_open_next_round = ROUND_SCHEDULE
+                        let cases_to_open_next_round = self
+                            .round_schedule
                             .get(self.current_round_schedule_index)
                             .copied()
                             .unwrap_or_else(|| {
@@ -590,7 +750,7 @@ impl DealNoDealGame {
                                 total_to_open_for_round: actual_open_for_next_round,
                                 opened_so_far_for_round: 0,
                             };
-                            self.current_votes_by_user.clear();
+                            self.clear_votes();
                         } else {
                             self.end_game_no_deal_final_case().await;
                         }
@@ -680,11 +840,7 @@ impl DealNoDealGame {
                     )
                 };
 
-                self.phase = GamePhase::GameOver {
-                    summary,
-                    winnings,
-                    player_case_original_value: p_case_val,
-                };
+                self.finish_game(summary, winnings, p_case_val);
             }
             _ => {
                 tracing::warn!(
@@ -734,10 +890,19 @@ impl DealNoDealGame {
             p_case_idx + 1,
             p_case_val
         );
+        self.finish_game(summary, p_case_val, p_case_val);
+    }
+
+    fn finish_game(&mut self, summary: String, winnings: u64, player_case_original_value: u64) {
+        self.lifecycle_events
+            .push(GameLifecycleEvent::GameOver(GameResult::Winnings {
+                winnings,
+                summary: summary.clone(),
+            }));
         self.phase = GamePhase::GameOver {
             summary,
-            winnings: p_case_val,
-            player_case_original_value: p_case_val,
+            winnings,
+            player_case_original_value,
         };
     }
 
@@ -749,7 +914,7 @@ impl DealNoDealGame {
         match current_game_phase {
             GamePhase::PlayerCaseSelectionVoting | GamePhase::RoundCaseOpeningVoting { .. } => {
                 if let Ok(case_id_1_based) = vote_text.parse::<u8>()
-                    && (1..=TOTAL_CASES).contains(&case_id_1_based)
+                    && (1..=self.case_count()).contains(&case_id_1_based)
                 {
                     let case_idx_0_based = (case_id_1_based - 1) as usize;
                     let is_player_sel_phase =
@@ -813,16 +978,24 @@ impl GameLogic for DealNoDealGame {
                         game.type_id = %game_type_id,
                         "Received command for wrong game_type_id"
                     );
-                    return EventHandlingResult::Handled;
+                    return EventHandlingResult::Rejected {
+                        reason: format!("Command is for game type '{}'", game_type_id),
+                    };
                 }
                 match serde_json::from_value::<AdminCommand>(command_data) {
                     Ok(cmd) => {
+                        if let Err(reason) = self.check_command(&cmd) {
+                            tracing::debug!(command = ?cmd, reason = %reason, "Rejected command");
+                            return EventHandlingResult::Rejected { reason };
+                        }
+                        let previous_phase = self.phase.clone();
                         match cmd {
                             AdminCommand::StartGame => self.admin_cmd_start_game().await,
                             AdminCommand::ConcludeVotingAndProcess => {
                                 self.admin_cmd_conclude_voting().await
                             }
                         }
+                        self.announce_vote_opened(&previous_phase);
                         self.broadcast_full_state_update_internal().await;
                     }
                     Err(e) => {
@@ -831,6 +1004,9 @@ impl GameLogic for DealNoDealGame {
                             "Failed to deserialize AdminCommand"
                         );
                         self.broadcast_full_state_update_internal().await;
+                        return EventHandlingResult::Rejected {
+                            reason: format!("Invalid command: {}", e),
+                        };
                     }
                 }
             }
@@ -851,6 +1027,11 @@ impl GameLogic for DealNoDealGame {
         EventHandlingResult::Handled
     }
 
+    fn apply_lobby_settings(&mut self, settings: Option<serde_json::Value>) -> Result<(), String> {
+        let _settings: DealNoDealLobbySettings = parse_lobby_settings(settings)?;
+        Ok(())
+    }
+
     async fn handle_twitch_message(&mut self, message: ParsedTwitchMessage) {
         let current_phase_clone = self.phase.clone();
         let is_voting_active_phase = matches!(
@@ -869,10 +1050,12 @@ impl GameLogic for DealNoDealGame {
             self.validate_and_parse_twitch_vote(message.text.trim(), &current_phase_clone);
 
         if is_valid && let Some(vote_value_str) = parsed_vote_value_opt {
+            let source = message.message_ref();
             let voter_username = message.sender_username;
 
             self.current_votes_by_user
                 .insert(voter_username.clone(), vote_value_str.clone());
+            self.vote_messages.insert(voter_username.clone(), source);
 
             self.broadcast_game_event_to_all_admins(GameEvent::PlayerVoteRegistered {
                 voter_username,
@@ -882,6 +1065,84 @@ impl GameLogic for DealNoDealGame {
         }
     }
 
+    async fn handle_chat_moderation(&mut self, moderation: ChatModeration) {
+        let voided = self.void_removed_votes(&moderation);
+        if voided == 0 {
+            return;
+        }
+        tracing::info!(
+            votes.voided = voided,
+            "Voided votes from messages removed by a moderator"
+        );
+        self.broadcast_full_state_update_internal().await;
+    }
+
+    fn phase_name(&self) -> &'static str {
+        match self.phase {
+            GamePhase::Setup => PHASES[0],
+            GamePhase::PlayerCaseSelectionVoting => PHASES[1],
+            GamePhase::RoundCaseOpeningVoting { .. } => PHASES[2],
+            GamePhase::BankerOfferCalculation { .. } => PHASES[3],
+            GamePhase::DealOrNoDealVoting { .. } => PHASES[4],
+            GamePhase::SwitchOrKeepVoting { .. } => PHASES[5],
+            GamePhase::GameOver { .. } => PHASES[6],
+        }
+    }
+
+    fn drain_lifecycle_events(&mut self) -> Vec<GameLifecycleEvent> {
+        std::mem::take(&mut self.lifecycle_events)
+    }
+
+    fn state_snapshot(&self) -> serde_json::Value {
+        let mut state_for_client = self.clone();
+        state_for_client.prepare_for_client_view();
+        serde_json::json!({
+            "event_type": "FullStateUpdate",
+            "data": state_for_client
+        })
+    }
+
+    fn redact_public_event(event_data: &mut serde_json::Value) {
+        // Unopened case values stay hidden; opened ones are already public.
+        let Some(data) = event_data.get_mut("data") else {
+            return;
+        };
+        let opened: Vec<bool> = data
+            .get("briefcase_is_opened")
+            .and_then(|v| v.as_array())
+            .map(|flags| flags.iter().map(|f| f.as_bool().unwrap_or(false)).collect())
+            .unwrap_or_default();
+        if let Some(values) = data
+            .get_mut("briefcase_values")
+            .and_then(|v| v.as_array_mut())
+        {
+            for (index, value) in values.iter_mut().enumerate() {
+                if !opened.get(index).copied().unwrap_or(false) {
+                    *value = serde_json::Value::Null;
+                }
+            }
+        }
+    }
+
+    fn command_permission(
+        &self,
+        command_data: &serde_json::Value,
+    ) -> Result<HostPermission, String> {
+        serde_json::from_value::<AdminCommand>(command_data.clone())
+            .map_err(|e| format!("Invalid DealNoDeal command: {}", e))?;
+        // Starting (or restarting) the board and concluding votes both drive the game forward.
+        Ok(HostPermission::ControlGame)
+    }
+
+    fn supports_scheduled_start(&self) -> bool {
+        true
+    }
+
+    async fn start_game(&mut self) {
+        self.admin_cmd_start_game().await;
+        self.broadcast_full_state_update_internal().await;
+    }
+
     fn is_empty(&self) -> bool {
         self.clients.is_empty()
     }
@@ -898,3 +1159,74 @@ impl GameLogic for DealNoDealGame {
         self.clients.keys().copied().collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn admin_command(command: &str) -> GenericClientToServerMessage {
+        GenericClientToServerMessage::GameSpecificCommand {
+            game_type_id: GAME_TYPE_ID_DND.to_string(),
+            command_data: serde_json::json!({ "command": command }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_opening_a_vote_queues_its_announcement() {
+        let mut game = DealNoDealGame::new(&DealNoDealDefaults::default());
+
+        game.handle_event(Uuid::new_v4(), admin_command("StartGame"))
+            .await;
+
+        let names: Vec<&str> = game
+            .drain_lifecycle_events()
+            .iter()
+            .map(GameLifecycleEvent::name)
+            .collect();
+        assert_eq!(names, ["game_started", "case_selection_opened"]);
+    }
+
+    #[tokio::test]
+    async fn test_removed_votes_are_left_out_of_the_tally() {
+        let mut game = DealNoDealGame::new(&DealNoDealDefaults::default());
+        game.handle_event(Uuid::new_v4(), admin_command("StartGame"))
+            .await;
+        let vote = |voter: &str, message_id: &str, text: &str| ParsedTwitchMessage {
+            channel: "channel".to_string(),
+            sender_username: voter.to_string(),
+            sender_user_id: None,
+            text: text.to_string(),
+            badges: None,
+            is_moderator: false,
+            is_subscriber: false,
+            is_vip: false,
+            is_broadcaster: false,
+            bits: 0,
+            is_first_message: false,
+            is_returning_chatter: false,
+            message_id: Some(message_id.to_string()),
+            reply_parent: None,
+            raw_irc_tags: None,
+            timestamp: chrono::Utc::now(),
+        };
+
+        game.handle_twitch_message(vote("Alice", "m1", "5")).await;
+        game.handle_twitch_message(vote("Bob", "m2", "5")).await;
+        game.handle_twitch_message(vote("Carol", "m3", "9")).await;
+        game.handle_chat_moderation(ChatModeration::MessageDeleted {
+            message_id: "m1".to_string(),
+            login: "alice".to_string(),
+        })
+        .await;
+        game.handle_chat_moderation(ChatModeration::UserCleared {
+            user_id: None,
+            login: "bob".to_string(),
+            ban_duration_seconds: Some(600),
+        })
+        .await;
+        game.handle_event(Uuid::new_v4(), admin_command("ConcludeVotingAndProcess"))
+            .await;
+
+        assert_eq!(game.player_chosen_case_index, Some(8));
+    }
+}
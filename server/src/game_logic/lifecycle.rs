@@ -0,0 +1,80 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::clip_queue::ClipInfo;
+
+/// Milestones a game reports to integrations outside the lobby (webhooks). Games
+/// queue them while handling events and the lobby actor drains them afterwards.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum GameLifecycleEvent {
+    GameStarted,
+    PlayerScored {
+        player: String,
+        points: u32,
+    },
+    GameOver(GameResult),
+    ClipAdded {
+        clip: ClipInfo,
+    },
+    /// A game-specific moment for Twitch chat, e.g. Deal or No Deal's
+    /// `banker_offer`. Only announced when the game type has a template for
+    /// `name`, whose placeholders `values` fill; never sent to webhooks.
+    Announcement {
+        name: &'static str,
+        values: Vec<(&'static str, String)>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum GameResult {
+    Scores {
+        winner: String,
+        player_scores: HashMap<String, u32>,
+    },
+    Winnings {
+        winnings: u64,
+        summary: String,
+    },
+}
+
+impl GameLifecycleEvent {
+    pub const NAMES: [&'static str; 4] =
+        ["game_started", "player_scored", "game_over", "clip_added"];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameLifecycleEvent::GameStarted => "game_started",
+            GameLifecycleEvent::PlayerScored { .. } => "player_scored",
+            GameLifecycleEvent::GameOver(_) => "game_over",
+            GameLifecycleEvent::ClipAdded { .. } => "clip_added",
+            GameLifecycleEvent::Announcement { name, .. } => name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_serializes_event_name_alongside_data() {
+        let event = GameLifecycleEvent::GameOver(GameResult::Scores {
+            winner: "alice".to_string(),
+            player_scores: HashMap::from([("alice".to_string(), 10)]),
+        });
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "event": "game_over",
+                "data": { "winner": "alice", "player_scores": { "alice": 10 } }
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(GameLifecycleEvent::GameStarted).unwrap(),
+            json!({ "event": "game_started" })
+        );
+    }
+}
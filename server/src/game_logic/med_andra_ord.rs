@@ -8,16 +8,26 @@ use std::time::Instant;
 use tokio::sync::mpsc::Sender as TokioMpscSender;
 use uuid::Uuid;
 
+use crate::cohost::HostPermission;
+use crate::config::ScoringGameDefaults;
 use crate::game_logic::messages::{
     ClientToServerMessage as GenericClientToServerMessage,
     ServerToClientMessage as GenericServerToClientMessage,
 };
-use crate::game_logic::utils::is_guess_acceptable;
-use crate::game_logic::{EventHandlingResult, GameLogic};
-use crate::twitch::ParsedTwitchMessage;
+use crate::game_logic::settings::{
+    parse_lobby_settings, validate_game_duration, validate_target_points,
+};
+use crate::game_logic::utils::{
+    ScoredMessage, is_guess_acceptable, redact_json_pointers, void_removed_guesses,
+};
+use crate::game_logic::{EventHandlingResult, GameLifecycleEvent, GameLogic, GameResult};
+use crate::twitch::{ChatMessageRef, ChatModeration, ParsedTwitchMessage};
 
 const GAME_TYPE_ID_MED_ANDRA_ORD: &str = "MedAndraOrd";
 
+/// Names reported by `phase_name`, in play order.
+pub const PHASES: [&str; 3] = ["Setup", "Playing", "GameOver"];
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RecentGuess {
     pub id: String,
@@ -40,6 +50,16 @@ pub enum MedAndraOrdAdminCommand {
     RemoveRecentGuess { guess_id: String },
 }
 
+/// Initial settings accepted by `create-lobby` for a MedAndraOrd lobby.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct MedAndraOrdLobbySettings {
+    pub target_points: Option<u32>,
+    pub game_duration_seconds: Option<u32>,
+    pub point_limit_enabled: Option<bool>,
+    pub time_limit_enabled: Option<bool>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "event_type", content = "data")]
 pub enum MedAndraOrdEvent {
@@ -77,6 +97,12 @@ pub struct MedAndraOrdGame {
     local_used_words: HashSet<String>,
     #[serde(skip)]
     game_start_time: Option<Instant>,
+    #[serde(skip)]
+    lifecycle_events: Vec<GameLifecycleEvent>,
+    #[serde(skip)]
+    recent_guesses_limit: usize,
+    #[serde(skip)]
+    scored_messages: Vec<ScoredMessage>,
 }
 
 impl Clone for MedAndraOrdGame {
@@ -93,24 +119,30 @@ impl Clone for MedAndraOrdGame {
             current_word_list: Arc::clone(&self.current_word_list),
             local_used_words: self.local_used_words.clone(),
             game_start_time: self.game_start_time,
+            lifecycle_events: Vec::new(),
+            recent_guesses_limit: self.recent_guesses_limit,
+            scored_messages: self.scored_messages.clone(),
         }
     }
 }
 
 impl MedAndraOrdGame {
-    pub fn new(word_list_snapshot: Arc<Vec<String>>) -> Self {
+    pub fn new(word_list_snapshot: Arc<Vec<String>>, defaults: &ScoringGameDefaults) -> Self {
         Self {
             clients: HashMap::new(),
             phase: MedAndraOrdPhase::Setup,
-            target_points: 10,
-            game_duration_seconds: 300,
-            point_limit_enabled: true,
-            time_limit_enabled: false,
+            target_points: defaults.target_points,
+            game_duration_seconds: u64::from(defaults.game_duration_seconds),
+            point_limit_enabled: defaults.point_limit_enabled,
+            time_limit_enabled: defaults.time_limit_enabled,
             player_scores: HashMap::new(),
             recent_guesses: Vec::new(),
             current_word_list: word_list_snapshot,
             local_used_words: HashSet::new(),
             game_start_time: None,
+            lifecycle_events: Vec::new(),
+            recent_guesses_limit: defaults.recent_guesses_limit,
+            scored_messages: Vec::new(),
         }
     }
 
@@ -227,8 +259,11 @@ impl MedAndraOrdGame {
             .map(|(player, _)| player.clone())
             .unwrap_or_else(|| "No players".to_string());
 
-        self.phase = MedAndraOrdPhase::GameOver { winner };
+        self.phase = MedAndraOrdPhase::GameOver {
+            winner: winner.clone(),
+        };
         self.game_start_time = None;
+        self.record_game_over(winner);
 
         self.broadcast_game_event_to_all(MedAndraOrdEvent::MedAndraOrdPhaseChanged {
             new_phase: self.phase.clone(),
@@ -238,6 +273,14 @@ impl MedAndraOrdGame {
         tracing::info!("Game ended due to time expiration");
     }
 
+    fn record_game_over(&mut self, winner: String) {
+        self.lifecycle_events
+            .push(GameLifecycleEvent::GameOver(GameResult::Scores {
+                winner,
+                player_scores: self.player_scores.clone(),
+            }));
+    }
+
     async fn handle_start_game(&mut self) {
         if self.phase != MedAndraOrdPhase::Setup {
             return;
@@ -245,7 +288,9 @@ impl MedAndraOrdGame {
 
         // Only clear player scores, not used words - preserve used words across multiple games
         self.player_scores.clear();
+        self.scored_messages.clear();
         self.game_start_time = Some(Instant::now());
+        self.lifecycle_events.push(GameLifecycleEvent::GameStarted);
 
         if let Some(word) = self.get_next_word() {
             self.phase = MedAndraOrdPhase::Playing {
@@ -310,6 +355,7 @@ impl MedAndraOrdGame {
     async fn handle_reset_game(&mut self) {
         self.phase = MedAndraOrdPhase::Setup;
         self.player_scores.clear();
+        self.scored_messages.clear();
         self.local_used_words.clear();
         self.recent_guesses.clear();
         self.game_start_time = None;
@@ -320,6 +366,42 @@ impl MedAndraOrdGame {
         .await;
     }
 
+    /// A round starts only from setup, words can only be passed while it runs and
+    /// the limits are locked once it starts.
+    fn check_command(&self, command: &MedAndraOrdAdminCommand) -> Result<(), String> {
+        match command {
+            MedAndraOrdAdminCommand::StartGame if self.phase != MedAndraOrdPhase::Setup => {
+                Err("The game can only be started from setup; reset it first".to_string())
+            }
+            MedAndraOrdAdminCommand::PassWord
+                if !matches!(self.phase, MedAndraOrdPhase::Playing { .. }) =>
+            {
+                Err("There is no word to pass outside of a running game".to_string())
+            }
+            MedAndraOrdAdminCommand::SetTargetPoints { .. }
+            | MedAndraOrdAdminCommand::SetGameDuration { .. }
+            | MedAndraOrdAdminCommand::SetPointLimitEnabled { .. }
+            | MedAndraOrdAdminCommand::SetTimeLimitEnabled { .. }
+                if self.phase != MedAndraOrdPhase::Setup =>
+            {
+                Err("Settings can only be changed before the game starts".to_string())
+            }
+            MedAndraOrdAdminCommand::SetTargetPoints { points } => validate_target_points(*points),
+            MedAndraOrdAdminCommand::SetGameDuration { seconds } => {
+                validate_game_duration(*seconds)
+            }
+            MedAndraOrdAdminCommand::RemoveRecentGuess { guess_id }
+                if !self
+                    .recent_guesses
+                    .iter()
+                    .any(|guess| guess.id == *guess_id) =>
+            {
+                Err(format!("No recent guess with id '{}'", guess_id))
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn handle_set_target_points(&mut self, points: u32) {
         if self.phase == MedAndraOrdPhase::Setup {
             self.target_points = points;
@@ -345,14 +427,16 @@ impl MedAndraOrdGame {
     }
 
     /// Adds a correct guess to the recent guesses list, maintaining a maximum of 5 entries.
-    fn add_recent_guess(&mut self, player: &str, guessed_text: &str, correct_word: &str) {
+    /// Returns the id of the new entry.
+    fn add_recent_guess(&mut self, player: &str, guessed_text: &str, correct_word: &str) -> String {
         let timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs();
 
+        let guess_id = uuid::Uuid::new_v4().to_string();
         let guess = RecentGuess {
-            id: uuid::Uuid::new_v4().to_string(),
+            id: guess_id.clone(),
             player: player.to_string(),
             guessed_text: guessed_text.to_string(),
             correct_word: correct_word.to_string(),
@@ -362,16 +446,16 @@ impl MedAndraOrdGame {
         // Add to front of list
         self.recent_guesses.insert(0, guess);
 
-        // Keep only the 5 most recent
-        if self.recent_guesses.len() > 5 {
-            self.recent_guesses.truncate(5);
-        }
+        self.recent_guesses.truncate(self.recent_guesses_limit);
+        guess_id
     }
 
     /// Removes a recent guess by ID and deducts one point from the player.
     async fn handle_remove_recent_guess(&mut self, guess_id: &str) {
         if let Some(pos) = self.recent_guesses.iter().position(|g| g.id == guess_id) {
             let removed_guess = self.recent_guesses.remove(pos);
+            self.scored_messages
+                .retain(|scored| scored.guess_id != guess_id);
 
             // Deduct point from player
             if let Some(current_score) = self.player_scores.get_mut(&removed_guess.player)
@@ -395,6 +479,19 @@ impl MedAndraOrdGame {
         }
     }
 
+    /// Removes the points and recent guesses earned by messages `moderation`
+    /// removed, returning how many guesses were voided.
+    fn void_removed_guesses(&mut self, moderation: &ChatModeration) -> usize {
+        let voided = void_removed_guesses(
+            &mut self.scored_messages,
+            &mut self.player_scores,
+            moderation,
+        );
+        self.recent_guesses
+            .retain(|guess| !voided.contains(&guess.id));
+        voided.len()
+    }
+
     fn get_next_word(&mut self) -> Option<String> {
         if self.current_word_list.is_empty() {
             tracing::warn!("Word list is empty, cannot get next word");
@@ -423,6 +520,7 @@ impl MedAndraOrdGame {
         player: &str,
         guessed_text: &str,
         correct_word: &str,
+        source: ChatMessageRef,
     ) {
         if self.check_game_time_expired() {
             self.end_game_time_expired().await;
@@ -432,8 +530,18 @@ impl MedAndraOrdGame {
         let current_score = self.player_scores.entry(player.to_string()).or_insert(0);
         *current_score += 1;
         let new_score = *current_score;
-
-        self.add_recent_guess(player, guessed_text, correct_word);
+        self.lifecycle_events
+            .push(GameLifecycleEvent::PlayerScored {
+                player: player.to_string(),
+                points: new_score,
+            });
+
+        let guess_id = self.add_recent_guess(player, guessed_text, correct_word);
+        self.scored_messages.push(ScoredMessage {
+            message: source,
+            player: player.to_string(),
+            guess_id,
+        });
 
         self.broadcast_game_event_to_all(MedAndraOrdEvent::PlayerScored {
             player: player.to_string(),
@@ -451,6 +559,7 @@ impl MedAndraOrdGame {
             self.phase = MedAndraOrdPhase::GameOver {
                 winner: player.to_string(),
             };
+            self.record_game_over(player.to_string());
             self.broadcast_game_event_to_all(MedAndraOrdEvent::MedAndraOrdPhaseChanged {
                 new_phase: self.phase.clone(),
             })
@@ -514,11 +623,17 @@ impl GameLogic for MedAndraOrdGame {
                         game.type_id = %game_type_id,
                         "Wrong game_type_id"
                     );
-                    return EventHandlingResult::Handled;
+                    return EventHandlingResult::Rejected {
+                        reason: format!("Command is for game type '{}'", game_type_id),
+                    };
                 }
 
                 match serde_json::from_value::<MedAndraOrdAdminCommand>(command_data) {
                     Ok(cmd) => {
+                        if let Err(reason) = self.check_command(&cmd) {
+                            tracing::debug!(command = ?cmd, reason = %reason, "Rejected command");
+                            return EventHandlingResult::Rejected { reason };
+                        }
                         match cmd {
                             MedAndraOrdAdminCommand::StartGame => self.handle_start_game().await,
                             MedAndraOrdAdminCommand::PassWord => self.handle_pass_word().await,
@@ -546,6 +661,9 @@ impl GameLogic for MedAndraOrdGame {
                             error = %e,
                             "Failed to deserialize command"
                         );
+                        return EventHandlingResult::Rejected {
+                            reason: format!("Invalid command: {}", e),
+                        };
                     }
                 }
             }
@@ -566,6 +684,31 @@ impl GameLogic for MedAndraOrdGame {
         EventHandlingResult::Handled
     }
 
+    fn apply_lobby_settings(&mut self, settings: Option<serde_json::Value>) -> Result<(), String> {
+        let settings: MedAndraOrdLobbySettings = parse_lobby_settings(settings)?;
+
+        if let Some(points) = settings.target_points {
+            validate_target_points(points)?;
+        }
+        if let Some(seconds) = settings.game_duration_seconds {
+            validate_game_duration(seconds)?;
+        }
+
+        if let Some(points) = settings.target_points {
+            self.handle_set_target_points(points);
+        }
+        if let Some(seconds) = settings.game_duration_seconds {
+            self.handle_set_game_duration(seconds);
+        }
+        if let Some(enabled) = settings.point_limit_enabled {
+            self.handle_set_point_limit_enabled(enabled);
+        }
+        if let Some(enabled) = settings.time_limit_enabled {
+            self.handle_set_time_limit_enabled(enabled);
+        }
+        Ok(())
+    }
+
     async fn handle_twitch_message(&mut self, message: ParsedTwitchMessage) {
         if let MedAndraOrdPhase::Playing { current_word } = &self.phase {
             if self.check_game_time_expired() {
@@ -588,13 +731,93 @@ impl GameLogic for MedAndraOrdGame {
                     "Correct guess"
                 );
                 self.local_used_words.insert(word.clone());
-                self.process_correct_guess(&message.sender_username, guess, &word)
-                    .await;
+                self.process_correct_guess(
+                    &message.sender_username,
+                    guess,
+                    &word,
+                    message.message_ref(),
+                )
+                .await;
                 self.broadcast_full_state_update().await;
             }
         }
     }
 
+    async fn handle_chat_moderation(&mut self, moderation: ChatModeration) {
+        let voided = self.void_removed_guesses(&moderation);
+        if voided == 0 {
+            return;
+        }
+        tracing::info!(
+            guesses.voided = voided,
+            "Voided guesses from messages removed by a moderator"
+        );
+        self.broadcast_game_event_to_all(MedAndraOrdEvent::RecentGuessesUpdated {
+            recent_guesses: self.recent_guesses.clone(),
+        })
+        .await;
+        self.broadcast_full_state_update().await;
+    }
+
+    fn phase_name(&self) -> &'static str {
+        match self.phase {
+            MedAndraOrdPhase::Setup => PHASES[0],
+            MedAndraOrdPhase::Playing { .. } => PHASES[1],
+            MedAndraOrdPhase::GameOver { .. } => PHASES[2],
+        }
+    }
+
+    fn drain_lifecycle_events(&mut self) -> Vec<GameLifecycleEvent> {
+        std::mem::take(&mut self.lifecycle_events)
+    }
+
+    fn state_snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "event_type": "FullStateUpdate",
+            "data": self
+        })
+    }
+
+    fn redact_public_event(event_data: &mut serde_json::Value) {
+        // Chat has to guess the word, so only hosts may see it.
+        redact_json_pointers(
+            event_data,
+            &[
+                "/data/word",
+                "/data/new_phase/data/current_word",
+                "/data/phase/data/current_word",
+            ],
+        );
+    }
+
+    fn command_permission(
+        &self,
+        command_data: &serde_json::Value,
+    ) -> Result<HostPermission, String> {
+        let command = serde_json::from_value::<MedAndraOrdAdminCommand>(command_data.clone())
+            .map_err(|e| format!("Invalid MedAndraOrd command: {}", e))?;
+        Ok(match command {
+            MedAndraOrdAdminCommand::StartGame | MedAndraOrdAdminCommand::PassWord => {
+                HostPermission::ControlGame
+            }
+            MedAndraOrdAdminCommand::RemoveRecentGuess { .. } => HostPermission::Moderate,
+            MedAndraOrdAdminCommand::SetTargetPoints { .. }
+            | MedAndraOrdAdminCommand::SetGameDuration { .. }
+            | MedAndraOrdAdminCommand::SetPointLimitEnabled { .. }
+            | MedAndraOrdAdminCommand::SetTimeLimitEnabled { .. } => HostPermission::ChangeSettings,
+            MedAndraOrdAdminCommand::ResetGame => HostPermission::ResetGame,
+        })
+    }
+
+    fn supports_scheduled_start(&self) -> bool {
+        true
+    }
+
+    async fn start_game(&mut self) {
+        self.handle_start_game().await;
+        self.broadcast_full_state_update().await;
+    }
+
     fn is_empty(&self) -> bool {
         self.clients.is_empty()
     }
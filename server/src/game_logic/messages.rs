@@ -7,7 +7,13 @@ use uuid::Uuid;
 #[serde(tag = "messageType", content = "payload")]
 pub enum ClientToServerMessage {
     /// Sent by the client immediately after WebSocket connection to associate with a lobby.
-    ConnectToLobby { lobby_id: Uuid },
+    /// `admin_token` is the lobby's `admin_id` or a co-host token; without one the
+    /// client can watch but not send game commands.
+    ConnectToLobby {
+        lobby_id: Uuid,
+        #[serde(default)]
+        admin_token: Option<Uuid>,
+    },
     /// Sent by the client to explicitly leave the lobby and close the connection.
     /// This indicates the user intentionally wants to be removed from the lobby.
     LeaveLobby,
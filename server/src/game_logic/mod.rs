@@ -1,9 +1,11 @@
 use axum::extract::ws;
+use serde_json::Value as JsonValue;
 use std::{fmt::Debug, future::Future};
 use tokio::sync::mpsc::Sender as TokioMpscSender;
 use uuid::Uuid;
 
-use crate::twitch::ParsedTwitchMessage;
+use crate::cohost::HostPermission;
+use crate::twitch::{ChatModeration, ParsedTwitchMessage, UserNotice};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum EventHandlingResult {
@@ -11,11 +13,18 @@ pub enum EventHandlingResult {
     Handled,
     /// Client should be disconnected (e.g., due to LeaveLobby request)
     DisconnectClient,
+    /// The command was not applied (unknown, or not valid in the current phase);
+    /// `reason` is reported back to whoever sent it
+    Rejected { reason: String },
 }
 
 pub mod messages;
 pub use messages::{ClientToServerMessage, ServerToClientMessage};
 
+pub mod lifecycle;
+pub use lifecycle::{GameLifecycleEvent, GameResult};
+
+pub mod settings;
 pub mod utils;
 
 pub mod clip_queue;
@@ -28,6 +37,10 @@ pub use deal_no_deal::DealNoDealGame;
 pub use med_andra_ord::MedAndraOrdGame;
 pub use quiz::QuizGame;
 
+/// The `phase_name` of a game that has not started yet. Scheduled starts are only
+/// accepted and only fire while the game reports it.
+pub const SETUP_PHASE: &str = "Setup";
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum GameType {
     DealNoDeal,
@@ -66,6 +79,34 @@ impl GameType {
     pub fn primary_id(&self) -> &'static str {
         self.aliases()[0]
     }
+
+    /// Names of the game's own chat announcements, which can be given templates
+    /// under `twitch.chat.games.<primary id>`.
+    pub fn announcement_names(&self) -> &'static [&'static str] {
+        match self {
+            GameType::DealNoDeal => &deal_no_deal::ANNOUNCEMENTS,
+            GameType::Quiz => &quiz::ANNOUNCEMENTS,
+            GameType::MedAndraOrd | GameType::ClipQueue => &[],
+        }
+    }
+
+    /// Every name the game's `GameLogic::phase_name` can report.
+    pub fn phase_names(&self) -> &'static [&'static str] {
+        match self {
+            GameType::DealNoDeal => &deal_no_deal::PHASES,
+            GameType::MedAndraOrd => &med_andra_ord::PHASES,
+            GameType::ClipQueue => &clip_queue::PHASES,
+            GameType::Quiz => &quiz::PHASES,
+        }
+    }
+
+    /// Resolves a requested game type (any alias, case-insensitive) to a `GameType`.
+    pub fn from_alias(requested: &str) -> Option<Self> {
+        let normalized = requested.trim().to_lowercase();
+        Self::all()
+            .into_iter()
+            .find(|game_type| game_type.aliases().contains(&normalized.as_str()))
+    }
 }
 
 pub trait GameLogic: Send + Sync + Debug {
@@ -83,11 +124,59 @@ pub trait GameLogic: Send + Sync + Debug {
         message: ClientToServerMessage,
     ) -> impl Future<Output = EventHandlingResult> + Send;
 
+    /// Applies the initial settings sent with `create-lobby`. Called before the lobby
+    /// actor is spawned; an error rejects the lobby creation request.
+    fn apply_lobby_settings(&mut self, settings: Option<JsonValue>) -> Result<(), String>;
+
+    /// Name of the current phase (its serialized `type` tag, e.g. `GameOver`), used to
+    /// trigger per-phase integrations such as OBS scene switching.
+    fn phase_name(&self) -> &'static str;
+
+    /// Takes the lifecycle events (start, score, game over, clip added) queued since
+    /// the last call.
+    fn drain_lifecycle_events(&mut self) -> Vec<GameLifecycleEvent>;
+
+    /// The game's current `FullStateUpdate` event payload, exactly as it would be
+    /// sent to a host client. Pass it through `redact_public_event` for viewers.
+    fn state_snapshot(&self) -> JsonValue;
+
+    /// Strips host-only information (hidden words, answers, case values) from the
+    /// `event_data` of a `GameSpecificEvent` before it reaches viewers.
+    fn redact_public_event(event_data: &mut JsonValue)
+    where
+        Self: Sized;
+
+    /// The host permission required to run a `GameSpecificCommand`, or an error
+    /// describing why `command_data` is not a valid command for this game.
+    fn command_permission(&self, command_data: &JsonValue) -> Result<HostPermission, String>;
+
+    /// Whether the game has a setup phase that a scheduled start can leave.
+    fn supports_scheduled_start(&self) -> bool;
+
+    /// Starts the game exactly like the admin's start command would, used when a
+    /// scheduled start fires.
+    fn start_game(&mut self) -> impl Future<Output = ()> + Send;
+
     fn handle_twitch_message(
         &mut self,
         message: ParsedTwitchMessage,
     ) -> impl Future<Output = ()> + Send;
 
+    /// Takes back whatever chat messages removed by a moderator did in the game:
+    /// guesses and the points they earned, votes, or submitted clips.
+    fn handle_chat_moderation(
+        &mut self,
+        moderation: ChatModeration,
+    ) -> impl Future<Output = ()> + Send;
+
+    /// Reacts to subscriptions, gift subs and raids in the channel. Unlike chat
+    /// moderation, which every game must honour, notices are an optional extra:
+    /// games opt in by overriding this, and by default they are ignored.
+    fn handle_user_notice(&mut self, notice: UserNotice) -> impl Future<Output = ()> + Send {
+        let _ = notice;
+        async {}
+    }
+
     fn is_empty(&self) -> bool;
 
     fn game_type_id(&self) -> String;
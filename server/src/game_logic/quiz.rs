@@ -8,14 +8,21 @@ use std::time::Instant;
 use tokio::sync::mpsc::Sender as TokioMpscSender;
 use uuid::Uuid;
 
+use crate::cohost::HostPermission;
+use crate::config::ScoringGameDefaults;
 use crate::content::{TrivialPursuitData, VemVetMestQuestion};
 use crate::game_logic::messages::{
     ClientToServerMessage as GenericClientToServerMessage,
     ServerToClientMessage as GenericServerToClientMessage,
 };
-use crate::game_logic::utils::is_guess_acceptable;
-use crate::game_logic::{EventHandlingResult, GameLogic};
-use crate::twitch::ParsedTwitchMessage;
+use crate::game_logic::settings::{
+    parse_lobby_settings, validate_game_duration, validate_target_points,
+};
+use crate::game_logic::utils::{
+    ScoredMessage, is_guess_acceptable, redact_json_pointers, void_removed_guesses,
+};
+use crate::game_logic::{EventHandlingResult, GameLifecycleEvent, GameLogic, GameResult};
+use crate::twitch::{ChatMessageRef, ChatModeration, ParsedTwitchMessage};
 
 const GAME_TYPE_ID_QUIZ: &str = "Quiz";
 
@@ -42,6 +49,55 @@ pub enum QuizAdminCommand {
     RemoveRecentGuess { guess_id: String },
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuizQuestionSource {
+    TrivialPursuit,
+    VemVetMest,
+}
+
+impl QuizQuestionSource {
+    pub const ALL: [QuizQuestionSource; 2] = [
+        QuizQuestionSource::TrivialPursuit,
+        QuizQuestionSource::VemVetMest,
+    ];
+
+    /// Name of the source in a streamer's `content_packs`.
+    pub fn content_pack(self) -> &'static str {
+        match self {
+            QuizQuestionSource::TrivialPursuit => "TrivialPursuit",
+            QuizQuestionSource::VemVetMest => "VemVetMest",
+        }
+    }
+
+    /// Whether a streamer with `content_packs` may use this source. An empty
+    /// list allows every source.
+    pub fn is_licensed(self, content_packs: &[String]) -> bool {
+        content_packs.is_empty() || content_packs.iter().any(|pack| pack == self.content_pack())
+    }
+}
+
+/// Chat announcements: `question_changed` fills `{question}` whenever a new
+/// question is shown.
+pub const ANNOUNCEMENTS: [&str; 1] = ["question_changed"];
+
+/// Names reported by `phase_name`, in play order.
+pub const PHASES: [&str; 3] = ["Setup", "Playing", "GameOver"];
+
+/// Initial settings accepted by `create-lobby` for a Quiz lobby.
+///
+/// `question_sources` limits which content packs are drawn from, and `categories`
+/// restricts Vem Vet Mest questions to the given categories (case-insensitive).
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct QuizLobbySettings {
+    pub target_points: Option<u32>,
+    pub game_duration_seconds: Option<u32>,
+    pub point_limit_enabled: Option<bool>,
+    pub time_limit_enabled: Option<bool>,
+    pub question_sources: Option<Vec<QuizQuestionSource>>,
+    pub categories: Option<Vec<String>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "event_type", content = "data")]
 pub enum QuizEvent {
@@ -101,6 +157,12 @@ pub struct QuizGame {
     local_used_vem_vet_mest_indices: HashSet<usize>,
     #[serde(skip)]
     game_start_time: Option<Instant>,
+    #[serde(skip)]
+    lifecycle_events: Vec<GameLifecycleEvent>,
+    #[serde(skip)]
+    recent_guesses_limit: usize,
+    #[serde(skip)]
+    scored_messages: Vec<ScoredMessage>,
 }
 
 impl Clone for QuizGame {
@@ -119,6 +181,9 @@ impl Clone for QuizGame {
             local_used_question_ids: self.local_used_question_ids.clone(),
             local_used_vem_vet_mest_indices: self.local_used_vem_vet_mest_indices.clone(),
             game_start_time: self.game_start_time,
+            lifecycle_events: Vec::new(),
+            recent_guesses_limit: self.recent_guesses_limit,
+            scored_messages: self.scored_messages.clone(),
         }
     }
 }
@@ -127,14 +192,15 @@ impl QuizGame {
     pub fn new(
         trivial_pursuit_data: Option<Arc<TrivialPursuitData>>,
         vem_vet_mest_data: Option<Arc<Vec<VemVetMestQuestion>>>,
+        defaults: &ScoringGameDefaults,
     ) -> Self {
         Self {
             clients: HashMap::new(),
             phase: QuizPhase::Setup,
-            target_points: 10,
-            game_duration_seconds: 300,
-            point_limit_enabled: true,
-            time_limit_enabled: false,
+            target_points: defaults.target_points,
+            game_duration_seconds: u64::from(defaults.game_duration_seconds),
+            point_limit_enabled: defaults.point_limit_enabled,
+            time_limit_enabled: defaults.time_limit_enabled,
             player_scores: HashMap::new(),
             recent_guesses: Vec::new(),
             trivial_pursuit_data,
@@ -142,6 +208,9 @@ impl QuizGame {
             local_used_question_ids: HashSet::new(),
             local_used_vem_vet_mest_indices: HashSet::new(),
             game_start_time: None,
+            lifecycle_events: Vec::new(),
+            recent_guesses_limit: defaults.recent_guesses_limit,
+            scored_messages: Vec::new(),
         }
     }
 
@@ -258,8 +327,11 @@ impl QuizGame {
             .map(|(player, _)| player.clone())
             .unwrap_or_else(|| "No players".to_string());
 
-        self.phase = QuizPhase::GameOver { winner };
+        self.phase = QuizPhase::GameOver {
+            winner: winner.clone(),
+        };
         self.game_start_time = None;
+        self.record_game_over(winner);
 
         self.broadcast_game_event_to_all(QuizEvent::QuizPhaseChanged {
             new_phase: self.phase.clone(),
@@ -269,6 +341,14 @@ impl QuizGame {
         tracing::info!("Game ended due to time expiration");
     }
 
+    fn record_game_over(&mut self, winner: String) {
+        self.lifecycle_events
+            .push(GameLifecycleEvent::GameOver(GameResult::Scores {
+                winner,
+                player_scores: self.player_scores.clone(),
+            }));
+    }
+
     async fn handle_start_game(&mut self) {
         if self.phase != QuizPhase::Setup {
             return;
@@ -276,7 +356,9 @@ impl QuizGame {
 
         // Only clear player scores, not used question IDs - preserve used questions across multiple games
         self.player_scores.clear();
+        self.scored_messages.clear();
         self.game_start_time = Some(Instant::now());
+        self.lifecycle_events.push(GameLifecycleEvent::GameStarted);
 
         if let Some((question, answer, extra_info)) = self.get_next_question() {
             self.phase = QuizPhase::Playing {
@@ -284,6 +366,7 @@ impl QuizGame {
                 current_answer: answer,
                 extra_info,
             };
+            self.announce_question(&question);
             self.broadcast_game_event_to_all(QuizEvent::QuestionChanged {
                 question,
                 is_placeholder: false,
@@ -312,6 +395,14 @@ impl QuizGame {
         }
     }
 
+    fn announce_question(&mut self, question: &str) {
+        self.lifecycle_events
+            .push(GameLifecycleEvent::Announcement {
+                name: ANNOUNCEMENTS[0],
+                values: vec![("question", question.to_string())],
+            });
+    }
+
     async fn handle_pass_question(&mut self) {
         if let QuizPhase::Playing { .. } = &self.phase {
             if self.check_game_time_expired() {
@@ -325,6 +416,7 @@ impl QuizGame {
                     current_answer: answer,
                     extra_info,
                 };
+                self.announce_question(&question);
                 self.broadcast_game_event_to_all(QuizEvent::QuestionChanged {
                     question,
                     is_placeholder: false,
@@ -349,6 +441,7 @@ impl QuizGame {
     async fn handle_reset_game(&mut self) {
         self.phase = QuizPhase::Setup;
         self.player_scores.clear();
+        self.scored_messages.clear();
         self.local_used_question_ids.clear();
         self.local_used_vem_vet_mest_indices.clear();
         self.recent_guesses.clear();
@@ -360,6 +453,38 @@ impl QuizGame {
         .await;
     }
 
+    /// A quiz starts only from setup, questions can only be passed while it runs
+    /// and the limits are locked once it starts.
+    fn check_command(&self, command: &QuizAdminCommand) -> Result<(), String> {
+        match command {
+            QuizAdminCommand::StartGame if self.phase != QuizPhase::Setup => {
+                Err("The game can only be started from setup; reset it first".to_string())
+            }
+            QuizAdminCommand::PassQuestion if !matches!(self.phase, QuizPhase::Playing { .. }) => {
+                Err("There is no question to pass outside of a running game".to_string())
+            }
+            QuizAdminCommand::SetTargetPoints { .. }
+            | QuizAdminCommand::SetGameDuration { .. }
+            | QuizAdminCommand::SetPointLimitEnabled { .. }
+            | QuizAdminCommand::SetTimeLimitEnabled { .. }
+                if self.phase != QuizPhase::Setup =>
+            {
+                Err("Settings can only be changed before the game starts".to_string())
+            }
+            QuizAdminCommand::SetTargetPoints { points } => validate_target_points(*points),
+            QuizAdminCommand::SetGameDuration { seconds } => validate_game_duration(*seconds),
+            QuizAdminCommand::RemoveRecentGuess { guess_id }
+                if !self
+                    .recent_guesses
+                    .iter()
+                    .any(|guess| guess.id == *guess_id) =>
+            {
+                Err(format!("No recent guess with id '{}'", guess_id))
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn handle_set_target_points(&mut self, points: u32) {
         if self.phase == QuizPhase::Setup {
             self.target_points = points;
@@ -385,20 +510,22 @@ impl QuizGame {
     }
 
     /// Adds a correct guess to the recent guesses list, maintaining a maximum of 5 entries.
+    /// Returns the id of the new entry.
     fn add_recent_guess(
         &mut self,
         player: &str,
         guessed_text: &str,
         correct_answer: &str,
         question: &str,
-    ) {
+    ) -> String {
         let timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs();
 
+        let guess_id = uuid::Uuid::new_v4().to_string();
         let guess = RecentGuess {
-            id: uuid::Uuid::new_v4().to_string(),
+            id: guess_id.clone(),
             player: player.to_string(),
             guessed_text: guessed_text.to_string(),
             correct_answer: correct_answer.to_string(),
@@ -409,16 +536,16 @@ impl QuizGame {
         // Add to front of list
         self.recent_guesses.insert(0, guess);
 
-        // Keep only the 5 most recent
-        if self.recent_guesses.len() > 5 {
-            self.recent_guesses.truncate(5);
-        }
+        self.recent_guesses.truncate(self.recent_guesses_limit);
+        guess_id
     }
 
     /// Removes a recent guess by ID and deducts one point from the player.
     async fn handle_remove_recent_guess(&mut self, guess_id: &str) {
         if let Some(pos) = self.recent_guesses.iter().position(|g| g.id == guess_id) {
             let removed_guess = self.recent_guesses.remove(pos);
+            self.scored_messages
+                .retain(|scored| scored.guess_id != guess_id);
 
             // Deduct point from player
             if let Some(current_score) = self.player_scores.get_mut(&removed_guess.player)
@@ -443,6 +570,19 @@ impl QuizGame {
         }
     }
 
+    /// Removes the points and recent guesses earned by messages `moderation`
+    /// removed, returning how many guesses were voided.
+    fn void_removed_guesses(&mut self, moderation: &ChatModeration) -> usize {
+        let voided = void_removed_guesses(
+            &mut self.scored_messages,
+            &mut self.player_scores,
+            moderation,
+        );
+        self.recent_guesses
+            .retain(|guess| !voided.contains(&guess.id));
+        voided.len()
+    }
+
     fn get_next_question(&mut self) -> Option<(String, String, Option<String>)> {
         // Collect available questions from both sources
         enum QuestionSource {
@@ -576,6 +716,7 @@ impl QuizGame {
         guessed_text: &str,
         correct_answer: &str,
         question: &str,
+        source: ChatMessageRef,
     ) {
         if self.check_game_time_expired() {
             self.end_game_time_expired().await;
@@ -585,8 +726,18 @@ impl QuizGame {
         let current_score = self.player_scores.entry(player.to_string()).or_insert(0);
         *current_score += 1;
         let new_score = *current_score;
-
-        self.add_recent_guess(player, guessed_text, correct_answer, question);
+        self.lifecycle_events
+            .push(GameLifecycleEvent::PlayerScored {
+                player: player.to_string(),
+                points: new_score,
+            });
+
+        let guess_id = self.add_recent_guess(player, guessed_text, correct_answer, question);
+        self.scored_messages.push(ScoredMessage {
+            message: source,
+            player: player.to_string(),
+            guess_id,
+        });
 
         // Mark question as used - find it in both sources by matching answer and question
         self.mark_question_as_used(question, correct_answer);
@@ -607,6 +758,7 @@ impl QuizGame {
             self.phase = QuizPhase::GameOver {
                 winner: player.to_string(),
             };
+            self.record_game_over(player.to_string());
             self.broadcast_game_event_to_all(QuizEvent::QuizPhaseChanged {
                 new_phase: self.phase.clone(),
             })
@@ -620,6 +772,7 @@ impl QuizGame {
                 current_answer: answer,
                 extra_info,
             };
+            self.announce_question(&question);
             self.broadcast_game_event_to_all(QuizEvent::QuestionChanged {
                 question,
                 is_placeholder: false,
@@ -674,11 +827,17 @@ impl GameLogic for QuizGame {
                         game.type_id = %game_type_id,
                         "Wrong game_type_id"
                     );
-                    return EventHandlingResult::Handled;
+                    return EventHandlingResult::Rejected {
+                        reason: format!("Command is for game type '{}'", game_type_id),
+                    };
                 }
 
                 match serde_json::from_value::<QuizAdminCommand>(command_data) {
                     Ok(cmd) => {
+                        if let Err(reason) = self.check_command(&cmd) {
+                            tracing::debug!(command = ?cmd, reason = %reason, "Rejected command");
+                            return EventHandlingResult::Rejected { reason };
+                        }
                         match cmd {
                             QuizAdminCommand::StartGame => self.handle_start_game().await,
                             QuizAdminCommand::PassQuestion => self.handle_pass_question().await,
@@ -706,6 +865,9 @@ impl GameLogic for QuizGame {
                             error = %e,
                             "Failed to deserialize command"
                         );
+                        return EventHandlingResult::Rejected {
+                            reason: format!("Invalid command: {}", e),
+                        };
                     }
                 }
             }
@@ -726,6 +888,81 @@ impl GameLogic for QuizGame {
         EventHandlingResult::Handled
     }
 
+    fn apply_lobby_settings(&mut self, settings: Option<serde_json::Value>) -> Result<(), String> {
+        let settings: QuizLobbySettings = parse_lobby_settings(settings)?;
+
+        if let Some(points) = settings.target_points {
+            validate_target_points(points)?;
+        }
+        if let Some(seconds) = settings.game_duration_seconds {
+            validate_game_duration(seconds)?;
+        }
+
+        let mut trivial_pursuit_data = self.trivial_pursuit_data.clone();
+        let mut vem_vet_mest_data = self.vem_vet_mest_data.clone();
+
+        if let Some(sources) = &settings.question_sources {
+            if sources.is_empty() {
+                return Err("question_sources must contain at least one source".to_string());
+            }
+            if !sources.contains(&QuizQuestionSource::TrivialPursuit) {
+                trivial_pursuit_data = None;
+            }
+            if !sources.contains(&QuizQuestionSource::VemVetMest) {
+                vem_vet_mest_data = None;
+            }
+        }
+
+        if let Some(categories) = &settings.categories {
+            let wanted: HashSet<String> = categories
+                .iter()
+                .map(|c| c.trim().to_lowercase())
+                .filter(|c| !c.is_empty())
+                .collect();
+            if !wanted.is_empty() {
+                vem_vet_mest_data = vem_vet_mest_data.map(|questions| {
+                    Arc::new(
+                        questions
+                            .iter()
+                            .filter(|q| {
+                                q.category
+                                    .as_ref()
+                                    .is_some_and(|c| wanted.contains(&c.trim().to_lowercase()))
+                            })
+                            .cloned()
+                            .collect(),
+                    )
+                });
+            }
+        }
+
+        let available_questions = trivial_pursuit_data
+            .as_ref()
+            .map(|tp| tp.cards.iter().map(|card| card.questions.len()).sum())
+            .unwrap_or(0)
+            + vem_vet_mest_data.as_ref().map(|vvm| vvm.len()).unwrap_or(0);
+        if available_questions == 0 {
+            return Err("No quiz questions match the selected content".to_string());
+        }
+
+        self.trivial_pursuit_data = trivial_pursuit_data;
+        self.vem_vet_mest_data = vem_vet_mest_data;
+
+        if let Some(points) = settings.target_points {
+            self.handle_set_target_points(points);
+        }
+        if let Some(seconds) = settings.game_duration_seconds {
+            self.handle_set_game_duration(seconds);
+        }
+        if let Some(enabled) = settings.point_limit_enabled {
+            self.handle_set_point_limit_enabled(enabled);
+        }
+        if let Some(enabled) = settings.time_limit_enabled {
+            self.handle_set_time_limit_enabled(enabled);
+        }
+        Ok(())
+    }
+
     async fn handle_twitch_message(&mut self, message: ParsedTwitchMessage) {
         if let QuizPhase::Playing {
             current_question: _,
@@ -755,14 +992,93 @@ impl GameLogic for QuizGame {
                 } = &self.phase
                 {
                     let question = current_question.clone();
-                    self.process_correct_guess(&message.sender_username, guess, &answer, &question)
-                        .await;
+                    self.process_correct_guess(
+                        &message.sender_username,
+                        guess,
+                        &answer,
+                        &question,
+                        message.message_ref(),
+                    )
+                    .await;
                     self.broadcast_full_state_update().await;
                 }
             }
         }
     }
 
+    async fn handle_chat_moderation(&mut self, moderation: ChatModeration) {
+        let voided = self.void_removed_guesses(&moderation);
+        if voided == 0 {
+            return;
+        }
+        tracing::info!(
+            guesses.voided = voided,
+            "Voided guesses from messages removed by a moderator"
+        );
+        self.broadcast_game_event_to_all(QuizEvent::RecentGuessesUpdated {
+            recent_guesses: self.recent_guesses.clone(),
+        })
+        .await;
+        self.broadcast_full_state_update().await;
+    }
+
+    fn phase_name(&self) -> &'static str {
+        match self.phase {
+            QuizPhase::Setup => PHASES[0],
+            QuizPhase::Playing { .. } => PHASES[1],
+            QuizPhase::GameOver { .. } => PHASES[2],
+        }
+    }
+
+    fn drain_lifecycle_events(&mut self) -> Vec<GameLifecycleEvent> {
+        std::mem::take(&mut self.lifecycle_events)
+    }
+
+    fn state_snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "event_type": "FullStateUpdate",
+            "data": self
+        })
+    }
+
+    fn redact_public_event(event_data: &mut serde_json::Value) {
+        redact_json_pointers(
+            event_data,
+            &[
+                "/data/new_phase/data/current_answer",
+                "/data/phase/data/current_answer",
+            ],
+        );
+    }
+
+    fn command_permission(
+        &self,
+        command_data: &serde_json::Value,
+    ) -> Result<HostPermission, String> {
+        let command = serde_json::from_value::<QuizAdminCommand>(command_data.clone())
+            .map_err(|e| format!("Invalid Quiz command: {}", e))?;
+        Ok(match command {
+            QuizAdminCommand::StartGame | QuizAdminCommand::PassQuestion => {
+                HostPermission::ControlGame
+            }
+            QuizAdminCommand::RemoveRecentGuess { .. } => HostPermission::Moderate,
+            QuizAdminCommand::SetTargetPoints { .. }
+            | QuizAdminCommand::SetGameDuration { .. }
+            | QuizAdminCommand::SetPointLimitEnabled { .. }
+            | QuizAdminCommand::SetTimeLimitEnabled { .. } => HostPermission::ChangeSettings,
+            QuizAdminCommand::ResetGame => HostPermission::ResetGame,
+        })
+    }
+
+    fn supports_scheduled_start(&self) -> bool {
+        true
+    }
+
+    async fn start_game(&mut self) {
+        self.handle_start_game().await;
+        self.broadcast_full_state_update().await;
+    }
+
     fn is_empty(&self) -> bool {
         self.clients.is_empty()
     }
@@ -812,7 +1128,11 @@ mod tests {
         let vvm_data = Arc::new(vec![vvm_question]);
 
         // Create quiz state with both sources
-        let mut quiz_state = QuizGame::new(Some(tp_data), Some(vvm_data));
+        let mut quiz_state = QuizGame::new(
+            Some(tp_data),
+            Some(vvm_data),
+            &ScoringGameDefaults::default(),
+        );
 
         // Get first question - should randomly pick from either source
         let first_question = quiz_state.get_next_question();
@@ -864,7 +1184,7 @@ mod tests {
         });
 
         // Create quiz state with only TP data
-        let mut quiz_state = QuizGame::new(Some(tp_data), None);
+        let mut quiz_state = QuizGame::new(Some(tp_data), None, &ScoringGameDefaults::default());
 
         // Should get the TP question
         let question_result = quiz_state.get_next_question();
@@ -887,7 +1207,7 @@ mod tests {
         let vvm_data = Arc::new(vec![vvm_question]);
 
         // Create quiz state with only VVM data
-        let mut quiz_state = QuizGame::new(None, Some(vvm_data));
+        let mut quiz_state = QuizGame::new(None, Some(vvm_data), &ScoringGameDefaults::default());
 
         // Should get the VVM question
         let question_result = quiz_state.get_next_question();
@@ -898,6 +1218,79 @@ mod tests {
         assert_eq!(answer, "Stockholm");
     }
 
+    #[test]
+    fn test_lobby_settings_filter_content() {
+        let tp_data = Arc::new(TrivialPursuitData {
+            cards: vec![TrivialPursuitCard {
+                id: 1,
+                questions: vec![TrivialPursuitQuestion {
+                    id: 1,
+                    question: "What is 2+2?".to_string(),
+                    answer: "4".to_string(),
+                    extra_info: None,
+                }],
+            }],
+        });
+        let vvm_data = Arc::new(vec![
+            VemVetMestQuestion {
+                question: "What is the capital of Sweden?".to_string(),
+                answer: "Stockholm".to_string(),
+                category: Some("Geography".to_string()),
+                extra_info: None,
+            },
+            VemVetMestQuestion {
+                question: "Who wrote Pippi Longstocking?".to_string(),
+                answer: "Astrid Lindgren".to_string(),
+                category: Some("Literature".to_string()),
+                extra_info: None,
+            },
+        ]);
+
+        let mut quiz_state = QuizGame::new(
+            Some(tp_data.clone()),
+            Some(vvm_data.clone()),
+            &ScoringGameDefaults::default(),
+        );
+        quiz_state
+            .apply_lobby_settings(Some(serde_json::json!({
+                "target_points": 3,
+                "question_sources": ["VemVetMest"],
+                "categories": ["geography"]
+            })))
+            .unwrap();
+        assert_eq!(quiz_state.target_points, 3);
+        let (question, answer, _) = quiz_state.get_next_question().unwrap();
+        assert_eq!(question, "What is the capital of Sweden?");
+        assert_eq!(answer, "Stockholm");
+
+        let mut quiz_state = QuizGame::new(
+            Some(tp_data),
+            Some(vvm_data),
+            &ScoringGameDefaults::default(),
+        );
+        assert!(
+            quiz_state
+                .apply_lobby_settings(Some(serde_json::json!({
+                    "question_sources": ["VemVetMest"],
+                    "categories": ["History"]
+                })))
+                .is_err()
+        );
+        assert!(
+            quiz_state
+                .apply_lobby_settings(Some(serde_json::json!({ "target_points": 0 })))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_content_packs_license_question_sources() {
+        assert!(QuizQuestionSource::TrivialPursuit.is_licensed(&[]));
+        let packs = vec!["VemVetMest".to_string()];
+        assert!(QuizQuestionSource::VemVetMest.is_licensed(&packs));
+        assert!(!QuizQuestionSource::TrivialPursuit.is_licensed(&packs));
+    }
+
     #[test]
     fn test_quiz_handles_duplicate_question_ids_across_cards() {
         // Create test data with two cards that have questions with the same IDs (1 and 2)
@@ -940,7 +1333,7 @@ mod tests {
             cards: vec![card1, card2],
         });
 
-        let mut quiz_state = QuizGame::new(Some(tp_data), None);
+        let mut quiz_state = QuizGame::new(Some(tp_data), None, &ScoringGameDefaults::default());
 
         // Get 4 questions and verify they are all unique
         let mut questions_seen = std::collections::HashSet::new();
@@ -980,4 +1373,131 @@ mod tests {
             "Should reset and provide questions again when all are used"
         );
     }
+
+    #[test]
+    fn test_public_snapshot_hides_current_answer() {
+        let mut quiz_state = QuizGame::new(None, None, &ScoringGameDefaults::default());
+        quiz_state.phase = QuizPhase::Playing {
+            current_question: "What is the capital of Sweden?".to_string(),
+            current_answer: "Stockholm".to_string(),
+            extra_info: None,
+        };
+
+        let mut snapshot = quiz_state.state_snapshot();
+        assert_eq!(
+            snapshot.pointer("/data/phase/data/current_answer"),
+            Some(&serde_json::json!("Stockholm"))
+        );
+
+        QuizGame::redact_public_event(&mut snapshot);
+        assert_eq!(
+            snapshot.pointer("/data/phase/data/current_answer"),
+            Some(&serde_json::Value::Null)
+        );
+        assert_eq!(
+            snapshot.pointer("/data/phase/data/current_question"),
+            Some(&serde_json::json!("What is the capital of Sweden?"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commands_invalid_for_phase_are_rejected() {
+        let mut quiz_state = QuizGame::new(None, None, &ScoringGameDefaults::default());
+        let command =
+            |command_data: serde_json::Value| GenericClientToServerMessage::GameSpecificCommand {
+                game_type_id: GAME_TYPE_ID_QUIZ.to_string(),
+                command_data,
+            };
+
+        let result = quiz_state
+            .handle_event(
+                Uuid::new_v4(),
+                command(serde_json::json!({ "command": "PassQuestion" })),
+            )
+            .await;
+        assert!(matches!(result, EventHandlingResult::Rejected { .. }));
+
+        let result = quiz_state
+            .handle_event(
+                Uuid::new_v4(),
+                command(serde_json::json!({ "command": "SetTargetPoints", "points": 0 })),
+            )
+            .await;
+        assert!(matches!(result, EventHandlingResult::Rejected { .. }));
+
+        let result = quiz_state
+            .handle_event(
+                Uuid::new_v4(),
+                command(serde_json::json!({ "command": "SetTargetPoints", "points": 7 })),
+            )
+            .await;
+        assert_eq!(result, EventHandlingResult::Handled);
+        assert_eq!(quiz_state.target_points, 7);
+
+        quiz_state.phase = QuizPhase::GameOver {
+            winner: "someone".to_string(),
+        };
+        let result = quiz_state
+            .handle_event(
+                Uuid::new_v4(),
+                command(serde_json::json!({ "command": "StartGame" })),
+            )
+            .await;
+        assert!(matches!(result, EventHandlingResult::Rejected { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_moderation_voids_guess_points() {
+        let mut quiz_state = QuizGame::new(None, None, &ScoringGameDefaults::default());
+        let answer = |quiz_state: &mut QuizGame| {
+            quiz_state.phase = QuizPhase::Playing {
+                current_question: "What is the capital of Sweden?".to_string(),
+                current_answer: "Stockholm".to_string(),
+                extra_info: None,
+            };
+        };
+        let guess = |message_id: &str| ParsedTwitchMessage {
+            channel: "channel".to_string(),
+            sender_username: "Spammer".to_string(),
+            sender_user_id: Some("42".to_string()),
+            text: "Stockholm".to_string(),
+            badges: None,
+            is_moderator: false,
+            is_subscriber: false,
+            is_vip: false,
+            is_broadcaster: false,
+            bits: 0,
+            is_first_message: false,
+            is_returning_chatter: false,
+            message_id: Some(message_id.to_string()),
+            reply_parent: None,
+            raw_irc_tags: None,
+            timestamp: chrono::Utc::now(),
+        };
+
+        for message_id in ["m1", "m2"] {
+            answer(&mut quiz_state);
+            quiz_state.handle_twitch_message(guess(message_id)).await;
+        }
+        assert_eq!(quiz_state.player_scores["Spammer"], 2);
+
+        quiz_state
+            .handle_chat_moderation(ChatModeration::MessageDeleted {
+                message_id: "m2".to_string(),
+                login: "spammer".to_string(),
+            })
+            .await;
+        assert_eq!(quiz_state.player_scores["Spammer"], 1);
+        assert_eq!(quiz_state.recent_guesses.len(), 1);
+
+        quiz_state
+            .handle_chat_moderation(ChatModeration::UserCleared {
+                user_id: None,
+                login: "spammer".to_string(),
+                ban_duration_seconds: None,
+            })
+            .await;
+        assert_eq!(quiz_state.player_scores["Spammer"], 0);
+        assert!(quiz_state.recent_guesses.is_empty());
+    }
 }
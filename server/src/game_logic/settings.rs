@@ -0,0 +1,87 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+
+pub const MIN_TARGET_POINTS: u32 = 1;
+pub const MAX_TARGET_POINTS: u32 = 1_000;
+pub const MIN_GAME_DURATION_SECONDS: u32 = 10;
+pub const MAX_GAME_DURATION_SECONDS: u32 = 24 * 60 * 60;
+pub const MAX_RECENT_GUESSES_LIMIT: usize = 50;
+
+/// Deserializes the optional settings object sent with `create-lobby` into a game's
+/// typed settings struct. A missing or `null` object yields the game's defaults.
+pub fn parse_lobby_settings<T: DeserializeOwned + Default>(
+    settings: Option<JsonValue>,
+) -> Result<T, String> {
+    match settings {
+        None | Some(JsonValue::Null) => Ok(T::default()),
+        Some(value) => {
+            serde_json::from_value(value).map_err(|e| format!("Invalid game settings: {}", e))
+        }
+    }
+}
+
+pub fn validate_target_points(points: u32) -> Result<(), String> {
+    if !(MIN_TARGET_POINTS..=MAX_TARGET_POINTS).contains(&points) {
+        return Err(format!(
+            "target_points must be between {} and {}, got {}",
+            MIN_TARGET_POINTS, MAX_TARGET_POINTS, points
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_game_duration(seconds: u32) -> Result<(), String> {
+    if !(MIN_GAME_DURATION_SECONDS..=MAX_GAME_DURATION_SECONDS).contains(&seconds) {
+        return Err(format!(
+            "game_duration_seconds must be between {} and {}, got {}",
+            MIN_GAME_DURATION_SECONDS, MAX_GAME_DURATION_SECONDS, seconds
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_recent_guesses_limit(limit: usize) -> Result<(), String> {
+    if !(1..=MAX_RECENT_GUESSES_LIMIT).contains(&limit) {
+        return Err(format!(
+            "recent_guesses_limit must be between 1 and {}, got {}",
+            MAX_RECENT_GUESSES_LIMIT, limit
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct ExampleSettings {
+        target_points: Option<u32>,
+    }
+
+    #[test]
+    fn test_missing_settings_use_defaults() {
+        let parsed: ExampleSettings = parse_lobby_settings(None).unwrap();
+        assert_eq!(parsed, ExampleSettings::default());
+        let parsed: ExampleSettings = parse_lobby_settings(Some(JsonValue::Null)).unwrap();
+        assert_eq!(parsed, ExampleSettings::default());
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        let result: Result<ExampleSettings, _> =
+            parse_lobby_settings(Some(serde_json::json!({ "target_point": 5 })));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_limit_validation() {
+        assert!(validate_target_points(10).is_ok());
+        assert!(validate_target_points(0).is_err());
+        assert!(validate_target_points(MAX_TARGET_POINTS + 1).is_err());
+        assert!(validate_game_duration(300).is_ok());
+        assert!(validate_game_duration(5).is_err());
+    }
+}
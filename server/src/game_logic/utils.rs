@@ -2,6 +2,8 @@ use std::cmp::min;
 use std::collections::HashMap;
 use unicode_normalization::UnicodeNormalization;
 
+use crate::twitch::{ChatMessageRef, ChatModeration};
+
 /// Computes the Damerau-Levenshtein distance between two strings,
 /// returning `Some(distance)` if it's less than or equal to a given
 /// threshold, or `None` otherwise.
@@ -261,6 +263,48 @@ pub fn is_guess_acceptable(target_word: &str, guessed_word: &str) -> bool {
     distance_result.is_some()
 }
 
+/// A point awarded for a chat message, kept for the rest of the game so it can be
+/// taken back if a moderator removes the message.
+#[derive(Debug, Clone)]
+pub struct ScoredMessage {
+    pub message: ChatMessageRef,
+    pub player: String,
+    pub guess_id: String,
+}
+
+/// Takes the points earned by messages `moderation` removed back out of
+/// `player_scores`, returning the ids of the voided guesses.
+pub fn void_removed_guesses(
+    scored_messages: &mut Vec<ScoredMessage>,
+    player_scores: &mut HashMap<String, u32>,
+    moderation: &ChatModeration,
+) -> Vec<String> {
+    let (voided, kept): (Vec<_>, Vec<_>) = std::mem::take(scored_messages)
+        .into_iter()
+        .partition(|scored| moderation.removes(&scored.message));
+    *scored_messages = kept;
+
+    voided
+        .into_iter()
+        .map(|scored| {
+            if let Some(current_score) = player_scores.get_mut(&scored.player) {
+                *current_score = current_score.saturating_sub(1);
+            }
+            scored.guess_id
+        })
+        .collect()
+}
+
+/// Replaces the value at each JSON pointer with `null`, skipping pointers that
+/// do not exist in `value`. Used to strip host-only fields from public events.
+pub fn redact_json_pointers(value: &mut serde_json::Value, pointers: &[&str]) {
+    for pointer in pointers {
+        if let Some(target) = value.pointer_mut(pointer) {
+            *target = serde_json::Value::Null;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests_damerau_levenshtein {
     use super::*;
@@ -628,3 +672,24 @@ mod tests_normalize_text {
         assert_eq!(normalize_text("test123"), "test123");
     }
 }
+
+#[cfg(test)]
+mod tests_redact_json_pointers {
+    use super::*;
+
+    #[test]
+    fn test_redacts_only_existing_pointers() {
+        let mut value = serde_json::json!({
+            "event_type": "WordChanged",
+            "data": { "word": "secret", "is_placeholder": false }
+        });
+        redact_json_pointers(&mut value, &["/data/word", "/data/phase/data/current_word"]);
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "event_type": "WordChanged",
+                "data": { "word": null, "is_placeholder": false }
+            })
+        );
+    }
+}
@@ -1,19 +1,30 @@
 use axum::extract::ws;
 use dashmap::DashMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration as StdDuration;
-use tokio::sync::mpsc;
+use std::time::{Duration as StdDuration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
 use tokio::time::Instant;
 use uuid::Uuid;
 
-use crate::config::{AppSettings, GamesConfig};
+use crate::cohost::{ClientRole, CoHostTokenRequest, CoHostTokens, HostPermission};
+use crate::config::AppSettings;
 use crate::content::GameContentCache;
 use crate::game_logic::{
-    ClipQueueGame, DealNoDealGame, GameLogic, MedAndraOrdGame, QuizGame, ServerToClientMessage,
-    messages as game_messages,
+    ClientToServerMessage, ClipQueueGame, DealNoDealGame, GameLogic, GameType, MedAndraOrdGame,
+    QuizGame, SETUP_PHASE, ServerToClientMessage, messages as game_messages,
+    quiz::QuizQuestionSource,
 };
-use crate::twitch::{ParsedTwitchMessage, TwitchChannelConnectionStatus, TwitchServiceHandle};
+use crate::obs::{ObsHandle, ObsLobbyConfig};
+use crate::streamers::StreamerStore;
+use crate::twitch::{
+    ChatAnnouncer, ChatModeration, ParsedTwitchMessage, TwitchChannelConnectionStatus,
+    TwitchChannelEvent, TwitchServiceHandle, UserNotice,
+};
+use crate::webhooks::{self, WebhookContext, WebhookHandle, WebhookTarget};
 
 #[derive(Debug, Serialize, Clone)]
 pub struct LobbyDetails {
@@ -21,23 +32,302 @@ pub struct LobbyDetails {
     pub admin_id: Uuid,
     pub game_type_created: String,
     pub twitch_channel_subscribed: Option<String>,
+    /// Unix timestamp (seconds) at which the game starts automatically, if scheduled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled_start_at: Option<u64>,
+}
+
+/// Longest allowed wait before a scheduled start fires.
+const MAX_SCHEDULED_START_DELAY: StdDuration = StdDuration::from_secs(24 * 60 * 60);
+
+/// Admin-only global commands for managing co-host tokens.
+const CO_HOST_COMMANDS: [&str; 3] = ["CreateCoHostToken", "ListCoHostTokens", "RevokeCoHostToken"];
+
+/// How often the remaining countdown is broadcast while a start is scheduled.
+const COUNTDOWN_BROADCAST_INTERVAL: StdDuration = StdDuration::from_secs(1);
+
+/// When a lobby should start its game automatically, e.g. `{"at": 1767225600}`
+/// or `{"in_minutes": 10}`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledStart {
+    /// Absolute start time as a Unix timestamp in seconds.
+    At(u64),
+    /// Start time relative to when the schedule is applied.
+    InMinutes(u32),
+}
+
+impl ScheduledStart {
+    /// Resolves the schedule into a delay from `now_unix_secs`.
+    pub fn delay_from(&self, now_unix_secs: u64) -> Result<StdDuration, String> {
+        let delay = match *self {
+            ScheduledStart::At(start_at) => {
+                if start_at <= now_unix_secs {
+                    return Err("Scheduled start time must be in the future".to_string());
+                }
+                StdDuration::from_secs(start_at - now_unix_secs)
+            }
+            ScheduledStart::InMinutes(0) => {
+                return Err("Scheduled start must be at least one minute away".to_string());
+            }
+            ScheduledStart::InMinutes(minutes) => StdDuration::from_secs(u64::from(minutes) * 60),
+        };
+
+        if delay > MAX_SCHEDULED_START_DELAY {
+            return Err(format!(
+                "Scheduled start must be within {} hours",
+                MAX_SCHEDULED_START_DELAY.as_secs() / 3600
+            ));
+        }
+        Ok(delay)
+    }
+}
+
+pub(crate) fn unix_now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Everything needed to set up a new lobby: which game to run, which Twitch
+/// channel to listen to, and the game-specific initial settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LobbyCreationOptions {
+    pub game_type: Option<String>,
+    pub twitch_channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<JsonValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_start: Option<ScheduledStart>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub obs: Option<ObsLobbyConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhooks: Option<Vec<WebhookTarget>>,
+}
+
+#[derive(Debug, Error)]
+pub enum LobbyCreationError {
+    #[error("Twitch channel '{channel}' has no enabled streamer account.")]
+    ChannelNotAllowed { channel: String },
+    #[error("Missing or invalid streamer API key for channel '{channel}'.")]
+    InvalidStreamerApiKey { channel: String },
+    #[error("Log in with Twitch as '{channel}' to create a lobby for that channel.")]
+    TwitchLoginRequired { channel: String },
+    #[error("Streamer '{channel}' may not host game type '{game_type}'.")]
+    GameNotAllowedForStreamer { channel: String, game_type: String },
+    #[error("Streamer '{channel}' already has {limit} active lobbies.")]
+    StreamerQuotaExceeded { channel: String, limit: usize },
+    #[error("Game type '{game_type}' is not enabled.")]
+    GameTypeDisabled { game_type: String },
+    #[error("Unknown game type '{requested}'.")]
+    UnknownGameType { requested: String },
+    #[error(
+        "ClipQueue requires YouTube API configuration. Please set KOLMODIN__YOUTUBE__API_KEY environment variable."
+    )]
+    MissingYouTubeConfig,
+    #[error("Lobby quota exceeded: at most {limit} lobbies may be active at once.")]
+    QuotaExceeded { limit: usize },
+    #[error("Invalid settings for game type '{game_type}': {message}")]
+    InvalidSettings { game_type: String, message: String },
+    #[error("Lobby preset '{name}' does not exist.")]
+    PresetNotFound { name: String },
+    #[error("Invalid scheduled start: {message}")]
+    InvalidSchedule { message: String },
+    #[error("OBS integration is not enabled on this server.")]
+    ObsDisabled,
+    #[error("Invalid OBS configuration: {message}")]
+    InvalidObsConfig { message: String },
+    #[error("Per-lobby webhooks are not enabled on this server.")]
+    LobbyWebhooksDisabled,
+    #[error("Invalid webhook: {message}")]
+    InvalidWebhook { message: String },
+}
+
+impl LobbyCreationError {
+    /// Stable machine-readable identifier returned to HTTP clients.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LobbyCreationError::ChannelNotAllowed { .. } => "channel_not_allowed",
+            LobbyCreationError::InvalidStreamerApiKey { .. } => "invalid_streamer_api_key",
+            LobbyCreationError::TwitchLoginRequired { .. } => "twitch_login_required",
+            LobbyCreationError::GameNotAllowedForStreamer { .. } => "game_not_allowed_for_streamer",
+            LobbyCreationError::StreamerQuotaExceeded { .. } => "streamer_quota_exceeded",
+            LobbyCreationError::GameTypeDisabled { .. } => "game_type_disabled",
+            LobbyCreationError::UnknownGameType { .. } => "unknown_game_type",
+            LobbyCreationError::MissingYouTubeConfig => "youtube_not_configured",
+            LobbyCreationError::QuotaExceeded { .. } => "lobby_quota_exceeded",
+            LobbyCreationError::InvalidSettings { .. } => "invalid_game_settings",
+            LobbyCreationError::PresetNotFound { .. } => "preset_not_found",
+            LobbyCreationError::InvalidSchedule { .. } => "invalid_schedule",
+            LobbyCreationError::ObsDisabled => "obs_disabled",
+            LobbyCreationError::InvalidObsConfig { .. } => "invalid_obs_config",
+            LobbyCreationError::LobbyWebhooksDisabled => "lobby_webhooks_disabled",
+            LobbyCreationError::InvalidWebhook { .. } => "invalid_webhook",
+        }
+    }
+}
+
+/// Point-in-time view of a lobby's game, as returned by `LobbyActorHandle::state_snapshot`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LobbyStateSnapshot {
+    pub lobby_id: Uuid,
+    pub game_type_id: String,
+    /// The game's `FullStateUpdate` payload.
+    pub state: JsonValue,
+    /// True when host-only fields were stripped because no valid token was presented.
+    pub redacted: bool,
+}
+
+/// Why a command sent through `LobbyActorHandle::execute_command` was rejected.
+#[derive(Debug, Error)]
+pub enum LobbyCommandError {
+    #[error("Lobby token is invalid or has been revoked.")]
+    InvalidToken,
+    #[error("This token does not grant the '{}' permission.", permission.as_str())]
+    PermissionDenied { permission: HostPermission },
+    #[error("Command targets game type '{received}', but this lobby runs '{expected}'.")]
+    WrongGameType { expected: String, received: String },
+    #[error("{0}")]
+    InvalidCommand(String),
+    #[error("Only GameSpecificCommand messages can be executed this way.")]
+    UnsupportedMessage,
+    /// The game did not apply the command, e.g. because of its current phase.
+    #[error("{0}")]
+    Rejected(String),
+    #[error("Lobby is shutting down.")]
+    LobbyUnavailable,
+}
+
+impl LobbyCommandError {
+    /// Stable machine-readable identifier returned to HTTP clients.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LobbyCommandError::InvalidToken => "invalid_lobby_token",
+            LobbyCommandError::PermissionDenied { .. } => "permission_denied",
+            LobbyCommandError::WrongGameType { .. } => "wrong_game_type",
+            LobbyCommandError::InvalidCommand(_) => "invalid_command",
+            LobbyCommandError::UnsupportedMessage => "unsupported_message",
+            LobbyCommandError::Rejected(_) => "command_rejected",
+            LobbyCommandError::LobbyUnavailable => "lobby_unavailable",
+        }
+    }
+}
+
+fn configure_game<G: GameLogic>(
+    mut game_engine: G,
+    game_type: &GameType,
+    settings: Option<JsonValue>,
+    start_scheduled: bool,
+) -> Result<G, LobbyCreationError> {
+    if start_scheduled && !game_engine.supports_scheduled_start() {
+        return Err(LobbyCreationError::InvalidSchedule {
+            message: format!(
+                "Game type '{}' does not support a scheduled start",
+                game_type.primary_id()
+            ),
+        });
+    }
+    game_engine
+        .apply_lobby_settings(settings)
+        .map_err(|message| LobbyCreationError::InvalidSettings {
+            game_type: game_type.primary_id().to_string(),
+            message,
+        })?;
+    Ok(game_engine)
+}
+
+/// Checks the parts of `options` that do not depend on who creates the lobby:
+/// the game type, its settings and the scheduled start. Presets are checked with
+/// this when saved; channel permissions and quotas are checked on creation.
+pub async fn validate_creation_options(
+    content_cache: &GameContentCache,
+    app_settings: Arc<AppSettings>,
+    options: &LobbyCreationOptions,
+) -> Result<(), LobbyCreationError> {
+    let requested_game_type = options
+        .game_type
+        .as_deref()
+        .unwrap_or(GameType::MedAndraOrd.primary_id());
+    let Some(game_type) = GameType::from_alias(requested_game_type) else {
+        return Err(LobbyCreationError::UnknownGameType {
+            requested: requested_game_type.to_string(),
+        });
+    };
+
+    if let Some(schedule) = options.scheduled_start {
+        schedule
+            .delay_from(unix_now_secs())
+            .map_err(|message| LobbyCreationError::InvalidSchedule { message })?;
+    }
+    let start_scheduled = options.scheduled_start.is_some();
+    let settings = options.settings.clone();
+
+    match game_type {
+        GameType::DealNoDeal => {
+            configure_game(
+                DealNoDealGame::new(&app_settings.games.dealnodeal),
+                &game_type,
+                settings,
+                start_scheduled,
+            )?;
+        }
+        GameType::MedAndraOrd => {
+            configure_game(
+                MedAndraOrdGame::new(
+                    content_cache.medandraord_words().await,
+                    &app_settings.games.medandraord,
+                ),
+                &game_type,
+                settings,
+                start_scheduled,
+            )?;
+        }
+        GameType::ClipQueue => {
+            configure_game(
+                ClipQueueGame::new(Arc::clone(&app_settings)),
+                &game_type,
+                settings,
+                start_scheduled,
+            )?;
+        }
+        GameType::Quiz => {
+            configure_game(
+                QuizGame::new(
+                    content_cache.trivial_pursuit_data().await,
+                    Some(content_cache.vem_vet_mest_questions().await),
+                    &app_settings.games.quiz,
+                ),
+                &game_type,
+                settings,
+                start_scheduled,
+            )?;
+        }
+    }
+    Ok(())
 }
 
-#[allow(clippy::too_many_arguments)]
 pub async fn create_lobby(
     active_lobbies: Arc<DashMap<Uuid, LobbyActorHandle>>,
-    games_config: GamesConfig,
     content_cache: Arc<GameContentCache>,
     twitch_service_handle: TwitchServiceHandle,
+    streamer_store: &StreamerStore,
     app_settings: Arc<AppSettings>,
-    requested_game_type: Option<String>,
-    requested_twitch_channel: Option<String>,
-) -> Result<LobbyDetails, String> {
+    options: LobbyCreationOptions,
+    streamer_api_key: Option<&str>,
+) -> Result<LobbyDetails, LobbyCreationError> {
     let lobby_id = Uuid::new_v4();
     let admin_id = Uuid::new_v4();
-    let requested_game_type = requested_game_type
-        .clone()
-        .unwrap_or_else(|| "medandraord".to_string());
+    let LobbyCreationOptions {
+        game_type: requested_game_type,
+        twitch_channel: requested_twitch_channel,
+        settings: requested_settings,
+        scheduled_start: requested_scheduled_start,
+        obs: requested_obs,
+        webhooks: requested_webhooks,
+    } = options;
+    let requested_game_type =
+        requested_game_type.unwrap_or_else(|| GameType::MedAndraOrd.primary_id().to_string());
 
     tracing::info!(
         lobby.id = %lobby_id,
@@ -46,39 +336,127 @@ pub async fn create_lobby(
         "Received create lobby request"
     );
 
-    if let Some(channel_name) = requested_twitch_channel.as_ref()
-        && !content_cache.is_twitch_channel_allowed(channel_name).await
+    if let Some(limit) = app_settings.server.max_active_lobbies
+        && active_lobbies.len() >= limit
     {
         tracing::warn!(
             lobby.id = %lobby_id,
-            twitch.channel = %channel_name,
-            "Twitch channel not allowed for lobby creation"
+            lobbies.active = active_lobbies.len(),
+            lobbies.limit = limit,
+            "Active lobby limit reached"
         );
-        return Err(format!(
-            "Twitch channel '{}' is not in the allowed channels list.",
-            channel_name
-        ));
+        return Err(LobbyCreationError::QuotaExceeded { limit });
     }
 
-    let medandraord_words = content_cache.medandraord_words().await;
-    let trivial_pursuit_data = content_cache.trivial_pursuit_data().await;
-    let vem_vet_mest_data = content_cache.vem_vet_mest_questions().await;
+    let Some(game_type) = GameType::from_alias(&requested_game_type) else {
+        tracing::warn!(
+            lobby.id = %lobby_id,
+            game.type.requested = %requested_game_type,
+            "Unknown game type requested"
+        );
+        return Err(LobbyCreationError::UnknownGameType {
+            requested: requested_game_type,
+        });
+    };
 
-    let (game_type_created, lobby_actor_handle) = match requested_game_type.to_lowercase().as_str()
+    if !app_settings
+        .games
+        .enabled_types
+        .contains(game_type.primary_id())
     {
-        "dealnodeal" | "dealornodeal" => {
-            if !games_config.enabled_types.contains("dealnodeal") {
-                tracing::error!(
-                    lobby.id = %lobby_id,
-                    game.type = "dealnodeal",
-                    "Game type not enabled"
-                );
-                return Err("Game type 'dealnodeal' is not enabled.".to_string());
-            }
-            let game_engine = DealNoDealGame::new();
+        tracing::error!(
+            lobby.id = %lobby_id,
+            game.type = game_type.primary_id(),
+            "Game type not enabled"
+        );
+        return Err(LobbyCreationError::GameTypeDisabled {
+            game_type: game_type.primary_id().to_string(),
+        });
+    }
+
+    // Held until the lobby is in `active_lobbies`, so it counts against the
+    // channel's quota while being set up.
+    let _streamer_reservation = match requested_twitch_channel.as_ref() {
+        Some(channel_name) => {
+            let count_active_lobbies = || {
+                active_lobbies
+                    .iter()
+                    .filter(|entry| {
+                        entry
+                            .value()
+                            .twitch_channel
+                            .as_deref()
+                            .is_some_and(|channel| channel.eq_ignore_ascii_case(channel_name))
+                    })
+                    .count()
+            };
+            let reservation = streamer_store
+                .check_lobby_creation(
+                    channel_name,
+                    &game_type,
+                    streamer_api_key,
+                    count_active_lobbies,
+                )
+                .await
+                .inspect_err(|e| {
+                    tracing::warn!(
+                        lobby.id = %lobby_id,
+                        twitch.channel = %channel_name,
+                        error.code = e.code(),
+                        "Streamer may not create this lobby"
+                    );
+                })?;
+            Some(reservation)
+        }
+        None => None,
+    };
+
+    if let Some(obs_config) = requested_obs.as_ref() {
+        if !app_settings.obs.enabled {
+            tracing::warn!(lobby.id = %lobby_id, "OBS integration requested but disabled");
+            return Err(LobbyCreationError::ObsDisabled);
+        }
+        obs_config
+            .validate(game_type.phase_names())
+            .map_err(|message| LobbyCreationError::InvalidObsConfig { message })?;
+    }
+
+    let lobby_webhooks = requested_webhooks.unwrap_or_default();
+    if !lobby_webhooks.is_empty() {
+        if !app_settings.webhooks.allow_lobby_targets {
+            tracing::warn!(lobby.id = %lobby_id, "Per-lobby webhooks requested but disabled");
+            return Err(LobbyCreationError::LobbyWebhooksDisabled);
+        }
+        webhooks::validate_lobby_targets(&lobby_webhooks)
+            .map_err(|message| LobbyCreationError::InvalidWebhook { message })?;
+    }
+
+    let now_unix_secs = unix_now_secs();
+    let scheduled_start_delay = requested_scheduled_start
+        .map(|schedule| schedule.delay_from(now_unix_secs))
+        .transpose()
+        .map_err(|message| {
+            tracing::warn!(
+                lobby.id = %lobby_id,
+                error = %message,
+                "Invalid scheduled start requested"
+            );
+            LobbyCreationError::InvalidSchedule { message }
+        })?;
+    let start_scheduled = scheduled_start_delay.is_some();
+
+    let (game_type_created, lobby_actor_handle) = match game_type {
+        GameType::DealNoDeal => {
+            let game_engine = configure_game(
+                DealNoDealGame::new(&app_settings.games.dealnodeal),
+                &game_type,
+                requested_settings,
+                start_scheduled,
+            )?;
             let game_type_id = game_engine.game_type_id();
             let handle = LobbyActorHandle::spawn::<DealNoDealGame>(
                 lobby_id,
+                admin_id,
                 32,
                 Arc::clone(&active_lobbies),
                 game_engine,
@@ -87,19 +465,18 @@ pub async fn create_lobby(
             );
             (game_type_id, handle)
         }
-        "medandraord" | "medandra" | "ord" => {
-            if !games_config.enabled_types.contains("medandraord") {
-                tracing::error!(
-                    lobby.id = %lobby_id,
-                    game.type = "medandraord",
-                    "Game type not enabled"
-                );
-                return Err("Game type 'medandraord' is not enabled.".to_string());
-            }
-            let game_engine = MedAndraOrdGame::new(medandraord_words.clone());
+        GameType::MedAndraOrd => {
+            let medandraord_words = content_cache.medandraord_words().await;
+            let game_engine = configure_game(
+                MedAndraOrdGame::new(medandraord_words, &app_settings.games.medandraord),
+                &game_type,
+                requested_settings,
+                start_scheduled,
+            )?;
             let game_type_id = game_engine.game_type_id();
             let handle = LobbyActorHandle::spawn::<MedAndraOrdGame>(
                 lobby_id,
+                admin_id,
                 32,
                 Arc::clone(&active_lobbies),
                 game_engine,
@@ -108,32 +485,26 @@ pub async fn create_lobby(
             );
             (game_type_id, handle)
         }
-        "clipqueue" | "queue" => {
-            if !games_config.enabled_types.contains("clipqueue") {
-                tracing::error!(
-                    lobby.id = %lobby_id,
-                    game.type = "clipqueue",
-                    "Game type not enabled"
-                );
-                return Err("Game type 'clipqueue' is not enabled.".to_string());
-            }
-
+        GameType::ClipQueue => {
             if app_settings.youtube.is_none() {
                 tracing::error!(
                     lobby.id = %lobby_id,
                     game.type = "clipqueue",
                     "YouTube API not configured for ClipQueue game"
                 );
-                return Err(
-                    "ClipQueue requires YouTube API configuration. Please set KOLMODIN__YOUTUBE__API_KEY environment variable."
-                        .to_string(),
-                );
+                return Err(LobbyCreationError::MissingYouTubeConfig);
             }
 
-            let game_engine = ClipQueueGame::new(app_settings.clone());
+            let game_engine = configure_game(
+                ClipQueueGame::new(app_settings.clone()),
+                &game_type,
+                requested_settings,
+                start_scheduled,
+            )?;
             let game_type_id = game_engine.game_type_id();
             let handle = LobbyActorHandle::spawn::<ClipQueueGame>(
                 lobby_id,
+                admin_id,
                 32,
                 Arc::clone(&active_lobbies),
                 game_engine,
@@ -142,50 +513,31 @@ pub async fn create_lobby(
             );
             (game_type_id, handle)
         }
-        "quiz" => {
-            if !games_config.enabled_types.contains("quiz") {
-                tracing::error!(
-                    lobby.id = %lobby_id,
-                    game.type = "quiz",
-                    "Game type not enabled"
-                );
-                return Err("Game type 'quiz' is not enabled.".to_string());
-            }
-            let game_engine = QuizGame::new(trivial_pursuit_data, Some(vem_vet_mest_data));
+        GameType::Quiz => {
+            let content_packs = match requested_twitch_channel.as_deref() {
+                Some(channel) => streamer_store.content_packs(channel).await,
+                None => Vec::new(),
+            };
+            let trivial_pursuit_data = content_cache
+                .trivial_pursuit_data()
+                .await
+                .filter(|_| QuizQuestionSource::TrivialPursuit.is_licensed(&content_packs));
+            let vem_vet_mest_data = Some(content_cache.vem_vet_mest_questions().await)
+                .filter(|_| QuizQuestionSource::VemVetMest.is_licensed(&content_packs));
+            let game_engine = configure_game(
+                QuizGame::new(
+                    trivial_pursuit_data,
+                    vem_vet_mest_data,
+                    &app_settings.games.quiz,
+                ),
+                &game_type,
+                requested_settings,
+                start_scheduled,
+            )?;
             let game_type_id = game_engine.game_type_id();
             let handle = LobbyActorHandle::spawn::<QuizGame>(
                 lobby_id,
-                32,
-                Arc::clone(&active_lobbies),
-                game_engine,
-                requested_twitch_channel.clone(),
-                twitch_service_handle.clone(),
-            );
-            (game_type_id, handle)
-        }
-        unknown => {
-            tracing::warn!(
-                lobby.id = %lobby_id,
-                game.type.requested = %unknown,
-                game.type.fallback = "medandraord",
-                "Unknown game type, defaulting to MedAndraOrd"
-            );
-            if !games_config.enabled_types.contains("medandraord") {
-                tracing::error!(
-                    lobby.id = %lobby_id,
-                    game.type.requested = %unknown,
-                    game.type.fallback = "medandraord",
-                    "Default game type not enabled for unknown request"
-                );
-                return Err(format!(
-                    "Default game type 'medandraord' is not enabled for unknown request '{}'.",
-                    unknown
-                ));
-            }
-            let game_engine = MedAndraOrdGame::new(medandraord_words.clone());
-            let game_type_id = game_engine.game_type_id();
-            let handle = LobbyActorHandle::spawn::<MedAndraOrdGame>(
-                lobby_id,
+                admin_id,
                 32,
                 Arc::clone(&active_lobbies),
                 game_engine,
@@ -196,6 +548,53 @@ pub async fn create_lobby(
         }
     };
 
+    let webhook_targets: Vec<WebhookTarget> = app_settings
+        .webhooks
+        .global_targets()
+        .into_iter()
+        .chain(lobby_webhooks)
+        .collect();
+    if !webhook_targets.is_empty() {
+        let context = WebhookContext {
+            lobby_id,
+            game_type_id: game_type_created.clone(),
+            twitch_channel: requested_twitch_channel.clone(),
+        };
+        lobby_actor_handle
+            .attach_webhooks(WebhookHandle::spawn(
+                context,
+                webhook_targets,
+                app_settings.webhooks.max_attempts,
+            ))
+            .await;
+    }
+    if let Some(channel) = requested_twitch_channel.as_ref()
+        && app_settings.twitch.chat.enabled
+    {
+        let templates = app_settings
+            .twitch
+            .chat
+            .templates_for(game_type.primary_id());
+        if !templates.is_empty() {
+            lobby_actor_handle
+                .attach_chat_announcer(ChatAnnouncer::new(
+                    channel.clone(),
+                    game_type_created.clone(),
+                    templates,
+                    twitch_service_handle.clone(),
+                ))
+                .await;
+        }
+    }
+    if let Some(delay) = scheduled_start_delay {
+        lobby_actor_handle.schedule_start(delay).await;
+    }
+    if let Some(obs_config) = requested_obs {
+        lobby_actor_handle
+            .attach_obs(ObsHandle::spawn(lobby_id, obs_config))
+            .await;
+    }
+
     active_lobbies.insert(lobby_id, lobby_actor_handle);
 
     tracing::info!(
@@ -211,6 +610,7 @@ pub async fn create_lobby(
         admin_id,
         game_type_created,
         twitch_channel_subscribed: requested_twitch_channel,
+        scheduled_start_at: scheduled_start_delay.map(|delay| now_unix_secs + delay.as_secs()),
     })
 }
 
@@ -223,17 +623,43 @@ pub enum LobbyActorMessage {
     ClientConnected {
         client_id: Uuid,
         client_tx: mpsc::Sender<ws::Message>,
+        admin_token: Option<Uuid>,
     },
     ClientDisconnected {
         client_id: Uuid,
     },
     InternalTwitchMessage(ParsedTwitchMessage),
+    InternalChatModeration(ChatModeration),
+    InternalUserNotice(UserNotice),
     InternalTwitchStatusUpdate(TwitchChannelConnectionStatus),
+    ScheduleStart {
+        delay: StdDuration,
+    },
+    ExecuteCommand {
+        admin_token: Uuid,
+        message: ClientToServerMessage,
+        respond_to: oneshot::Sender<Result<(), LobbyCommandError>>,
+    },
+    GetStateSnapshot {
+        admin_token: Option<Uuid>,
+        respond_to: oneshot::Sender<Result<LobbyStateSnapshot, LobbyCommandError>>,
+    },
+    AttachObs(ObsHandle),
+    AttachWebhooks(WebhookHandle),
+    AttachChatAnnouncer(ChatAnnouncer),
+}
+
+/// A pending automatic game start and its countdown.
+#[derive(Debug, Clone, Copy)]
+struct PendingStart {
+    starts_at: Instant,
+    starts_at_unix_secs: u64,
 }
 
 pub struct LobbyActor<G: GameLogic + Send + 'static> {
     receiver: mpsc::Receiver<LobbyActorMessage>,
     lobby_id: Uuid,
+    admin_id: Uuid,
     game_engine: G,
     active_lobbies: Arc<DashMap<Uuid, LobbyActorHandle>>,
     twitch_channel_name: Option<String>,
@@ -242,20 +668,30 @@ pub struct LobbyActor<G: GameLogic + Send + 'static> {
     twitch_subscribed: bool,
     _twitch_message_task_handle: Option<tokio::task::JoinHandle<()>>,
     _twitch_status_task_handle: Option<tokio::task::JoinHandle<()>>,
+    pending_start: Option<PendingStart>,
+    co_hosts: CoHostTokens,
+    client_roles: HashMap<Uuid, ClientRole>,
+    obs: Option<ObsHandle>,
+    webhooks: Option<WebhookHandle>,
+    chat_announcer: Option<ChatAnnouncer>,
+    last_phase: &'static str,
 }
 
 impl<G: GameLogic + Send + 'static> LobbyActor<G> {
     fn new(
         receiver: mpsc::Receiver<LobbyActorMessage>,
         lobby_id: Uuid,
+        admin_id: Uuid,
         game_engine: G,
         active_lobbies: Arc<DashMap<Uuid, LobbyActorHandle>>,
         twitch_channel_name: Option<String>,
         twitch_service_handle: TwitchServiceHandle,
     ) -> Self {
+        let last_phase = game_engine.phase_name();
         LobbyActor {
             receiver,
             lobby_id,
+            admin_id,
             game_engine,
             active_lobbies,
             twitch_channel_name,
@@ -264,6 +700,13 @@ impl<G: GameLogic + Send + 'static> LobbyActor<G> {
             twitch_status_receiver: None,
             _twitch_message_task_handle: None,
             _twitch_status_task_handle: None,
+            pending_start: None,
+            co_hosts: CoHostTokens::default(),
+            client_roles: HashMap::new(),
+            obs: None,
+            webhooks: None,
+            chat_announcer: None,
+            last_phase,
         }
     }
 
@@ -301,7 +744,46 @@ impl<G: GameLogic + Send + 'static> LobbyActor<G> {
                 );
 
                 match game_messages::parse_client_ws_message(&raw_payload) {
+                    Ok(ClientToServerMessage::GlobalCommand { command_name, data })
+                        if command_name == "ScheduleStart"
+                            || command_name == "CancelScheduledStart" =>
+                    {
+                        if self
+                            .authorize(client_id, HostPermission::ScheduleStart)
+                            .await
+                        {
+                            self.handle_schedule_command(client_id, &command_name, data)
+                                .await;
+                        }
+                    }
+                    Ok(ClientToServerMessage::GlobalCommand { command_name, data })
+                        if CO_HOST_COMMANDS.contains(&command_name.as_str()) =>
+                    {
+                        self.handle_co_host_command(client_id, &command_name, data)
+                            .await;
+                    }
                     Ok(parsed_message) => {
+                        if let ClientToServerMessage::GameSpecificCommand { command_data, .. } =
+                            &parsed_message
+                        {
+                            let permission = match self.game_engine.command_permission(command_data)
+                            {
+                                Ok(permission) => permission,
+                                Err(message) => {
+                                    tracing::warn!(
+                                        client.id = %client_id,
+                                        error = %message,
+                                        "Rejected invalid game command"
+                                    );
+                                    self.send_system_error(client_id, message).await;
+                                    return false;
+                                }
+                            };
+                            if !self.authorize(client_id, permission).await {
+                                return false;
+                            }
+                        }
+
                         tracing::debug!(
                             client.id = %client_id,
                             event.type = ?parsed_message,
@@ -316,6 +798,9 @@ impl<G: GameLogic + Send + 'static> LobbyActor<G> {
                             crate::game_logic::EventHandlingResult::Handled => {
                                 // Normal event handling, no special action needed
                             }
+                            crate::game_logic::EventHandlingResult::Rejected { reason } => {
+                                self.send_system_error(client_id, reason).await;
+                            }
                             crate::game_logic::EventHandlingResult::DisconnectClient => {
                                 // Client requested to leave lobby, trigger disconnection
                                 tracing::info!(
@@ -324,6 +809,7 @@ impl<G: GameLogic + Send + 'static> LobbyActor<G> {
                                 );
                                 // Manually trigger client disconnection which will run the empty lobby check
                                 self.game_engine.client_disconnected(client_id).await;
+                                self.client_roles.remove(&client_id);
 
                                 // Check if lobby is now empty and should shut down immediately
                                 if self.game_engine.is_empty() {
@@ -364,11 +850,38 @@ impl<G: GameLogic + Send + 'static> LobbyActor<G> {
             LobbyActorMessage::ClientConnected {
                 client_id,
                 client_tx,
+                admin_token,
             } => {
+                let role = match self.co_hosts.resolve_role(self.admin_id, admin_token) {
+                    Some(role) => role,
+                    None => {
+                        tracing::warn!(
+                            client.id = %client_id,
+                            "Client presented an unknown or revoked admin token. Connecting as viewer"
+                        );
+                        if let Ok(ws_msg) = (ServerToClientMessage::SystemError {
+                            message:
+                                "Admin token is invalid or has been revoked. Connected as a viewer."
+                                    .to_string(),
+                        })
+                        .to_ws_text()
+                        {
+                            let _ = client_tx.send(ws_msg).await;
+                        }
+                        ClientRole::Viewer
+                    }
+                };
                 tracing::debug!(
                     client.id = %client_id,
+                    client.role = role.name(),
                     "Client connected"
                 );
+                let client_tx = if role == ClientRole::Viewer {
+                    spawn_viewer_relay::<G>(client_tx)
+                } else {
+                    client_tx
+                };
+                self.client_roles.insert(client_id, role);
 
                 // Ensure Twitch subscription on first client connection
                 self.ensure_twitch_subscription(self_sender).await;
@@ -378,6 +891,12 @@ impl<G: GameLogic + Send + 'static> LobbyActor<G> {
                     .await;
 
                 self.send_current_twitch_status_to_client(client_id).await;
+
+                self.send_role_to_client(client_id).await;
+
+                if let Some(pending) = self.pending_start {
+                    self.send_countdown_to_client(client_id, pending).await;
+                }
             }
             LobbyActorMessage::ClientDisconnected { client_id } => {
                 tracing::debug!(
@@ -385,6 +904,7 @@ impl<G: GameLogic + Send + 'static> LobbyActor<G> {
                     "Client disconnected"
                 );
                 self.game_engine.client_disconnected(client_id).await;
+                self.client_roles.remove(&client_id);
 
                 // Check if lobby is now empty and should shut down immediately
                 // This fixes the issue where empty lobbies would persist indefinitely
@@ -405,6 +925,23 @@ impl<G: GameLogic + Send + 'static> LobbyActor<G> {
                 );
                 self.game_engine.handle_twitch_message(twitch_msg).await;
             }
+            LobbyActorMessage::InternalChatModeration(moderation) => {
+                tracing::info!(
+                    twitch.channel = %self.twitch_channel_name.as_deref().unwrap_or("N/A"),
+                    twitch.moderation = ?moderation,
+                    "Moderator removed chat messages"
+                );
+                self.game_engine.handle_chat_moderation(moderation).await;
+            }
+            LobbyActorMessage::InternalUserNotice(notice) => {
+                tracing::info!(
+                    twitch.channel = %notice.channel,
+                    twitch.sender = %notice.login,
+                    twitch.notice = ?notice.kind,
+                    "Received Twitch user notice"
+                );
+                self.game_engine.handle_user_notice(notice).await;
+            }
             LobbyActorMessage::InternalTwitchStatusUpdate(status) => {
                 tracing::info!(
                     twitch.channel = %self.twitch_channel_name.as_deref().unwrap_or("N/A"),
@@ -414,10 +951,499 @@ impl<G: GameLogic + Send + 'static> LobbyActor<G> {
 
                 self.broadcast_twitch_status_update(status).await;
             }
+            LobbyActorMessage::ScheduleStart { delay } => {
+                self.schedule_start(delay).await;
+            }
+            LobbyActorMessage::ExecuteCommand {
+                admin_token,
+                message,
+                respond_to,
+            } => {
+                let result = self.execute_command(admin_token, message).await;
+                if let Err(e) = &result {
+                    tracing::warn!(error = %e, error.code = e.code(), "Rejected external command");
+                }
+                let _ = respond_to.send(result);
+            }
+            LobbyActorMessage::GetStateSnapshot {
+                admin_token,
+                respond_to,
+            } => {
+                let _ = respond_to.send(self.state_snapshot(admin_token));
+            }
+            LobbyActorMessage::AttachObs(obs) => {
+                tracing::info!("OBS integration attached");
+                // Apply the actions for the phase the lobby is already in.
+                obs.phase_changed(self.last_phase);
+                self.obs = Some(obs);
+            }
+            LobbyActorMessage::AttachWebhooks(webhooks) => {
+                tracing::info!("Webhooks attached");
+                self.webhooks = Some(webhooks);
+            }
+            LobbyActorMessage::AttachChatAnnouncer(announcer) => {
+                tracing::info!("Chat announcer attached");
+                self.chat_announcer = Some(announcer);
+            }
         }
         false // Default: don't shut down
     }
 
+    /// Publishes what changed in the game after it handled a message.
+    async fn publish_game_updates(&mut self) {
+        self.notify_phase_change().await;
+        self.dispatch_lifecycle_events();
+    }
+
+    /// Forwards the game's phase to OBS whenever it differs from the last one seen,
+    /// and drops a pending scheduled start once the game has left its setup phase.
+    async fn notify_phase_change(&mut self) {
+        let phase = self.game_engine.phase_name();
+        if phase == self.last_phase {
+            return;
+        }
+        tracing::debug!(
+            game.phase.previous = self.last_phase,
+            game.phase = phase,
+            "Game phase changed"
+        );
+        self.last_phase = phase;
+        if let Some(obs) = self.obs.as_ref() {
+            obs.phase_changed(phase);
+        }
+        if phase != SETUP_PHASE {
+            self.cancel_scheduled_start().await;
+        }
+    }
+
+    /// Hands queued lifecycle events to the webhook workers and the chat
+    /// announcer. Always drains, so games without either do not accumulate events.
+    fn dispatch_lifecycle_events(&mut self) {
+        let events = self.game_engine.drain_lifecycle_events();
+        if let Some(webhooks) = self.webhooks.as_ref() {
+            for event in &events {
+                if !matches!(
+                    event,
+                    crate::game_logic::GameLifecycleEvent::Announcement { .. }
+                ) {
+                    webhooks.dispatch(event);
+                }
+            }
+        }
+        if let Some(announcer) = self.chat_announcer.as_ref() {
+            for event in &events {
+                announcer.announce(event);
+            }
+        }
+    }
+
+    /// Builds the current state snapshot. Without a token the snapshot is redacted
+    /// like a viewer's events; an invalid token is rejected rather than downgraded.
+    fn state_snapshot(
+        &self,
+        admin_token: Option<Uuid>,
+    ) -> Result<LobbyStateSnapshot, LobbyCommandError> {
+        let role = self
+            .co_hosts
+            .resolve_role(self.admin_id, admin_token)
+            .ok_or(LobbyCommandError::InvalidToken)?;
+
+        let mut state = self.game_engine.state_snapshot();
+        let redacted = role == ClientRole::Viewer;
+        if redacted {
+            G::redact_public_event(&mut state);
+        }
+
+        Ok(LobbyStateSnapshot {
+            lobby_id: self.lobby_id,
+            game_type_id: self.game_engine.game_type_id(),
+            state,
+            redacted,
+        })
+    }
+
+    /// Runs a game command on behalf of a caller that is not a connected client
+    /// (e.g. the HTTP command endpoint), authenticated by its admin or co-host token.
+    async fn execute_command(
+        &mut self,
+        admin_token: Uuid,
+        message: ClientToServerMessage,
+    ) -> Result<(), LobbyCommandError> {
+        let role = self
+            .co_hosts
+            .resolve_role(self.admin_id, Some(admin_token))
+            .ok_or(LobbyCommandError::InvalidToken)?;
+
+        let ClientToServerMessage::GameSpecificCommand {
+            game_type_id,
+            command_data,
+        } = &message
+        else {
+            return Err(LobbyCommandError::UnsupportedMessage);
+        };
+
+        let expected = self.game_engine.game_type_id();
+        if *game_type_id != expected {
+            return Err(LobbyCommandError::WrongGameType {
+                expected,
+                received: game_type_id.clone(),
+            });
+        }
+
+        let permission = self
+            .game_engine
+            .command_permission(command_data)
+            .map_err(LobbyCommandError::InvalidCommand)?;
+        if !self.co_hosts.is_allowed(&role, permission) {
+            return Err(LobbyCommandError::PermissionDenied { permission });
+        }
+
+        tracing::info!(
+            client.role = role.name(),
+            permission = permission.as_str(),
+            "Executing external game command"
+        );
+        // External callers have no socket; a fresh id keeps them apart from connected
+        // clients, and the outcome goes back in the response instead.
+        match self.game_engine.handle_event(Uuid::new_v4(), message).await {
+            crate::game_logic::EventHandlingResult::Rejected { reason } => {
+                Err(LobbyCommandError::Rejected(reason))
+            }
+            crate::game_logic::EventHandlingResult::Handled
+            | crate::game_logic::EventHandlingResult::DisconnectClient => Ok(()),
+        }
+    }
+
+    /// Checks that the client's role grants `permission`, telling the client when it does not.
+    async fn authorize(&self, client_id: Uuid, permission: HostPermission) -> bool {
+        let role = self
+            .client_roles
+            .get(&client_id)
+            .cloned()
+            .unwrap_or(ClientRole::Viewer);
+        if self.co_hosts.is_allowed(&role, permission) {
+            return true;
+        }
+
+        tracing::warn!(
+            client.id = %client_id,
+            client.role = role.name(),
+            permission = ?permission,
+            "Rejected command: missing permission"
+        );
+        self.send_system_error(
+            client_id,
+            format!(
+                "You do not have permission to perform this action ({})",
+                permission.as_str()
+            ),
+        )
+        .await;
+        false
+    }
+
+    async fn send_system_error(&self, client_id: Uuid, message: String) {
+        if let Some(client_tx) = self.game_engine.get_client_tx(client_id)
+            && let Ok(ws_msg) = (ServerToClientMessage::SystemError { message }).to_ws_text()
+            && client_tx.send(ws_msg).await.is_err()
+        {
+            tracing::warn!(
+                client.id = %client_id,
+                "Failed to send system error to client"
+            );
+        }
+    }
+
+    async fn send_global_event_to_client(
+        &self,
+        client_id: Uuid,
+        event_name: &str,
+        data: &JsonValue,
+    ) {
+        let Ok(message) = ServerToClientMessage::new_global_event(event_name.to_string(), data)
+        else {
+            return;
+        };
+
+        if let Some(client_tx) = self.game_engine.get_client_tx(client_id)
+            && let Ok(ws_msg) = message.to_ws_text()
+            && client_tx.send(ws_msg).await.is_err()
+        {
+            tracing::warn!(
+                client.id = %client_id,
+                event.name = event_name,
+                "Failed to send global event to client"
+            );
+        }
+    }
+
+    async fn send_role_to_client(&self, client_id: Uuid) {
+        let role = self
+            .client_roles
+            .get(&client_id)
+            .cloned()
+            .unwrap_or(ClientRole::Viewer);
+        let data = serde_json::json!({
+            "role": role.name(),
+            "permissions": self.co_hosts.permissions_for(&role),
+        });
+        self.send_global_event_to_client(client_id, "ClientRole", &data)
+            .await;
+    }
+
+    async fn handle_co_host_command(
+        &mut self,
+        client_id: Uuid,
+        command_name: &str,
+        data: JsonValue,
+    ) {
+        if self.client_roles.get(&client_id) != Some(&ClientRole::Admin) {
+            tracing::warn!(
+                client.id = %client_id,
+                command = command_name,
+                "Rejected co-host command from non-admin client"
+            );
+            self.send_system_error(
+                client_id,
+                "Only the lobby admin can manage co-host tokens".to_string(),
+            )
+            .await;
+            return;
+        }
+
+        match command_name {
+            "CreateCoHostToken" => {
+                let request = if data.is_null() {
+                    Ok(CoHostTokenRequest::default())
+                } else {
+                    serde_json::from_value::<CoHostTokenRequest>(data)
+                        .map_err(|e| format!("Invalid CreateCoHostToken data: {}", e))
+                };
+                match request.and_then(|request| self.co_hosts.mint(request)) {
+                    Ok(token) => {
+                        tracing::info!(
+                            cohost.label = ?token.label,
+                            cohost.permissions = ?token.permissions,
+                            "Minted co-host token"
+                        );
+                        self.send_global_event_to_client(
+                            client_id,
+                            "CoHostTokenCreated",
+                            &serde_json::json!({ "token": token }),
+                        )
+                        .await;
+                    }
+                    Err(message) => self.send_system_error(client_id, message).await,
+                }
+            }
+            "ListCoHostTokens" => {
+                self.send_global_event_to_client(
+                    client_id,
+                    "CoHostTokens",
+                    &serde_json::json!({ "tokens": self.co_hosts.list() }),
+                )
+                .await;
+            }
+            "RevokeCoHostToken" => {
+                let token = data
+                    .get("token")
+                    .and_then(|token| token.as_str())
+                    .and_then(|token| Uuid::parse_str(token).ok());
+                let Some(token) = token else {
+                    self.send_system_error(
+                        client_id,
+                        "RevokeCoHostToken requires data like {\"token\": \"<uuid>\"}".to_string(),
+                    )
+                    .await;
+                    return;
+                };
+
+                if self.co_hosts.revoke(token).is_none() {
+                    self.send_system_error(client_id, "Unknown co-host token".to_string())
+                        .await;
+                    return;
+                }
+
+                self.disconnect_co_host_clients(token).await;
+                tracing::info!("Revoked co-host token");
+                self.send_global_event_to_client(
+                    client_id,
+                    "CoHostTokenRevoked",
+                    &serde_json::json!({ "token": token }),
+                )
+                .await;
+            }
+            _ => {}
+        }
+    }
+
+    /// Drops every connection that authenticated with a now-revoked co-host token.
+    async fn disconnect_co_host_clients(&mut self, token: Uuid) {
+        let revoked_role = ClientRole::CoHost { token };
+        let affected: Vec<Uuid> = self
+            .client_roles
+            .iter()
+            .filter(|(_, role)| **role == revoked_role)
+            .map(|(client_id, _)| *client_id)
+            .collect();
+
+        for client_id in affected {
+            tracing::info!(
+                client.id = %client_id,
+                "Disconnecting client whose co-host token was revoked"
+            );
+            self.send_system_error(
+                client_id,
+                "Your co-host access has been revoked".to_string(),
+            )
+            .await;
+            // Dropping the game's sender ends the client's send task, which closes the socket.
+            self.game_engine.client_disconnected(client_id).await;
+            self.client_roles.remove(&client_id);
+        }
+    }
+
+    async fn handle_schedule_command(
+        &mut self,
+        client_id: Uuid,
+        command_name: &str,
+        data: JsonValue,
+    ) {
+        let result = if command_name == "CancelScheduledStart" {
+            self.cancel_scheduled_start().await;
+            Ok(())
+        } else if !self.game_engine.supports_scheduled_start() {
+            Err("This game does not support a scheduled start".to_string())
+        } else if self.game_engine.phase_name() != SETUP_PHASE {
+            Err("A start can only be scheduled before the game has started".to_string())
+        } else {
+            match serde_json::from_value::<ScheduledStart>(data) {
+                Ok(schedule) => match schedule.delay_from(unix_now_secs()) {
+                    Ok(delay) => {
+                        self.schedule_start(delay).await;
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(format!(
+                    "Invalid ScheduleStart data: {}. Expected {{\"at\": <unix seconds>}} or {{\"in_minutes\": <minutes>}}",
+                    e
+                )),
+            }
+        };
+
+        if let Err(message) = result {
+            tracing::warn!(
+                client.id = %client_id,
+                error = %message,
+                "Rejected schedule command"
+            );
+            self.send_system_error(client_id, message).await;
+        }
+    }
+
+    async fn schedule_start(&mut self, delay: StdDuration) {
+        let pending = PendingStart {
+            starts_at: Instant::now() + delay,
+            starts_at_unix_secs: unix_now_secs() + delay.as_secs(),
+        };
+        self.pending_start = Some(pending);
+
+        tracing::info!(
+            schedule.delay_secs = delay.as_secs(),
+            schedule.starts_at = pending.starts_at_unix_secs,
+            "Scheduled automatic game start"
+        );
+
+        self.broadcast_countdown(pending).await;
+    }
+
+    async fn cancel_scheduled_start(&mut self) {
+        if self.pending_start.take().is_none() {
+            tracing::debug!("No scheduled start to cancel");
+            return;
+        }
+
+        tracing::info!("Scheduled game start cancelled");
+        self.broadcast_global_event("StartCountdownCancelled", &serde_json::json!({}))
+            .await;
+    }
+
+    async fn fire_scheduled_start(&mut self) {
+        if self.pending_start.take().is_none() {
+            return;
+        }
+        if self.game_engine.phase_name() != SETUP_PHASE {
+            tracing::debug!(
+                game.phase = self.game_engine.phase_name(),
+                "Scheduled start reached after the game already started"
+            );
+            return;
+        }
+
+        tracing::info!("Scheduled start reached. Starting game");
+        self.broadcast_global_event(
+            "StartCountdown",
+            &serde_json::json!({ "starts_at": unix_now_secs(), "remaining_seconds": 0 }),
+        )
+        .await;
+        self.game_engine.start_game().await;
+    }
+
+    fn countdown_payload(pending: PendingStart) -> JsonValue {
+        let remaining = pending
+            .starts_at
+            .saturating_duration_since(Instant::now())
+            .as_secs_f64()
+            .ceil() as u64;
+        serde_json::json!({
+            "starts_at": pending.starts_at_unix_secs,
+            "remaining_seconds": remaining
+        })
+    }
+
+    async fn broadcast_countdown(&self, pending: PendingStart) {
+        self.broadcast_global_event("StartCountdown", &Self::countdown_payload(pending))
+            .await;
+    }
+
+    async fn send_countdown_to_client(&self, client_id: Uuid, pending: PendingStart) {
+        self.send_global_event_to_client(
+            client_id,
+            "StartCountdown",
+            &Self::countdown_payload(pending),
+        )
+        .await;
+    }
+
+    async fn broadcast_global_event(&self, event_name: &str, data: &JsonValue) {
+        let message = match ServerToClientMessage::new_global_event(event_name.to_string(), data) {
+            Ok(msg) => msg,
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    event.name = event_name,
+                    "Failed to serialize global event"
+                );
+                return;
+            }
+        };
+
+        if let Ok(ws_msg) = message.to_ws_text() {
+            for client_id in self.game_engine.get_all_client_ids() {
+                if let Some(client_tx) = self.game_engine.get_client_tx(client_id)
+                    && client_tx.send(ws_msg.clone()).await.is_err()
+                {
+                    tracing::warn!(
+                        client.id = %client_id,
+                        event.name = event_name,
+                        "Failed to send global event to client"
+                    );
+                }
+            }
+        }
+    }
+
     async fn broadcast_twitch_status_update(&self, status: TwitchChannelConnectionStatus) {
         let (status_type, details) = match &status {
             TwitchChannelConnectionStatus::Initializing => ("Initializing".to_string(), None),
@@ -583,12 +1609,19 @@ impl<G: GameLogic + Send + 'static> LobbyActor<G> {
                     let actor_sender_clone = self_sender.clone();
                     self._twitch_message_task_handle = Some(tokio::spawn(async move {
                         tracing::debug!("Twitch message listener task started");
-                        while let Some(twitch_msg) = rx_for_lobby_messages.recv().await {
-                            if actor_sender_clone
-                                .send(LobbyActorMessage::InternalTwitchMessage(twitch_msg))
-                                .await
-                                .is_err()
-                            {
+                        while let Some(twitch_event) = rx_for_lobby_messages.recv().await {
+                            let lobby_message = match twitch_event {
+                                TwitchChannelEvent::Message(twitch_msg) => {
+                                    LobbyActorMessage::InternalTwitchMessage(twitch_msg)
+                                }
+                                TwitchChannelEvent::Moderation(moderation) => {
+                                    LobbyActorMessage::InternalChatModeration(moderation)
+                                }
+                                TwitchChannelEvent::Notice(notice) => {
+                                    LobbyActorMessage::InternalUserNotice(notice)
+                                }
+                            };
+                            if actor_sender_clone.send(lobby_message).await.is_err() {
                                 tracing::debug!(
                                     "Lobby shutting down before Twitch message relay finished"
                                 );
@@ -634,6 +1667,41 @@ impl<G: GameLogic + Send + 'static> LobbyActor<G> {
     }
 }
 
+/// Removes host-only game data from a message destined for a viewer.
+fn redact_for_viewer<G: GameLogic>(message: ws::Message) -> ws::Message {
+    let ws::Message::Text(text) = &message else {
+        return message;
+    };
+    let Ok(mut parsed) = serde_json::from_str::<ServerToClientMessage>(text.as_str()) else {
+        return message;
+    };
+    if let ServerToClientMessage::GameSpecificEvent { event_data, .. } = &mut parsed {
+        G::redact_public_event(event_data);
+        return parsed.to_ws_text().unwrap_or(message);
+    }
+    message
+}
+
+/// Routes a viewer's outgoing messages through a task that redacts them. The
+/// relay ends, closing the viewer's connection, once the game drops its sender.
+fn spawn_viewer_relay<G: GameLogic + 'static>(
+    client_tx: mpsc::Sender<ws::Message>,
+) -> mpsc::Sender<ws::Message> {
+    let (relay_tx, mut relay_rx) = mpsc::channel::<ws::Message>(32);
+    tokio::spawn(async move {
+        while let Some(message) = relay_rx.recv().await {
+            if client_tx
+                .send(redact_for_viewer::<G>(message))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+    relay_tx
+}
+
 #[tracing::instrument(skip(actor, self_sender), fields(
     lobby.id = %actor.lobby_id,
     game.type = %actor.game_engine.game_type_id(),
@@ -649,17 +1717,24 @@ pub async fn run_lobby_actor<G: GameLogic + Send + 'static>(
 
     let client_ws_inactivity_timeout_duration = StdDuration::from_secs(60 * 60);
     let mut last_client_ws_activity = Instant::now();
+    let mut countdown_interval = tokio::time::interval(COUNTDOWN_BROADCAST_INTERVAL);
+    countdown_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
     loop {
         tokio::select! {
             maybe_msg = actor.receiver.recv() => {
                 match maybe_msg {
                     Some(msg) => {
-                        if matches!(msg, LobbyActorMessage::ClientEvent { .. }) {
+                        if matches!(
+                            msg,
+                            LobbyActorMessage::ClientEvent { .. }
+                                | LobbyActorMessage::ExecuteCommand { .. }
+                        ) {
                             last_client_ws_activity = Instant::now();
                             tracing::trace!("Client WS activity detected. Resetting inactivity timer");
                         }
                         let should_shutdown = actor.handle_message(msg, &self_sender).await;
+                        actor.publish_game_updates().await;
                         if should_shutdown {
                             tracing::info!("Lobby shutdown requested by message handler");
                             break;
@@ -671,6 +1746,17 @@ pub async fn run_lobby_actor<G: GameLogic + Send + 'static>(
                     }
                 }
             }
+            _ = tokio::time::sleep_until(
+                actor.pending_start.map_or_else(Instant::now, |pending| pending.starts_at)
+            ), if actor.pending_start.is_some() => {
+                actor.fire_scheduled_start().await;
+                actor.publish_game_updates().await;
+            }
+            _ = countdown_interval.tick(), if actor.pending_start.is_some() => {
+                if let Some(pending) = actor.pending_start {
+                    actor.broadcast_countdown(pending).await;
+                }
+            }
             _ = tokio::time::sleep_until(last_client_ws_activity + client_ws_inactivity_timeout_duration), if !actor.game_engine.is_empty() => {
                  // Only run inactivity timeout if there are clients.
                 tracing::info!("Lobby inactivity timeout. Shutting down lobby");
@@ -720,11 +1806,13 @@ pub async fn run_lobby_actor<G: GameLogic + Send + 'static>(
 pub struct LobbyActorHandle {
     pub sender: mpsc::Sender<LobbyActorMessage>,
     pub lobby_id: Uuid,
+    pub twitch_channel: Option<String>,
 }
 
 impl LobbyActorHandle {
     pub fn spawn<G: GameLogic + Send + 'static>(
         lobby_id: Uuid,
+        admin_id: Uuid,
         buffer_size: usize,
         active_lobbies: Arc<DashMap<Uuid, LobbyActorHandle>>,
         game_engine_instance: G,
@@ -732,16 +1820,22 @@ impl LobbyActorHandle {
         twitch_service_handle: TwitchServiceHandle,
     ) -> Self {
         let (sender, receiver) = mpsc::channel(buffer_size);
+        let twitch_channel = twitch_channel_name.clone();
         let actor = LobbyActor::<G>::new(
             receiver,
             lobby_id,
+            admin_id,
             game_engine_instance,
             active_lobbies,
             twitch_channel_name,
             twitch_service_handle,
         );
         tokio::spawn(run_lobby_actor::<G>(actor, sender.clone()));
-        Self { sender, lobby_id }
+        Self {
+            sender,
+            lobby_id,
+            twitch_channel,
+        }
     }
 
     pub async fn forward_client_event(
@@ -758,12 +1852,18 @@ impl LobbyActorHandle {
             .map_err(|e| format!("Failed to send event: {}", e))
     }
 
-    pub async fn client_connected(&self, client_id: Uuid, client_tx: mpsc::Sender<ws::Message>) {
+    pub async fn client_connected(
+        &self,
+        client_id: Uuid,
+        client_tx: mpsc::Sender<ws::Message>,
+        admin_token: Option<Uuid>,
+    ) {
         if self
             .sender
             .send(LobbyActorMessage::ClientConnected {
                 client_id,
                 client_tx,
+                admin_token,
             })
             .await
             .is_err()
@@ -775,6 +1875,98 @@ impl LobbyActorHandle {
         }
     }
 
+    pub async fn schedule_start(&self, delay: StdDuration) {
+        if self
+            .sender
+            .send(LobbyActorMessage::ScheduleStart { delay })
+            .await
+            .is_err()
+        {
+            tracing::debug!(
+                lobby.id = %self.lobby_id,
+                "Lobby actor dropped before ScheduleStart message delivered"
+            );
+        }
+    }
+
+    pub async fn execute_command(
+        &self,
+        admin_token: Uuid,
+        message: ClientToServerMessage,
+    ) -> Result<(), LobbyCommandError> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(LobbyActorMessage::ExecuteCommand {
+                admin_token,
+                message,
+                respond_to,
+            })
+            .await
+            .map_err(|_| LobbyCommandError::LobbyUnavailable)?;
+        response
+            .await
+            .map_err(|_| LobbyCommandError::LobbyUnavailable)?
+    }
+
+    pub async fn attach_obs(&self, obs: ObsHandle) {
+        if self
+            .sender
+            .send(LobbyActorMessage::AttachObs(obs))
+            .await
+            .is_err()
+        {
+            tracing::debug!(
+                lobby.id = %self.lobby_id,
+                "Lobby actor dropped before AttachObs message delivered"
+            );
+        }
+    }
+
+    pub async fn attach_webhooks(&self, webhooks: WebhookHandle) {
+        if self
+            .sender
+            .send(LobbyActorMessage::AttachWebhooks(webhooks))
+            .await
+            .is_err()
+        {
+            tracing::debug!(
+                lobby.id = %self.lobby_id,
+                "Lobby actor dropped before AttachWebhooks message delivered"
+            );
+        }
+    }
+
+    pub async fn attach_chat_announcer(&self, announcer: ChatAnnouncer) {
+        if self
+            .sender
+            .send(LobbyActorMessage::AttachChatAnnouncer(announcer))
+            .await
+            .is_err()
+        {
+            tracing::debug!(
+                lobby.id = %self.lobby_id,
+                "Lobby actor dropped before AttachChatAnnouncer message delivered"
+            );
+        }
+    }
+
+    pub async fn state_snapshot(
+        &self,
+        admin_token: Option<Uuid>,
+    ) -> Result<LobbyStateSnapshot, LobbyCommandError> {
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(LobbyActorMessage::GetStateSnapshot {
+                admin_token,
+                respond_to,
+            })
+            .await
+            .map_err(|_| LobbyCommandError::LobbyUnavailable)?;
+        response
+            .await
+            .map_err(|_| LobbyCommandError::LobbyUnavailable)?
+    }
+
     pub async fn client_disconnected(&self, client_id: Uuid) {
         if self
             .sender
@@ -789,3 +1981,150 @@ impl LobbyActorHandle {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheduled_start_delay() {
+        let now = 1_700_000_000;
+        assert_eq!(
+            ScheduledStart::InMinutes(5).delay_from(now),
+            Ok(StdDuration::from_secs(300))
+        );
+        assert_eq!(
+            ScheduledStart::At(now + 90).delay_from(now),
+            Ok(StdDuration::from_secs(90))
+        );
+        assert!(ScheduledStart::At(now).delay_from(now).is_err());
+        assert!(ScheduledStart::InMinutes(0).delay_from(now).is_err());
+        assert!(
+            ScheduledStart::InMinutes(24 * 60 + 1)
+                .delay_from(now)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_scheduled_start_deserializes_both_forms() {
+        let absolute: ScheduledStart = serde_json::from_str(r#"{"at": 1767225600}"#).unwrap();
+        assert_eq!(absolute, ScheduledStart::At(1_767_225_600));
+        let relative: ScheduledStart = serde_json::from_str(r#"{"in_minutes": 10}"#).unwrap();
+        assert_eq!(relative, ScheduledStart::InMinutes(10));
+    }
+
+    /// Names of the global events and game events queued for a client so far.
+    fn drain_event_names(client_rx: &mut mpsc::Receiver<ws::Message>) -> Vec<String> {
+        let mut names = Vec::new();
+        while let Ok(ws::Message::Text(text)) = client_rx.try_recv() {
+            let message: JsonValue = serde_json::from_str(&text).unwrap();
+            let payload = &message["payload"];
+            let name = payload["event_name"]
+                .as_str()
+                .or_else(|| payload["event_data"]["event_type"].as_str())
+                .unwrap_or_else(|| message["messageType"].as_str().unwrap());
+            names.push(name.to_string());
+        }
+        names
+    }
+
+    #[tokio::test]
+    async fn test_manual_start_cancels_scheduled_start() {
+        let app_state = crate::state::AppState::for_tests().await;
+        let admin_id = Uuid::new_v4();
+        let lobby = LobbyActorHandle::spawn(
+            Uuid::new_v4(),
+            admin_id,
+            8,
+            Arc::new(DashMap::new()),
+            DealNoDealGame::new(&Default::default()),
+            None,
+            app_state.twitch_service,
+        );
+        let client_id = Uuid::new_v4();
+        let (client_tx, mut client_rx) = mpsc::channel(64);
+        lobby
+            .client_connected(client_id, client_tx, Some(admin_id))
+            .await;
+        lobby.schedule_start(StdDuration::from_millis(300)).await;
+
+        let start = ClientToServerMessage::GameSpecificCommand {
+            game_type_id: "DealNoDeal".to_string(),
+            command_data: serde_json::json!({ "command": "StartGame" }),
+        };
+        lobby.execute_command(admin_id, start).await.unwrap();
+        let started = lobby.state_snapshot(Some(admin_id)).await.unwrap().state;
+        assert!(drain_event_names(&mut client_rx).contains(&"StartCountdownCancelled".to_string()));
+
+        tokio::time::sleep(StdDuration::from_millis(1500)).await;
+        let later = lobby.state_snapshot(Some(admin_id)).await.unwrap().state;
+        assert_eq!(later, started);
+        assert_eq!(drain_event_names(&mut client_rx), Vec::<String>::new());
+
+        let schedule = serde_json::json!({
+            "messageType": "GlobalCommand",
+            "payload": { "command_name": "ScheduleStart", "data": { "in_minutes": 5 } }
+        });
+        lobby
+            .forward_client_event(client_id, schedule.to_string())
+            .await
+            .unwrap();
+        lobby.state_snapshot(None).await.unwrap();
+        assert_eq!(drain_event_names(&mut client_rx), ["SystemError"]);
+    }
+
+    #[tokio::test]
+    async fn test_validate_creation_options() {
+        let (settings, content) = crate::state::test_settings_and_content().await;
+        let validate = |options: LobbyCreationOptions| {
+            let settings = Arc::clone(&settings);
+            let content = &content;
+            async move { validate_creation_options(content, settings, &options).await }
+        };
+
+        validate(LobbyCreationOptions {
+            game_type: Some("quiz".to_string()),
+            settings: Some(serde_json::json!({ "target_points": 5 })),
+            scheduled_start: Some(ScheduledStart::InMinutes(10)),
+            ..LobbyCreationOptions::default()
+        })
+        .await
+        .unwrap();
+        assert!(matches!(
+            validate(LobbyCreationOptions {
+                game_type: Some("quiz".to_string()),
+                settings: Some(serde_json::json!({ "target_points": 0 })),
+                ..LobbyCreationOptions::default()
+            })
+            .await,
+            Err(LobbyCreationError::InvalidSettings { .. })
+        ));
+        assert!(matches!(
+            validate(LobbyCreationOptions {
+                game_type: Some("dealnodeal".to_string()),
+                scheduled_start: Some(ScheduledStart::InMinutes(0)),
+                ..LobbyCreationOptions::default()
+            })
+            .await,
+            Err(LobbyCreationError::InvalidSchedule { .. })
+        ));
+        assert!(matches!(
+            validate(LobbyCreationOptions {
+                game_type: Some("clipqueue".to_string()),
+                scheduled_start: Some(ScheduledStart::InMinutes(10)),
+                ..LobbyCreationOptions::default()
+            })
+            .await,
+            Err(LobbyCreationError::InvalidSchedule { .. })
+        ));
+        assert!(matches!(
+            validate(LobbyCreationOptions {
+                game_type: Some("chess".to_string()),
+                ..LobbyCreationOptions::default()
+            })
+            .await,
+            Err(LobbyCreationError::UnknownGameType { .. })
+        ));
+    }
+}
@@ -2,22 +2,31 @@ use dashmap::DashMap;
 use std::sync::Arc;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+mod cohost;
 mod config;
 mod content;
 mod error;
 mod game_logic;
 mod lobby;
+mod obs;
+mod presets;
+mod reload;
 mod state;
+mod streamers;
 mod twitch;
 mod web;
+mod webhooks;
 
-use crate::config::load_settings;
+use crate::config::{CliOptions, SharedSettings, load_settings};
 use crate::content::GameContentCache;
-use crate::error::Result as AppResult;
+use crate::error::{AppError, ConfigError, Result as AppResult};
+use crate::presets::PresetStore;
 use crate::state::AppState;
+use crate::streamers::StreamerStore;
 use crate::twitch::TokenProvider;
 use crate::twitch::TwitchServiceHandle;
-use crate::web::run_server;
+use crate::web::twitch_login::LoginSessions;
+use crate::web::{RouterHandle, run_server};
 
 #[tracing::instrument(name = "main")]
 #[tokio::main]
@@ -37,9 +46,22 @@ async fn main() -> AppResult<()> {
 
     tracing::info!("Application starting...");
 
-    let app_settings = load_settings()?;
+    // Every rustls user (TLS serving, Twitch, OBS, webhooks) shares the ring provider.
+    if rustls::crypto::ring::default_provider()
+        .install_default()
+        .is_err()
+    {
+        tracing::warn!("A rustls crypto provider was already installed");
+    }
+
+    let cli_options = CliOptions::parse(std::env::args().skip(1))?;
+    if cli_options.check_config {
+        return check_config(&cli_options);
+    }
+
+    let app_settings = load_settings(cli_options.config_file.as_deref())?;
     tracing::info!(
-        config.source = "environment variables",
+        config.file = ?cli_options.config_file,
         "Configuration loaded successfully"
     );
 
@@ -54,6 +76,10 @@ async fn main() -> AppResult<()> {
         "GameContentCache initialized"
     );
 
+    let preset_store = Arc::new(PresetStore::load(&app_settings.presets).await?);
+    let streamer_store =
+        Arc::new(StreamerStore::load(&app_settings.streamers, &initial_whitelist).await?);
+
     let token_provider = TokenProvider::new(Arc::new(app_settings.twitch.clone())).await?;
     tracing::info!("TokenProvider initialized");
 
@@ -61,28 +87,50 @@ async fn main() -> AppResult<()> {
     tracing::info!("TwitchService started");
 
     let active_lobbies = Arc::new(DashMap::new());
-    let server_config_for_state = Arc::new(app_settings.server.clone());
-    let shared_app_settings = Arc::new(app_settings.clone());
-    let games_config = app_settings.games.clone();
     let server_config_for_run = app_settings.server.clone();
 
     let app_state = AppState {
         active_lobbies,
         game_content_cache,
-        server_config: server_config_for_state,
-        games_config,
         twitch_service: twitch_service_handle,
-        app_settings: shared_app_settings,
+        settings: SharedSettings::new(app_settings),
+        preset_store,
+        streamer_store,
+        login_sessions: Arc::new(LoginSessions::new()),
     };
 
+    let router = RouterHandle::new(app_state.clone(), &server_config_for_run);
+    reload::spawn_sighup_listener(cli_options.config_file, app_state, router.clone());
+
     tracing::info!(
         server.port = server_config_for_run.port,
         server.cors_origins.count = server_config_for_run.cors_origins.len(),
         "Starting HTTP server"
     );
 
-    run_server(app_state, server_config_for_run).await?;
+    run_server(router, server_config_for_run).await?;
 
     tracing::info!("Application shutting down");
     Ok(())
 }
+
+/// `--check-config`: loads and validates the configuration, printing every problem.
+fn check_config(cli_options: &CliOptions) -> AppResult<()> {
+    match load_settings(cli_options.config_file.as_deref()) {
+        Ok(_) => {
+            println!("Configuration OK");
+            Ok(())
+        }
+        Err(AppError::Config(ConfigError::Invalid(problems))) => {
+            eprintln!("Found {} configuration problem(s):", problems.len());
+            for problem in &problems {
+                eprintln!("  - {}", problem);
+            }
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to load configuration: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
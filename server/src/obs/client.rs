@@ -0,0 +1,360 @@
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{Value as JsonValue, json};
+use std::time::Duration as StdDuration;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message};
+use uuid::Uuid;
+
+use super::error::ObsError;
+use super::protocol;
+use super::types::{ObsAction, ObsActionKind, ObsLobbyConfig};
+
+const OBS_RESPONSE_TIMEOUT: StdDuration = StdDuration::from_secs(5);
+
+type ObsSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// An identified OBS WebSocket v5 session.
+pub struct ObsConnection {
+    socket: ObsSocket,
+}
+
+impl ObsConnection {
+    #[tracing::instrument(skip(password))]
+    pub async fn connect(url: &str, password: Option<&str>) -> Result<Self, ObsError> {
+        let (socket, _) = tokio::time::timeout(OBS_RESPONSE_TIMEOUT, connect_async(url))
+            .await
+            .map_err(|_| ObsError::Timeout)?
+            .map_err(|e| ObsError::Connection(e.to_string()))?;
+        let mut connection = Self { socket };
+
+        let hello = connection.read_json().await?;
+        if hello["op"].as_u64() != Some(protocol::OP_HELLO) {
+            return Err(ObsError::Protocol(format!(
+                "Expected Hello, got op {}",
+                hello["op"]
+            )));
+        }
+
+        let authentication = match hello["d"].get("authentication") {
+            Some(auth) => {
+                let password = password.ok_or_else(|| {
+                    ObsError::Auth("OBS requires a password but none is configured".to_string())
+                })?;
+                let salt = auth["salt"].as_str().unwrap_or_default();
+                let challenge = auth["challenge"].as_str().unwrap_or_default();
+                Some(protocol::authentication_string(password, salt, challenge))
+            }
+            None => None,
+        };
+
+        connection
+            .send_json(protocol::identify_message(authentication))
+            .await?;
+
+        let identified = connection
+            .read_json()
+            .await
+            .map_err(|e| ObsError::Auth(format!("OBS rejected identification: {}", e)))?;
+        if identified["op"].as_u64() != Some(protocol::OP_IDENTIFIED) {
+            return Err(ObsError::Protocol(format!(
+                "Expected Identified, got op {}",
+                identified["op"]
+            )));
+        }
+
+        tracing::info!(obs.url = %url, "Connected to OBS WebSocket");
+        Ok(connection)
+    }
+
+    /// Sends a request and waits for its response, returning `responseData`.
+    pub async fn request(
+        &mut self,
+        request_type: &str,
+        request_data: JsonValue,
+    ) -> Result<JsonValue, ObsError> {
+        let request_id = Uuid::new_v4().to_string();
+        self.send_json(protocol::request_message(
+            request_type,
+            &request_id,
+            request_data,
+        ))
+        .await?;
+
+        loop {
+            let message = self.read_json().await?;
+            if message["op"].as_u64() != Some(protocol::OP_REQUEST_RESPONSE)
+                || message["d"]["requestId"].as_str() != Some(request_id.as_str())
+            {
+                continue;
+            }
+
+            let status = &message["d"]["requestStatus"];
+            if status["result"].as_bool() != Some(true) {
+                return Err(ObsError::RequestFailed {
+                    request_type: request_type.to_string(),
+                    code: status["code"].as_u64().unwrap_or(0),
+                    comment: status["comment"].as_str().unwrap_or_default().to_string(),
+                });
+            }
+            return Ok(message["d"]
+                .get("responseData")
+                .cloned()
+                .unwrap_or(JsonValue::Null));
+        }
+    }
+
+    pub async fn run_action(&mut self, action: &ObsAction) -> Result<(), ObsError> {
+        match &action.kind {
+            ObsActionKind::SwitchScene { scene } => {
+                self.request("SetCurrentProgramScene", json!({ "sceneName": scene }))
+                    .await?;
+            }
+            ObsActionKind::SetSourceVisibility {
+                scene,
+                source,
+                visible,
+            } => {
+                let response = self
+                    .request(
+                        "GetSceneItemId",
+                        json!({ "sceneName": scene, "sourceName": source }),
+                    )
+                    .await?;
+                let scene_item_id = response["sceneItemId"].as_u64().ok_or_else(|| {
+                    ObsError::Protocol("GetSceneItemId response had no sceneItemId".to_string())
+                })?;
+                self.request(
+                    "SetSceneItemEnabled",
+                    json!({
+                        "sceneName": scene,
+                        "sceneItemId": scene_item_id,
+                        "sceneItemEnabled": visible
+                    }),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn send_json(&mut self, value: JsonValue) -> Result<(), ObsError> {
+        self.socket
+            .send(Message::text(value.to_string()))
+            .await
+            .map_err(|e| ObsError::Connection(e.to_string()))
+    }
+
+    async fn read_json(&mut self) -> Result<JsonValue, ObsError> {
+        loop {
+            let message = tokio::time::timeout(OBS_RESPONSE_TIMEOUT, self.socket.next())
+                .await
+                .map_err(|_| ObsError::Timeout)?;
+            match message {
+                Some(Ok(Message::Text(text))) => {
+                    return serde_json::from_str(text.as_str())
+                        .map_err(|e| ObsError::Protocol(format!("Invalid JSON from OBS: {}", e)));
+                }
+                Some(Ok(Message::Close(frame))) => {
+                    let reason = frame
+                        .map(|f| format!("{} ({})", f.reason, u16::from(f.code)))
+                        .unwrap_or_else(|| "no reason given".to_string());
+                    return Err(ObsError::Connection(format!(
+                        "OBS closed the connection: {}",
+                        reason
+                    )));
+                }
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => return Err(ObsError::Connection(e.to_string())),
+                None => {
+                    return Err(ObsError::Connection(
+                        "OBS closed the connection".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ObsCommand {
+    PhaseChanged { phase: String },
+}
+
+/// Drives one lobby's OBS integration. The actor stops, closing the OBS
+/// connection, once every handle (i.e. the lobby) is dropped.
+#[derive(Clone, Debug)]
+pub struct ObsHandle {
+    sender: mpsc::Sender<ObsCommand>,
+}
+
+impl ObsHandle {
+    pub fn spawn(lobby_id: Uuid, config: ObsLobbyConfig) -> Self {
+        let (sender, receiver) = mpsc::channel(16);
+        tokio::spawn(run_obs_actor(lobby_id, config, receiver));
+        Self { sender }
+    }
+
+    /// Queues the actions configured for `phase`. Never blocks the lobby; if OBS
+    /// is falling behind, the phase change is dropped.
+    pub fn phase_changed(&self, phase: &str) {
+        if let Err(e) = self.sender.try_send(ObsCommand::PhaseChanged {
+            phase: phase.to_string(),
+        }) {
+            tracing::warn!(error = %e, game.phase = phase, "Dropped OBS phase change");
+        }
+    }
+}
+
+#[tracing::instrument(skip(config, receiver), fields(lobby.id = %lobby_id, obs.url = %config.url))]
+async fn run_obs_actor(
+    lobby_id: Uuid,
+    config: ObsLobbyConfig,
+    mut receiver: mpsc::Receiver<ObsCommand>,
+) {
+    tracing::info!("OBS actor started");
+    let mut connection: Option<ObsConnection> = None;
+
+    while let Some(command) = receiver.recv().await {
+        let ObsCommand::PhaseChanged { phase } = command;
+        for action in config.actions_for_phase(&phase) {
+            if connection.is_none() {
+                match ObsConnection::connect(&config.url, config.password.as_deref()).await {
+                    Ok(new_connection) => connection = Some(new_connection),
+                    Err(e) => {
+                        tracing::warn!(error = %e, game.phase = %phase, "Could not connect to OBS");
+                        break;
+                    }
+                }
+            }
+
+            let Some(active) = connection.as_mut() else {
+                break;
+            };
+            match active.run_action(action).await {
+                Ok(()) => {
+                    tracing::debug!(game.phase = %phase, obs.action = ?action.kind, "Ran OBS action");
+                }
+                Err(e @ ObsError::RequestFailed { .. }) => {
+                    tracing::warn!(error = %e, game.phase = %phase, "OBS rejected action");
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, game.phase = %phase, "OBS connection lost");
+                    // Reconnect on the next action.
+                    connection = None;
+                }
+            }
+        }
+    }
+
+    if let Some(mut active) = connection {
+        let _ = active.socket.close(None).await;
+    }
+    tracing::info!("OBS actor stopped");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    const PASSWORD: &str = "hunter2";
+    const SALT: &str = "salt";
+    const CHALLENGE: &str = "challenge";
+
+    /// Accepts one connection, performs the authenticated handshake and reports
+    /// every request it receives, answering each with success.
+    async fn spawn_mock_obs() -> (String, mpsc::Receiver<JsonValue>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (requests_tx, requests_rx) = mpsc::channel(16);
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let hello = json!({
+                "op": 0,
+                "d": {
+                    "obsWebSocketVersion": "5.0.0",
+                    "rpcVersion": 1,
+                    "authentication": { "challenge": CHALLENGE, "salt": SALT }
+                }
+            });
+            socket.send(Message::text(hello.to_string())).await.unwrap();
+
+            let Some(Ok(Message::Text(identify))) = socket.next().await else {
+                return;
+            };
+            let identify: JsonValue = serde_json::from_str(identify.as_str()).unwrap();
+            assert_eq!(
+                identify["d"]["authentication"].as_str(),
+                Some(protocol::authentication_string(PASSWORD, SALT, CHALLENGE).as_str())
+            );
+            let identified = json!({ "op": 2, "d": { "negotiatedRpcVersion": 1 } });
+            socket
+                .send(Message::text(identified.to_string()))
+                .await
+                .unwrap();
+
+            while let Some(Ok(Message::Text(text))) = socket.next().await {
+                let request: JsonValue = serde_json::from_str(text.as_str()).unwrap();
+                let response = json!({
+                    "op": 7,
+                    "d": {
+                        "requestType": request["d"]["requestType"],
+                        "requestId": request["d"]["requestId"],
+                        "requestStatus": { "result": true, "code": 100 },
+                        "responseData": { "sceneItemId": 7 }
+                    }
+                });
+                socket
+                    .send(Message::text(response.to_string()))
+                    .await
+                    .unwrap();
+                let _ = requests_tx.send(request["d"].clone()).await;
+            }
+        });
+
+        (url, requests_rx)
+    }
+
+    async fn next_request(requests: &mut mpsc::Receiver<JsonValue>) -> JsonValue {
+        tokio::time::timeout(StdDuration::from_secs(5), requests.recv())
+            .await
+            .expect("mock OBS received no request")
+            .expect("mock OBS stopped")
+    }
+
+    #[tokio::test]
+    async fn test_phase_change_runs_configured_actions_against_mock_obs() {
+        let (url, mut requests) = spawn_mock_obs().await;
+        let config: ObsLobbyConfig = serde_json::from_value(json!({
+            "url": url,
+            "password": PASSWORD,
+            "actions": [
+                { "phase": "GameOver", "switch_scene": { "scene": "Results" } },
+                {
+                    "phase": "GameOver",
+                    "set_source_visibility": { "scene": "Results", "source": "Confetti", "visible": true }
+                },
+                { "phase": "Setup", "switch_scene": { "scene": "Lobby" } }
+            ]
+        }))
+        .unwrap();
+
+        let handle = ObsHandle::spawn(Uuid::new_v4(), config);
+        handle.phase_changed("Playing");
+        handle.phase_changed("GameOver");
+
+        let first = next_request(&mut requests).await;
+        assert_eq!(first["requestType"], "SetCurrentProgramScene");
+        assert_eq!(first["requestData"]["sceneName"], "Results");
+        let second = next_request(&mut requests).await;
+        assert_eq!(second["requestType"], "GetSceneItemId");
+        assert_eq!(second["requestData"]["sourceName"], "Confetti");
+        let third = next_request(&mut requests).await;
+        assert_eq!(third["requestType"], "SetSceneItemEnabled");
+        assert_eq!(third["requestData"]["sceneItemId"], 7);
+        assert_eq!(third["requestData"]["sceneItemEnabled"], true);
+    }
+}
@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ObsError {
+    #[error("OBS WebSocket connection error: {0}")]
+    Connection(String),
+    #[error("OBS WebSocket protocol error: {0}")]
+    Protocol(String),
+    #[error("OBS authentication failed: {0}")]
+    Auth(String),
+    #[error("OBS request '{request_type}' failed (code {code}): {comment}")]
+    RequestFailed {
+        request_type: String,
+        code: u64,
+        comment: String,
+    },
+    #[error("Timed out waiting for OBS")]
+    Timeout,
+}
@@ -0,0 +1,7 @@
+pub mod client;
+pub mod error;
+pub mod protocol;
+pub mod types;
+
+pub use client::ObsHandle;
+pub use types::ObsLobbyConfig;
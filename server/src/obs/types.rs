@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+
+const MAX_OBS_ACTIONS: usize = 32;
+
+/// Per-lobby OBS WebSocket (v5) settings sent with `create-lobby`.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ObsLobbyConfig {
+    /// e.g. `ws://192.168.1.20:4455`
+    pub url: String,
+    pub password: Option<String>,
+    pub actions: Vec<ObsAction>,
+}
+
+impl std::fmt::Debug for ObsLobbyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObsLobbyConfig")
+            .field("url", &self.url)
+            .field("actions", &self.actions)
+            .finish()
+    }
+}
+
+/// What to do in OBS when the game enters `phase` (the phase's `type` tag,
+/// e.g. `DealOrNoDealVoting` or `GameOver`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ObsAction {
+    pub phase: String,
+    #[serde(flatten)]
+    pub kind: ObsActionKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObsActionKind {
+    /// Switch the program scene.
+    SwitchScene { scene: String },
+    /// Show or hide a source within a scene.
+    SetSourceVisibility {
+        scene: String,
+        source: String,
+        visible: bool,
+    },
+}
+
+impl ObsLobbyConfig {
+    /// Checks the connection URL and actions; every action's phase must be one of
+    /// `phases`, the names the lobby's game can report.
+    pub fn validate(&self, phases: &[&str]) -> Result<(), String> {
+        if !(self.url.starts_with("ws://") || self.url.starts_with("wss://")) {
+            return Err("OBS url must start with ws:// or wss://".to_string());
+        }
+        if self.actions.is_empty() {
+            return Err("At least one OBS action is required".to_string());
+        }
+        if self.actions.len() > MAX_OBS_ACTIONS {
+            return Err(format!(
+                "At most {} OBS actions are allowed",
+                MAX_OBS_ACTIONS
+            ));
+        }
+        for action in &self.actions {
+            if action.phase.trim().is_empty() {
+                return Err("OBS action phase must not be empty".to_string());
+            }
+            if !phases.contains(&action.phase.as_str()) {
+                return Err(format!(
+                    "OBS action phase '{}' is not a phase of this game; expected one of: {}",
+                    action.phase,
+                    phases.join(", ")
+                ));
+            }
+            let names_empty = match &action.kind {
+                ObsActionKind::SwitchScene { scene } => scene.trim().is_empty(),
+                ObsActionKind::SetSourceVisibility { scene, source, .. } => {
+                    scene.trim().is_empty() || source.trim().is_empty()
+                }
+            };
+            if names_empty {
+                return Err(format!(
+                    "OBS action for phase '{}' has an empty scene or source name",
+                    action.phase
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn actions_for_phase<'a>(&'a self, phase: &'a str) -> impl Iterator<Item = &'a ObsAction> {
+        self.actions
+            .iter()
+            .filter(move |action| action.phase == phase)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_logic::GameType;
+
+    #[test]
+    fn test_parse_and_validate_actions() {
+        let config: ObsLobbyConfig = serde_json::from_value(serde_json::json!({
+            "url": "ws://localhost:4455",
+            "password": "secret",
+            "actions": [
+                { "phase": "DealOrNoDealVoting", "switch_scene": { "scene": "Banker" } },
+                {
+                    "phase": "GameOver",
+                    "set_source_visibility": { "scene": "Game", "source": "Confetti", "visible": true }
+                }
+            ]
+        }))
+        .unwrap();
+
+        let phases = GameType::DealNoDeal.phase_names();
+        assert!(config.validate(phases).is_ok());
+        assert_eq!(config.actions_for_phase("GameOver").count(), 1);
+        assert_eq!(config.actions_for_phase("Setup").count(), 0);
+        assert!(!format!("{:?}", config).contains("secret"));
+
+        let misspelled: ObsLobbyConfig = serde_json::from_value(serde_json::json!({
+            "url": "ws://localhost:4455",
+            "actions": [{ "phase": "DealOrNoDealVote", "switch_scene": { "scene": "Banker" } }]
+        }))
+        .unwrap();
+        assert!(misspelled.validate(phases).is_err());
+        // Valid for Deal or No Deal, but the quiz has no voting phases.
+        assert!(config.validate(GameType::Quiz.phase_names()).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_url() {
+        let config = ObsLobbyConfig {
+            url: "http://localhost:4455".to_string(),
+            password: None,
+            actions: vec![ObsAction {
+                phase: "GameOver".to_string(),
+                kind: ObsActionKind::SwitchScene {
+                    scene: "End".to_string(),
+                },
+            }],
+        };
+        assert!(config.validate(GameType::Quiz.phase_names()).is_err());
+    }
+}
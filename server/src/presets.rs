@@ -0,0 +1,294 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+
+use crate::config::PresetsConfig;
+use crate::error::PresetError;
+use crate::lobby::LobbyCreationOptions;
+
+const MAX_PRESET_NAME_LENGTH: usize = 64;
+
+/// A named, reusable lobby configuration (game type, channel and initial settings).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LobbyPreset {
+    pub name: String,
+    #[serde(flatten)]
+    pub options: LobbyCreationOptions,
+}
+
+impl LobbyPreset {
+    /// Builds the options for a new lobby, letting any explicitly requested value
+    /// take precedence over the one stored in the preset.
+    pub fn merged_with(&self, overrides: LobbyCreationOptions) -> LobbyCreationOptions {
+        LobbyCreationOptions {
+            game_type: overrides
+                .game_type
+                .or_else(|| self.options.game_type.clone()),
+            twitch_channel: overrides
+                .twitch_channel
+                .or_else(|| self.options.twitch_channel.clone()),
+            settings: overrides.settings.or_else(|| self.options.settings.clone()),
+            scheduled_start: overrides.scheduled_start.or(self.options.scheduled_start),
+            obs: overrides.obs.or_else(|| self.options.obs.clone()),
+            webhooks: overrides.webhooks.or_else(|| self.options.webhooks.clone()),
+        }
+    }
+
+    /// The preset as returned by the API. The OBS password and webhook signing
+    /// secrets are only kept for the server's own use, so they have to be sent
+    /// again when a preset is updated.
+    pub fn redacted(&self) -> Self {
+        let mut preset = self.clone();
+        if let Some(obs) = preset.options.obs.as_mut() {
+            obs.password = None;
+        }
+        for target in preset.options.webhooks.iter_mut().flatten() {
+            target.secret = None;
+        }
+        preset
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PresetFile {
+    presets: Vec<LobbyPreset>,
+}
+
+pub fn validate_preset_name(name: &str) -> Result<(), PresetError> {
+    if name.is_empty() || name.len() > MAX_PRESET_NAME_LENGTH {
+        return Err(PresetError::InvalidName(format!(
+            "Preset name must be between 1 and {} characters",
+            MAX_PRESET_NAME_LENGTH
+        )));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(PresetError::InvalidName(
+            "Preset name may only contain ASCII letters, digits, '-' and '_'".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Server-side preset storage. Presets live in memory and, when a file path is
+/// configured, are persisted to a JSON file after every change.
+pub struct PresetStore {
+    presets: RwLock<BTreeMap<String, LobbyPreset>>,
+    file_path: Option<PathBuf>,
+}
+
+impl PresetStore {
+    #[tracing::instrument(skip(config), fields(presets.file_path = ?config.file_path))]
+    pub async fn load(config: &PresetsConfig) -> Result<Self, PresetError> {
+        let file_path = config.file_path.as_ref().map(PathBuf::from);
+        let mut presets = BTreeMap::new();
+
+        if let Some(path) = file_path.as_ref() {
+            match tokio::fs::read_to_string(path).await {
+                Ok(content) => {
+                    let file: PresetFile = serde_json::from_str(&content).map_err(|e| {
+                        PresetError::Parse(format!(
+                            "Failed to parse preset file '{}': {}",
+                            path.display(),
+                            e
+                        ))
+                    })?;
+                    for preset in file.presets {
+                        presets.insert(preset.name.clone(), preset);
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    tracing::info!(
+                        presets.file_path = %path.display(),
+                        "Preset file does not exist yet, starting with no presets"
+                    );
+                }
+                Err(e) => {
+                    return Err(PresetError::FileRead {
+                        path: path.display().to_string(),
+                        source: e,
+                    });
+                }
+            }
+        } else {
+            tracing::info!("No preset file configured, presets are kept in memory only");
+        }
+
+        tracing::info!(presets.count = presets.len(), "PresetStore initialized");
+
+        Ok(Self {
+            presets: RwLock::new(presets),
+            file_path,
+        })
+    }
+
+    pub async fn list(&self, twitch_channel: Option<&str>) -> Vec<LobbyPreset> {
+        self.presets
+            .read()
+            .await
+            .values()
+            .filter(|preset| match twitch_channel {
+                Some(channel) => preset
+                    .options
+                    .twitch_channel
+                    .as_deref()
+                    .is_some_and(|c| c.eq_ignore_ascii_case(channel)),
+                None => true,
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub async fn get(&self, name: &str) -> Option<LobbyPreset> {
+        self.presets.read().await.get(name).cloned()
+    }
+
+    #[tracing::instrument(skip(self, preset), fields(preset.name = %preset.name))]
+    pub async fn upsert(&self, preset: LobbyPreset) -> Result<(), PresetError> {
+        validate_preset_name(&preset.name)?;
+        let mut presets = self.presets.write().await;
+        presets.insert(preset.name.clone(), preset);
+        self.persist(&presets).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn remove(&self, name: &str) -> Result<(), PresetError> {
+        let mut presets = self.presets.write().await;
+        if presets.remove(name).is_none() {
+            return Err(PresetError::NotFound(name.to_string()));
+        }
+        self.persist(&presets).await
+    }
+
+    async fn persist(&self, presets: &BTreeMap<String, LobbyPreset>) -> Result<(), PresetError> {
+        let Some(path) = self.file_path.as_ref() else {
+            return Ok(());
+        };
+
+        let file = PresetFile {
+            presets: presets.values().cloned().collect(),
+        };
+        let content = serde_json::to_string_pretty(&file)
+            .map_err(|e| PresetError::Parse(format!("Failed to serialize presets: {}", e)))?;
+
+        // Write to a sibling temp file first so a crash never leaves a truncated preset file.
+        let tmp_path = path.with_extension("json.tmp");
+        let write_err = |source| PresetError::FileWrite {
+            path: path.display().to_string(),
+            source,
+        };
+        tokio::fs::write(&tmp_path, content)
+            .await
+            .map_err(write_err)?;
+        tokio::fs::rename(&tmp_path, path)
+            .await
+            .map_err(write_err)?;
+
+        tracing::debug!(
+            presets.count = presets.len(),
+            presets.file_path = %path.display(),
+            "Persisted presets"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lobby::ScheduledStart;
+
+    fn preset(name: &str, channel: &str) -> LobbyPreset {
+        LobbyPreset {
+            name: name.to_string(),
+            options: LobbyCreationOptions {
+                game_type: Some("quiz".to_string()),
+                twitch_channel: Some(channel.to_string()),
+                settings: Some(serde_json::json!({ "target_points": 5 })),
+                scheduled_start: None,
+                obs: None,
+                webhooks: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_validate_preset_name() {
+        assert!(validate_preset_name("friday-quiz_2").is_ok());
+        assert!(validate_preset_name("").is_err());
+        assert!(validate_preset_name("has space").is_err());
+        assert!(validate_preset_name(&"a".repeat(MAX_PRESET_NAME_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_merged_with_prefers_explicit_values() {
+        let merged = preset("weekly", "streamer").merged_with(LobbyCreationOptions {
+            game_type: None,
+            twitch_channel: Some("other".to_string()),
+            settings: None,
+            scheduled_start: Some(ScheduledStart::InMinutes(10)),
+            obs: None,
+            webhooks: None,
+        });
+        assert_eq!(merged.game_type.as_deref(), Some("quiz"));
+        assert_eq!(merged.twitch_channel.as_deref(), Some("other"));
+        assert_eq!(
+            merged.settings,
+            Some(serde_json::json!({ "target_points": 5 }))
+        );
+    }
+
+    #[test]
+    fn test_redacted_hides_credentials() {
+        let mut with_obs = preset("weekly", "streamer");
+        with_obs.options.obs = Some(crate::obs::ObsLobbyConfig {
+            url: "ws://127.0.0.1:4455".to_string(),
+            password: Some("obs-password".to_string()),
+            actions: Vec::new(),
+        });
+        with_obs.options.webhooks = Some(vec![crate::webhooks::WebhookTarget {
+            url: "https://example.com/hook".to_string(),
+            secret: Some("hook-secret".to_string()),
+        }]);
+
+        let json = serde_json::to_string(&with_obs.redacted()).unwrap();
+        assert!(!json.contains("obs-password"));
+        assert!(!json.contains("hook-secret"));
+        assert!(json.contains("ws://127.0.0.1:4455"));
+        assert!(json.contains("https://example.com/hook"));
+        assert!(
+            serde_json::to_string(&with_obs)
+                .unwrap()
+                .contains("obs-password")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_presets_round_trip_through_file() {
+        let path =
+            std::env::temp_dir().join(format!("kolmodin-presets-{}.json", uuid::Uuid::new_v4()));
+        let config = PresetsConfig {
+            file_path: Some(path.display().to_string()),
+        };
+
+        let store = PresetStore::load(&config).await.unwrap();
+        store.upsert(preset("weekly", "Streamer")).await.unwrap();
+        store.upsert(preset("other", "someone")).await.unwrap();
+        store.remove("other").await.unwrap();
+        assert!(matches!(
+            store.remove("other").await,
+            Err(PresetError::NotFound(_))
+        ));
+
+        let reloaded = PresetStore::load(&config).await.unwrap();
+        let listed = reloaded.list(Some("streamer")).await;
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "weekly");
+        assert!(reloaded.list(Some("someone")).await.is_empty());
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,99 @@
+use std::path::{Path, PathBuf};
+
+use crate::config::load_settings;
+use crate::state::AppState;
+use crate::web::RouterHandle;
+
+/// Reloads the configuration whenever the process receives SIGHUP.
+#[cfg(unix)]
+pub fn spawn_sighup_listener(
+    config_file: Option<PathBuf>,
+    app_state: AppState,
+    router: RouterHandle,
+) {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    tokio::spawn(async move {
+        let mut hangups = match signal(SignalKind::hangup()) {
+            Ok(hangups) => hangups,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to listen for SIGHUP; configuration reload disabled");
+                return;
+            }
+        };
+        while hangups.recv().await.is_some() {
+            tracing::info!("Received SIGHUP, reloading configuration");
+            reload_settings(config_file.as_deref(), &app_state, &router).await;
+        }
+    });
+}
+
+#[cfg(not(unix))]
+pub fn spawn_sighup_listener(
+    _config_file: Option<PathBuf>,
+    _app_state: AppState,
+    _router: RouterHandle,
+) {
+    tracing::info!("Configuration reload on SIGHUP is only available on Unix");
+}
+
+/// Re-reads the config file and environment and applies the reload-safe fields.
+/// An invalid configuration is rejected as a whole and the current one is kept.
+#[tracing::instrument(skip(app_state, router))]
+pub async fn reload_settings(
+    config_file: Option<&Path>,
+    app_state: &AppState,
+    router: &RouterHandle,
+) {
+    let reloaded = match load_settings(config_file) {
+        Ok(reloaded) => reloaded,
+        Err(e) => {
+            tracing::error!(error = %e, "Configuration reload failed; keeping current settings");
+            return;
+        }
+    };
+
+    let previous = app_state.settings.current();
+    let current = app_state.settings.apply_reload(reloaded);
+
+    if previous.server.cors_origins != current.server.cors_origins
+        || previous.server.rate_limit != current.server.rate_limit
+    {
+        router.rebuild(app_state.clone(), &current.server);
+        tracing::info!(
+            cors.origins.count = current.server.cors_origins.len(),
+            rate_limit.per_ms = current.server.rate_limit.per_millisecond,
+            rate_limit.burst_size = current.server.rate_limit.burst_size,
+            "Applied reloaded CORS and rate limit settings"
+        );
+    }
+
+    if previous.server.websocket != current.server.websocket {
+        tracing::info!(
+            websocket.max_message_bytes = current.server.websocket.max_message_bytes,
+            "Applied reloaded WebSocket settings; open connections keep their old limit"
+        );
+    }
+
+    if previous.games != current.games {
+        tracing::info!(
+            games.enabled_types = ?current.games.enabled_types,
+            "Applied reloaded game settings"
+        );
+    }
+
+    if previous.database != current.database {
+        app_state
+            .game_content_cache
+            .set_source(current.database.clone())
+            .await;
+        match app_state.game_content_cache.refresh_all_content().await {
+            Ok(()) => tracing::info!("Reloaded game content from the new source"),
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to load content from the new source; serving cached content")
+            }
+        }
+    }
+
+    tracing::info!("Configuration reloaded; changes to other settings take effect after a restart");
+}
@@ -3,42 +3,130 @@ use std::sync::Arc;
 use dashmap::DashMap;
 use uuid::Uuid;
 
-use crate::config::{AppSettings, GamesConfig, ServerConfig};
+use crate::config::SharedSettings;
 use crate::content::GameContentCache;
-use crate::lobby::{self, LobbyActorHandle, LobbyDetails};
+use crate::lobby::{
+    self, LobbyActorHandle, LobbyCreationError, LobbyCreationOptions, LobbyDetails,
+};
+use crate::presets::PresetStore;
+use crate::streamers::StreamerStore;
 use crate::twitch::TwitchServiceHandle;
+use crate::web::twitch_login::LoginSessions;
 
 #[derive(Clone)]
 pub struct AppState {
     pub active_lobbies: Arc<DashMap<Uuid, LobbyActorHandle>>,
     pub game_content_cache: Arc<GameContentCache>,
-    pub server_config: Arc<ServerConfig>,
-    pub games_config: GamesConfig,
     pub twitch_service: TwitchServiceHandle,
-    pub app_settings: Arc<AppSettings>,
+    /// Reloadable settings; take a snapshot with `settings.current()`.
+    pub settings: SharedSettings,
+    pub preset_store: Arc<PresetStore>,
+    pub streamer_store: Arc<StreamerStore>,
+    pub login_sessions: Arc<LoginSessions>,
 }
 
 impl AppState {
     pub async fn create_lobby(
         &self,
-        requested_game_type: Option<String>,
-        requested_twitch_channel: Option<String>,
-    ) -> Result<LobbyDetails, String> {
+        options: LobbyCreationOptions,
+        streamer_api_key: Option<&str>,
+    ) -> Result<LobbyDetails, LobbyCreationError> {
         lobby::create_lobby(
             Arc::clone(&self.active_lobbies),
-            self.games_config.clone(),
             Arc::clone(&self.game_content_cache),
             self.twitch_service.clone(),
-            Arc::clone(&self.app_settings),
-            requested_game_type,
-            requested_twitch_channel,
+            &self.streamer_store,
+            self.settings.current(),
+            options,
+            streamer_api_key,
         )
         .await
     }
 
+    pub async fn validate_creation_options(
+        &self,
+        options: &LobbyCreationOptions,
+    ) -> Result<(), LobbyCreationError> {
+        lobby::validate_creation_options(&self.game_content_cache, self.settings.current(), options)
+            .await
+    }
+
     pub fn get_lobby_handle(&self, lobby_id: Uuid) -> Option<LobbyActorHandle> {
         self.active_lobbies
             .get(&lobby_id)
             .map(|entry| entry.value().clone())
     }
 }
+
+#[cfg(test)]
+impl AppState {
+    /// An app state in anonymous Twitch mode with in-memory stores, built from
+    /// `test_settings_and_content`.
+    pub(crate) async fn for_tests() -> Self {
+        let (settings, content) = test_settings_and_content().await;
+        let token_provider = crate::twitch::TokenProvider::new(Arc::new(settings.twitch.clone()))
+            .await
+            .unwrap();
+        let whitelist = content.twitch_whitelist().await;
+        AppState {
+            active_lobbies: Arc::new(DashMap::new()),
+            preset_store: Arc::new(PresetStore::load(&settings.presets).await.unwrap()),
+            streamer_store: Arc::new(
+                StreamerStore::load(&settings.streamers, &whitelist)
+                    .await
+                    .unwrap(),
+            ),
+            game_content_cache: Arc::new(content),
+            twitch_service: TwitchServiceHandle::spawn(token_provider, 0, 0),
+            settings: SharedSettings::new(settings.as_ref().clone()),
+            login_sessions: Arc::new(LoginSessions::new()),
+        }
+    }
+}
+
+/// Settings with every game type enabled and content read from a small data file.
+#[cfg(test)]
+pub(crate) async fn test_settings_and_content()
+-> (Arc<crate::config::AppSettings>, GameContentCache) {
+    let dir = std::env::temp_dir();
+    let data_path = dir.join(format!("kolmodin-content-{}.json", Uuid::new_v4()));
+    std::fs::write(
+        &data_path,
+        r#"{
+            "medandraord_words": ["katt"],
+            "trivial_pursuit": {"cards": []},
+            "vem_vet_mest": [{"question": "Huvudstad i Norge?", "answer": "Oslo"}]
+        }"#,
+    )
+    .unwrap();
+    let config_path = dir.join(format!("kolmodin-config-{}.toml", Uuid::new_v4()));
+    std::fs::write(
+        &config_path,
+        format!(
+            r#"
+[server]
+admin_api_key = "secret"
+
+[twitch]
+anonymous = true
+
+[database]
+source_type = "file"
+file_path = "{}"
+
+[games]
+enabled_types = ["quiz", "medandraord", "dealnodeal", "clipqueue"]
+"#,
+            data_path.display()
+        ),
+    )
+    .unwrap();
+
+    let settings = crate::config::load_settings(Some(&config_path)).unwrap();
+    let content = GameContentCache::new(settings.database.clone())
+        .await
+        .unwrap();
+    std::fs::remove_file(&config_path).unwrap();
+    std::fs::remove_file(&data_path).unwrap();
+    (Arc::new(settings), content)
+}
@@ -0,0 +1,606 @@
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+
+use crate::config::StreamersConfig;
+use crate::error::StreamerError;
+use crate::game_logic::GameType;
+use crate::game_logic::quiz::QuizQuestionSource;
+use crate::lobby::LobbyCreationError;
+use crate::web::admin_auth::digests_equal;
+
+const MAX_CHANNEL_NAME_LENGTH: usize = 25;
+
+/// A Twitch channel allowed to host lobbies on this server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StreamerAccount {
+    /// Lowercase Twitch login.
+    #[serde(default)]
+    pub channel: String,
+    pub display_name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Game types (primary ids) this streamer may host. Empty allows every game
+    /// enabled on the server.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub enabled_games: BTreeSet<String>,
+    /// Quiz question sources (`TrivialPursuit`, `VemVetMest`) this streamer's
+    /// lobbies may draw from. Empty allows every source.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_packs: Vec<String>,
+    #[serde(default)]
+    pub quotas: StreamerQuotas,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct StreamerQuotas {
+    /// Lobbies this channel may have running at once. `None` means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_active_lobbies: Option<usize>,
+}
+
+/// An account as stored. The API key itself is only shown once, when issued.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredStreamer {
+    #[serde(flatten)]
+    account: StreamerAccount,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    api_key_sha256: Option<String>,
+}
+
+/// An account as returned by the admin API.
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamerSummary {
+    #[serde(flatten)]
+    pub account: StreamerAccount,
+    /// When set, lobbies for this channel can only be created with the streamer's
+    /// API key.
+    pub has_api_key: bool,
+}
+
+impl From<&StoredStreamer> for StreamerSummary {
+    fn from(stored: &StoredStreamer) -> Self {
+        Self {
+            account: stored.account.clone(),
+            has_api_key: stored.api_key_sha256.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StreamerFile {
+    streamers: Vec<StoredStreamer>,
+}
+
+/// Lowercases a Twitch login and checks it only uses characters Twitch allows.
+pub fn normalize_channel(channel: &str) -> Result<String, StreamerError> {
+    let channel = channel.trim().to_lowercase();
+    if channel.is_empty()
+        || channel.len() > MAX_CHANNEL_NAME_LENGTH
+        || !channel
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(StreamerError::InvalidChannel(format!(
+            "'{}' is not a Twitch login (1-{} letters, digits or '_')",
+            channel, MAX_CHANNEL_NAME_LENGTH
+        )));
+    }
+    Ok(channel)
+}
+
+fn hash_api_key(api_key: &str) -> String {
+    Sha256::digest(api_key.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Server-side streamer accounts, replacing the `twitch_whitelist` of the content
+/// file. Accounts live in memory and, when a file path is configured, are
+/// persisted to a JSON file after every change.
+pub struct StreamerStore {
+    streamers: RwLock<BTreeMap<String, StoredStreamer>>,
+    file_path: Option<PathBuf>,
+    allow_unregistered_channels: bool,
+    /// Lobbies per channel that passed the quota check but are not active yet.
+    pending_lobbies: DashMap<String, usize>,
+}
+
+/// Counts a lobby being created against its channel's quota. Drop it only once
+/// the lobby is active, so concurrent requests cannot both take the last slot.
+pub struct LobbyReservation<'a> {
+    pending_lobbies: &'a DashMap<String, usize>,
+    /// `None` for unregistered channels, which have no quota.
+    channel: Option<String>,
+}
+
+impl Drop for LobbyReservation<'_> {
+    fn drop(&mut self) {
+        let Some(channel) = self.channel.take() else {
+            return;
+        };
+        if let Entry::Occupied(mut pending) = self.pending_lobbies.entry(channel) {
+            *pending.get_mut() -= 1;
+            if *pending.get() == 0 {
+                pending.remove();
+            }
+        }
+    }
+}
+
+impl StreamerStore {
+    /// Loads the stored accounts. While there are none, channels from the legacy
+    /// content-file whitelist are imported as enabled accounts.
+    #[tracing::instrument(skip(config, legacy_whitelist), fields(streamers.file_path = ?config.file_path))]
+    pub async fn load(
+        config: &StreamersConfig,
+        legacy_whitelist: &[String],
+    ) -> Result<Self, StreamerError> {
+        let file_path = config.file_path.as_ref().map(PathBuf::from);
+        let mut streamers = BTreeMap::new();
+
+        if let Some(path) = file_path.as_ref() {
+            match tokio::fs::read_to_string(path).await {
+                Ok(content) => {
+                    let file: StreamerFile = serde_json::from_str(&content).map_err(|e| {
+                        StreamerError::Parse(format!(
+                            "Failed to parse streamer file '{}': {}",
+                            path.display(),
+                            e
+                        ))
+                    })?;
+                    for stored in file.streamers {
+                        streamers.insert(stored.account.channel.clone(), stored);
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    tracing::info!(
+                        streamers.file_path = %path.display(),
+                        "Streamer file does not exist yet, starting with no accounts"
+                    );
+                }
+                Err(e) => {
+                    return Err(StreamerError::FileRead {
+                        path: path.display().to_string(),
+                        source: e,
+                    });
+                }
+            }
+        } else {
+            tracing::info!("No streamer file configured, accounts are kept in memory only");
+        }
+
+        let store = Self {
+            streamers: RwLock::new(streamers),
+            file_path,
+            allow_unregistered_channels: config.allow_unregistered_channels,
+            pending_lobbies: DashMap::new(),
+        };
+
+        if store.streamers.read().await.is_empty() && !legacy_whitelist.is_empty() {
+            let mut streamers = store.streamers.write().await;
+            for channel in legacy_whitelist {
+                let Ok(channel) = normalize_channel(channel) else {
+                    tracing::warn!(twitch.channel = %channel, "Skipping invalid whitelisted channel");
+                    continue;
+                };
+                streamers.insert(
+                    channel.clone(),
+                    StoredStreamer {
+                        account: StreamerAccount {
+                            display_name: channel.clone(),
+                            channel,
+                            enabled: true,
+                            enabled_games: BTreeSet::new(),
+                            content_packs: Vec::new(),
+                            quotas: StreamerQuotas::default(),
+                        },
+                        api_key_sha256: None,
+                    },
+                );
+            }
+            tracing::info!(
+                streamers.count = streamers.len(),
+                "Imported streamer accounts from the legacy twitch_whitelist"
+            );
+            store.persist(&streamers).await?;
+        }
+
+        tracing::info!(
+            streamers.count = store.streamers.read().await.len(),
+            streamers.allow_unregistered = store.allow_unregistered_channels,
+            "StreamerStore initialized"
+        );
+
+        Ok(store)
+    }
+
+    pub async fn list(&self) -> Vec<StreamerSummary> {
+        self.streamers
+            .read()
+            .await
+            .values()
+            .map(StreamerSummary::from)
+            .collect()
+    }
+
+    pub async fn enabled_channels(&self) -> Vec<String> {
+        self.streamers
+            .read()
+            .await
+            .values()
+            .filter(|stored| stored.account.enabled)
+            .map(|stored| stored.account.channel.clone())
+            .collect()
+    }
+
+    /// Adds or replaces an account. An API key issued earlier is kept.
+    #[tracing::instrument(skip(self, account), fields(twitch.channel = %account.channel))]
+    pub async fn upsert(
+        &self,
+        mut account: StreamerAccount,
+    ) -> Result<StreamerSummary, StreamerError> {
+        account.channel = normalize_channel(&account.channel)?;
+        if let Some(unknown) = account.content_packs.iter().find(|pack| {
+            !QuizQuestionSource::ALL
+                .iter()
+                .any(|source| source.content_pack() == pack.as_str())
+        }) {
+            return Err(StreamerError::UnknownContentPack(unknown.clone()));
+        }
+        let mut streamers = self.streamers.write().await;
+        let api_key_sha256 = streamers
+            .get(&account.channel)
+            .and_then(|existing| existing.api_key_sha256.clone());
+        let stored = StoredStreamer {
+            account,
+            api_key_sha256,
+        };
+        let summary = StreamerSummary::from(&stored);
+        streamers.insert(stored.account.channel.clone(), stored);
+        self.persist(&streamers).await?;
+        Ok(summary)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn remove(&self, channel: &str) -> Result<(), StreamerError> {
+        let channel = normalize_channel(channel)?;
+        let mut streamers = self.streamers.write().await;
+        if streamers.remove(&channel).is_none() {
+            return Err(StreamerError::NotFound(channel));
+        }
+        self.persist(&streamers).await
+    }
+
+    /// Generates a new API key for `channel`, replacing any previous one. Only its
+    /// hash is stored, so the returned key cannot be retrieved again.
+    #[tracing::instrument(skip(self))]
+    pub async fn issue_api_key(&self, channel: &str) -> Result<String, StreamerError> {
+        let channel = normalize_channel(channel)?;
+        let mut streamers = self.streamers.write().await;
+        let stored = streamers
+            .get_mut(&channel)
+            .ok_or_else(|| StreamerError::NotFound(channel.clone()))?;
+
+        let mut key_bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key_bytes);
+        let api_key: String = key_bytes
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        stored.api_key_sha256 = Some(hash_api_key(&api_key));
+
+        self.persist(&streamers).await?;
+        Ok(api_key)
+    }
+
+    /// Decides whether `channel` may host a new `game_type` lobby, given the API
+    /// key sent with the request, if any. `count_active_lobbies` returns how many
+    /// lobbies the channel already runs; it is called while lobbies still being
+    /// created are counted, and the returned reservation adds this one.
+    #[tracing::instrument(skip(self, api_key, count_active_lobbies), fields(twitch.channel = %channel, game.type = game_type.primary_id()))]
+    pub async fn check_lobby_creation(
+        &self,
+        channel: &str,
+        game_type: &GameType,
+        api_key: Option<&str>,
+        count_active_lobbies: impl FnOnce() -> usize,
+    ) -> Result<LobbyReservation<'_>, LobbyCreationError> {
+        let normalized_channel = channel.trim().to_lowercase();
+        let streamers = self.streamers.read().await;
+        let Some(stored) = streamers.get(&normalized_channel) else {
+            if self.allow_unregistered_channels {
+                return Ok(LobbyReservation {
+                    pending_lobbies: &self.pending_lobbies,
+                    channel: None,
+                });
+            }
+            return Err(LobbyCreationError::ChannelNotAllowed {
+                channel: channel.to_string(),
+            });
+        };
+        let account = &stored.account;
+
+        if !account.enabled {
+            return Err(LobbyCreationError::ChannelNotAllowed {
+                channel: channel.to_string(),
+            });
+        }
+        if let Some(expected_hash) = stored.api_key_sha256.as_deref() {
+            let provided_hash = api_key.map(hash_api_key).unwrap_or_default();
+            if !digests_equal(provided_hash.as_bytes(), expected_hash.as_bytes()) {
+                return Err(LobbyCreationError::InvalidStreamerApiKey {
+                    channel: channel.to_string(),
+                });
+            }
+        }
+        if !account.enabled_games.is_empty()
+            && !account.enabled_games.contains(game_type.primary_id())
+        {
+            return Err(LobbyCreationError::GameNotAllowedForStreamer {
+                channel: channel.to_string(),
+                game_type: game_type.primary_id().to_string(),
+            });
+        }
+
+        // The entry stays locked until this lobby is counted as pending.
+        let mut pending = self
+            .pending_lobbies
+            .entry(normalized_channel.clone())
+            .or_insert(0);
+        if let Some(limit) = account.quotas.max_active_lobbies
+            && count_active_lobbies() + *pending >= limit
+        {
+            return Err(LobbyCreationError::StreamerQuotaExceeded {
+                channel: channel.to_string(),
+                limit,
+            });
+        }
+        *pending += 1;
+        Ok(LobbyReservation {
+            pending_lobbies: &self.pending_lobbies,
+            channel: Some(normalized_channel),
+        })
+    }
+
+    /// Content packs of `channel`'s account; empty when it has none or no account.
+    pub async fn content_packs(&self, channel: &str) -> Vec<String> {
+        self.streamers
+            .read()
+            .await
+            .get(&channel.trim().to_lowercase())
+            .map(|stored| stored.account.content_packs.clone())
+            .unwrap_or_default()
+    }
+
+    /// Whether `api_key` is the key issued to `channel`. Accounts without a key
+    /// never match.
+    pub async fn verify_api_key(&self, channel: &str, api_key: &str) -> bool {
+        let streamers = self.streamers.read().await;
+        streamers
+            .get(&channel.trim().to_lowercase())
+            .and_then(|stored| stored.api_key_sha256.as_deref())
+            .is_some_and(|expected_hash| {
+                digests_equal(hash_api_key(api_key).as_bytes(), expected_hash.as_bytes())
+            })
+    }
+
+    async fn persist(
+        &self,
+        streamers: &BTreeMap<String, StoredStreamer>,
+    ) -> Result<(), StreamerError> {
+        let Some(path) = self.file_path.as_ref() else {
+            return Ok(());
+        };
+
+        let file = StreamerFile {
+            streamers: streamers.values().cloned().collect(),
+        };
+        let content = serde_json::to_string_pretty(&file)
+            .map_err(|e| StreamerError::Parse(format!("Failed to serialize streamers: {}", e)))?;
+
+        // Write to a sibling temp file first so a crash never leaves a truncated file.
+        let tmp_path = path.with_extension("json.tmp");
+        let write_err = |source| StreamerError::FileWrite {
+            path: path.display().to_string(),
+            source,
+        };
+        tokio::fs::write(&tmp_path, content)
+            .await
+            .map_err(write_err)?;
+        tokio::fs::rename(&tmp_path, path)
+            .await
+            .map_err(write_err)?;
+
+        tracing::debug!(
+            streamers.count = streamers.len(),
+            streamers.file_path = %path.display(),
+            "Persisted streamer accounts"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(channel: &str) -> StreamerAccount {
+        StreamerAccount {
+            channel: channel.to_string(),
+            display_name: channel.to_string(),
+            enabled: true,
+            enabled_games: BTreeSet::new(),
+            content_packs: Vec::new(),
+            quotas: StreamerQuotas::default(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_channel() {
+        assert_eq!(normalize_channel(" Kolmodin_1 ").unwrap(), "kolmodin_1");
+        assert!(normalize_channel("").is_err());
+        assert!(normalize_channel("not a channel").is_err());
+        assert!(normalize_channel(&"a".repeat(MAX_CHANNEL_NAME_LENGTH + 1)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lobby_creation_checks() {
+        let store = StreamerStore::load(&StreamersConfig::default(), &[])
+            .await
+            .unwrap();
+        let quiz_only = StreamerAccount {
+            enabled_games: BTreeSet::from([GameType::Quiz.primary_id().to_string()]),
+            quotas: StreamerQuotas {
+                max_active_lobbies: Some(1),
+            },
+            ..account("Streamer")
+        };
+        store.upsert(quiz_only).await.unwrap();
+        store
+            .upsert(StreamerAccount {
+                enabled: false,
+                ..account("disabled")
+            })
+            .await
+            .unwrap();
+
+        assert!(
+            store
+                .check_lobby_creation("streamer", &GameType::Quiz, None, || 0)
+                .await
+                .is_ok()
+        );
+        assert!(matches!(
+            store
+                .check_lobby_creation("streamer", &GameType::MedAndraOrd, None, || 0)
+                .await,
+            Err(LobbyCreationError::GameNotAllowedForStreamer { .. })
+        ));
+        assert!(matches!(
+            store
+                .check_lobby_creation("streamer", &GameType::Quiz, None, || 1)
+                .await,
+            Err(LobbyCreationError::StreamerQuotaExceeded { limit: 1, .. })
+        ));
+        assert!(matches!(
+            store
+                .check_lobby_creation("disabled", &GameType::Quiz, None, || 0)
+                .await,
+            Err(LobbyCreationError::ChannelNotAllowed { .. })
+        ));
+        assert!(matches!(
+            store
+                .check_lobby_creation("unknown", &GameType::Quiz, None, || 0)
+                .await,
+            Err(LobbyCreationError::ChannelNotAllowed { .. })
+        ));
+
+        let api_key = store.issue_api_key("streamer").await.unwrap();
+        assert!(matches!(
+            store
+                .check_lobby_creation("streamer", &GameType::Quiz, None, || 0)
+                .await,
+            Err(LobbyCreationError::InvalidStreamerApiKey { .. })
+        ));
+        assert!(
+            store
+                .check_lobby_creation("streamer", &GameType::Quiz, Some(&api_key), || 0)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upsert_rejects_unknown_content_packs() {
+        let store = StreamerStore::load(&StreamersConfig::default(), &[])
+            .await
+            .unwrap();
+        let with_packs = |packs: &[&str]| StreamerAccount {
+            content_packs: packs.iter().map(|pack| pack.to_string()).collect(),
+            ..account("streamer")
+        };
+
+        assert!(matches!(
+            store.upsert(with_packs(&["VemVetMest", "Jeopardy"])).await,
+            Err(StreamerError::UnknownContentPack(pack)) if pack == "Jeopardy"
+        ));
+        store.upsert(with_packs(&["VemVetMest"])).await.unwrap();
+        assert_eq!(store.content_packs("Streamer").await, vec!["VemVetMest"]);
+    }
+
+    #[tokio::test]
+    async fn test_pending_lobbies_count_against_quota() {
+        let store = StreamerStore::load(&StreamersConfig::default(), &[])
+            .await
+            .unwrap();
+        store
+            .upsert(StreamerAccount {
+                quotas: StreamerQuotas {
+                    max_active_lobbies: Some(1),
+                },
+                ..account("streamer")
+            })
+            .await
+            .unwrap();
+
+        let reservation = store
+            .check_lobby_creation("streamer", &GameType::Quiz, None, || 0)
+            .await
+            .unwrap();
+        assert!(matches!(
+            store
+                .check_lobby_creation("Streamer", &GameType::Quiz, None, || 0)
+                .await,
+            Err(LobbyCreationError::StreamerQuotaExceeded { limit: 1, .. })
+        ));
+
+        drop(reservation);
+        assert!(
+            store
+                .check_lobby_creation("streamer", &GameType::Quiz, None, || 0)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_imports_legacy_whitelist_and_persists() {
+        let path =
+            std::env::temp_dir().join(format!("kolmodin-streamers-{}.json", uuid::Uuid::new_v4()));
+        let config = StreamersConfig {
+            file_path: Some(path.display().to_string()),
+            allow_unregistered_channels: false,
+        };
+
+        let store = StreamerStore::load(&config, &["legacy".to_string()])
+            .await
+            .unwrap();
+        store.issue_api_key("legacy").await.unwrap();
+
+        // Once accounts exist, the legacy whitelist is no longer consulted.
+        let reloaded = StreamerStore::load(&config, &["other".to_string()])
+            .await
+            .unwrap();
+        let listed = reloaded.list().await;
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].account.channel, "legacy");
+        assert!(listed[0].has_api_key);
+        assert!(matches!(
+            reloaded.remove("other").await,
+            Err(StreamerError::NotFound(_))
+        ));
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use super::error::TwitchError;
+use super::service::TwitchServiceHandle;
+use crate::game_logic::{GameLifecycleEvent, GameResult};
+
+/// Posts a lobby's lifecycle events to its Twitch chat using the configured
+/// templates. Sending happens on a separate task, so a slow or failing send never
+/// blocks the lobby.
+#[derive(Clone, Debug)]
+pub struct ChatAnnouncer {
+    channel: String,
+    game_type_id: String,
+    templates: Arc<BTreeMap<String, String>>,
+    twitch_service: TwitchServiceHandle,
+}
+
+impl ChatAnnouncer {
+    pub fn new(
+        channel: String,
+        game_type_id: String,
+        templates: BTreeMap<String, String>,
+        twitch_service: TwitchServiceHandle,
+    ) -> Self {
+        Self {
+            channel,
+            game_type_id,
+            templates: Arc::new(templates),
+            twitch_service,
+        }
+    }
+
+    pub fn announce(&self, event: &GameLifecycleEvent) {
+        let Some(template) = self.templates.get(event.name()) else {
+            return;
+        };
+        let message = render_announcement(template, event, &self.game_type_id);
+        if message.trim().is_empty() {
+            return;
+        }
+
+        let twitch_service = self.twitch_service.clone();
+        let channel = self.channel.clone();
+        let event_name = event.name();
+        tokio::spawn(async move {
+            match twitch_service
+                .send_chat_message(channel.clone(), message)
+                .await
+            {
+                Ok(()) => {}
+                Err(e @ TwitchError::ChatRateLimited { .. }) => {
+                    tracing::debug!(
+                        twitch.channel = %channel,
+                        event = event_name,
+                        error = %e,
+                        "Chat announcement dropped"
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        twitch.channel = %channel,
+                        event = event_name,
+                        error = %e,
+                        "Chat announcement failed"
+                    );
+                }
+            }
+        });
+    }
+}
+
+/// Fills `{placeholder}`s in `template` from the event. `{game}` is available for
+/// every event; unknown placeholders are left as they are.
+pub fn render_announcement(
+    template: &str,
+    event: &GameLifecycleEvent,
+    game_type_id: &str,
+) -> String {
+    let mut values = vec![("game", game_type_id.to_string())];
+    match event {
+        GameLifecycleEvent::GameStarted => {}
+        GameLifecycleEvent::PlayerScored { player, points } => {
+            values.push(("player", player.clone()));
+            values.push(("points", points.to_string()));
+        }
+        GameLifecycleEvent::GameOver(GameResult::Scores { winner, .. }) => {
+            values.push(("winner", winner.clone()));
+            values.push(("result", format!("{} wins!", winner)));
+        }
+        GameLifecycleEvent::GameOver(GameResult::Winnings { winnings, summary }) => {
+            values.push(("winnings", winnings.to_string()));
+            values.push(("summary", summary.clone()));
+            values.push(("result", summary.clone()));
+        }
+        GameLifecycleEvent::ClipAdded { clip } => {
+            values.push(("title", clip.title.clone()));
+            values.push(("player", clip.submitted_by_username.clone()));
+        }
+        GameLifecycleEvent::Announcement {
+            values: announced, ..
+        } => {
+            values.extend(announced.iter().cloned());
+        }
+    }
+
+    values
+        .iter()
+        .fold(template.to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TwitchChatConfig;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_fills_event_placeholders() {
+        let scored = GameLifecycleEvent::PlayerScored {
+            player: "user123".to_string(),
+            points: 7,
+        };
+        assert_eq!(
+            render_announcement("Correct! {player} has {points} points", &scored, "quiz"),
+            "Correct! user123 has 7 points"
+        );
+
+        let game_over = GameLifecycleEvent::GameOver(GameResult::Scores {
+            winner: "alice".to_string(),
+            player_scores: HashMap::new(),
+        });
+        assert_eq!(
+            render_announcement("{game} over! {result} {unknown}", &game_over, "medandraord"),
+            "medandraord over! alice wins! {unknown}"
+        );
+
+        let offer = GameLifecycleEvent::Announcement {
+            name: "banker_offer",
+            values: vec![("offer", "25000".to_string())],
+        };
+        assert_eq!(
+            render_announcement("{game}: the banker offers {offer}", &offer, "DealNoDeal"),
+            "DealNoDeal: the banker offers 25000"
+        );
+    }
+
+    #[test]
+    fn test_game_templates_layer_over_shared_ones() {
+        let mut chat = TwitchChatConfig::default();
+        chat.games.insert(
+            "dealnodeal".to_string(),
+            BTreeMap::from([
+                ("banker_offer".to_string(), "Offer: {offer}".to_string()),
+                ("player_scored".to_string(), String::new()),
+            ]),
+        );
+
+        let templates = chat.templates_for("dealnodeal");
+        assert_eq!(templates["banker_offer"], "Offer: {offer}");
+        assert!(!templates.contains_key("player_scored"));
+        assert!(templates.contains_key("game_started"));
+
+        let quiz_templates = chat.templates_for("quiz");
+        assert!(quiz_templates.contains_key("player_scored"));
+        assert!(!quiz_templates.contains_key("banker_offer"));
+    }
+}
@@ -37,14 +37,13 @@ impl std::fmt::Debug for AppAccessToken {
 }
 
 pub async fn fetch_twitch_app_access_token(
-    client_id: &str,
-    client_secret: &str,
+    twitch_config: &TwitchConfig,
 ) -> Result<AppAccessToken, TwitchError> {
     tracing::info!("Fetching App Access Token");
-    let url = "https://id.twitch.tv/oauth2/token";
+    let url = format!("{}/oauth2/token", twitch_config.auth_base_url);
     let params = [
-        ("client_id", client_id),
-        ("client_secret", client_secret),
+        ("client_id", twitch_config.client_id.as_str()),
+        ("client_secret", twitch_config.client_secret.as_str()),
         ("grant_type", "client_credentials"),
     ];
     let client = reqwest::Client::new();
@@ -100,53 +99,169 @@ pub async fn fetch_twitch_app_access_token(
     }
 }
 
+#[derive(Deserialize)]
+struct RefreshTokenResponse {
+    access_token: String,
+    refresh_token: String,
+    expires_in: u64,
+}
+
+/// User access token of the chat bot, refreshed from its refresh token.
+struct BotToken {
+    access_token: String,
+    refresh_token: String,
+    expires_at: Instant,
+}
+
+/// Exchanges the bot's refresh token for a new access token. Twitch may rotate
+/// the refresh token, so the returned one replaces the old one.
+async fn refresh_bot_token(
+    twitch_config: &TwitchConfig,
+    refresh_token: &str,
+) -> Result<BotToken, TwitchError> {
+    tracing::info!(twitch.bot = %twitch_config.chat.bot_login, "Refreshing chat bot access token");
+    let url = format!("{}/oauth2/token", twitch_config.auth_base_url);
+    let params = [
+        ("client_id", twitch_config.client_id.as_str()),
+        ("client_secret", twitch_config.client_secret.as_str()),
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token),
+    ];
+    let response = reqwest::Client::new()
+        .post(url)
+        .form(&params)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_body = response.text().await.unwrap_or_default();
+        tracing::error!(
+            http.status = %status,
+            error.body = %error_body,
+            "Failed to refresh chat bot access token"
+        );
+        return Err(TwitchError::TwitchAuth(format!(
+            "Bot token refresh failed (HTTP {}): {}",
+            status, error_body
+        )));
+    }
+    let token_data = response.json::<RefreshTokenResponse>().await?;
+    Ok(BotToken {
+        access_token: token_data.access_token,
+        refresh_token: token_data.refresh_token,
+        expires_at: Instant::now() + Duration::from_secs(token_data.expires_in),
+    })
+}
+
+/// Nick and password the IRC connection logs in with.
+pub struct IrcLogin {
+    pub nickname: String,
+    pub password: Option<String>,
+}
+
 const TOKEN_REFRESH_GRACE_PERIOD: Duration = Duration::from_secs(60 * 60); // Refresh 1 hour before expiry
+// Bot tokens only live a few hours, so they are refreshed closer to expiry.
+const BOT_TOKEN_REFRESH_GRACE_PERIOD: Duration = Duration::from_secs(5 * 60);
 
 #[derive(Clone)]
 pub struct TokenProvider {
-    current_token: Arc<RwLock<AppAccessToken>>,
+    /// `None` in anonymous mode, where no app token is fetched.
+    current_token: Option<Arc<RwLock<AppAccessToken>>>,
     twitch_config: Arc<TwitchConfig>,
     // Used to signal the refresh task to check/refresh immediately
     // Useful if an external event (like a 401) indicates the token is bad.
     force_refresh_trigger: Arc<tokio::sync::Notify>,
+    /// Set when chat sending is enabled; the IRC connection then logs in as the bot.
+    bot_token: Option<Arc<RwLock<BotToken>>>,
+    bot_refresh_trigger: Arc<tokio::sync::Notify>,
 }
 
 impl TokenProvider {
     pub async fn new(twitch_config: Arc<TwitchConfig>) -> Result<Self, TwitchError> {
-        let initial_token =
-            fetch_twitch_app_access_token(&twitch_config.client_id, &twitch_config.client_secret)
-                .await?;
+        if twitch_config.anonymous {
+            tracing::warn!(
+                "Twitch anonymous mode: reading chat without app credentials. Twitch login and chat sending are unavailable"
+            );
+            return Ok(Self {
+                current_token: None,
+                twitch_config,
+                force_refresh_trigger: Arc::new(tokio::sync::Notify::new()),
+                bot_token: None,
+                bot_refresh_trigger: Arc::new(tokio::sync::Notify::new()),
+            });
+        }
+
+        let initial_token = fetch_twitch_app_access_token(&twitch_config).await?;
+        let bot_token = if twitch_config.chat.enabled {
+            let token =
+                refresh_bot_token(&twitch_config, &twitch_config.chat.refresh_token).await?;
+            Some(Arc::new(RwLock::new(token)))
+        } else {
+            None
+        };
 
         let provider = Self {
-            current_token: Arc::new(RwLock::new(initial_token)),
+            current_token: Some(Arc::new(RwLock::new(initial_token))),
             twitch_config,
             force_refresh_trigger: Arc::new(tokio::sync::Notify::new()),
+            bot_token,
+            bot_refresh_trigger: Arc::new(tokio::sync::Notify::new()),
         };
 
         provider.spawn_refresh_task();
+        provider.spawn_bot_refresh_task();
         Ok(provider)
     }
 
-    pub async fn get_token(&self) -> String {
-        self.current_token.read().await.token.clone()
+    /// Whether a bot account is configured to send chat messages.
+    pub fn can_send_chat(&self) -> bool {
+        self.bot_token.is_some()
+    }
+
+    /// Messages allowed per channel every 30 seconds when chat sending is enabled.
+    pub fn chat_messages_per_30_seconds(&self) -> u32 {
+        self.twitch_config.chat.messages_per_30_seconds
+    }
+
+    /// Credentials for a new IRC connection: the bot account when chat sending is
+    /// enabled, otherwise an anonymous `justinfan` user.
+    pub async fn irc_login(&self) -> IrcLogin {
+        if let Some(bot_token) = &self.bot_token {
+            return IrcLogin {
+                nickname: self.twitch_config.chat.bot_login.to_lowercase(),
+                password: Some(bot_token.read().await.access_token.clone()),
+            };
+        }
+        IrcLogin {
+            nickname: format!("justinfan{}", rand::random::<u32>() % 80000 + 1000),
+            password: self.get_token().await,
+        }
+    }
+
+    /// The current app access token, or `None` in anonymous mode.
+    pub async fn get_token(&self) -> Option<String> {
+        match &self.current_token {
+            Some(current_token) => Some(current_token.read().await.token.clone()),
+            None => None,
+        }
     }
 
     /// Signals the background refresh task to attempt a token fetch immediately.
     pub fn signal_immediate_refresh(&self) {
         self.force_refresh_trigger.notify_one();
+        self.bot_refresh_trigger.notify_one();
     }
 
     // Internal method to fetch a new token
-    async fn fetch_new_token_and_update(&self) -> Result<(), TwitchError> {
+    async fn fetch_new_token_and_update(
+        &self,
+        current_token: &RwLock<AppAccessToken>,
+    ) -> Result<(), TwitchError> {
         tracing::info!("Attempting to fetch new app access token");
-        match fetch_twitch_app_access_token(
-            &self.twitch_config.client_id,
-            &self.twitch_config.client_secret,
-        )
-        .await
-        {
+        match fetch_twitch_app_access_token(&self.twitch_config).await {
             Ok(new_token_info) => {
-                let mut token_wlock = self.current_token.write().await;
+                let mut token_wlock = current_token.write().await;
                 *token_wlock = new_token_info;
                 tracing::info!("App access token fetched/updated successfully");
                 Ok(())
@@ -162,10 +277,13 @@ impl TokenProvider {
     }
 
     fn spawn_refresh_task(&self) {
+        let Some(current_token) = self.current_token.clone() else {
+            return;
+        };
         let self_clone = self.clone();
         tokio::spawn(async move {
             loop {
-                let expires_at = self_clone.current_token.read().await.expires_at;
+                let expires_at = current_token.read().await.expires_at;
                 let now = Instant::now();
                 let time_to_expiry = expires_at.saturating_duration_since(now);
 
@@ -197,7 +315,7 @@ impl TokenProvider {
                 let mut fetch_attempts = 0;
                 loop {
                     fetch_attempts += 1;
-                    match self_clone.fetch_new_token_and_update().await {
+                    match self_clone.fetch_new_token_and_update(&current_token).await {
                         Ok(_) => break, // Success, continue the outer loop to wait for next expiry
                         Err(_) => {
                             if fetch_attempts >= 3 {
@@ -223,6 +341,40 @@ impl TokenProvider {
         });
     }
 
+    fn spawn_bot_refresh_task(&self) {
+        let Some(bot_token) = self.bot_token.clone() else {
+            return;
+        };
+        let twitch_config = Arc::clone(&self.twitch_config);
+        let refresh_trigger = Arc::clone(&self.bot_refresh_trigger);
+        tokio::spawn(async move {
+            loop {
+                let expires_at = bot_token.read().await.expires_at;
+                let sleep_duration = expires_at
+                    .saturating_duration_since(Instant::now())
+                    .saturating_sub(BOT_TOKEN_REFRESH_GRACE_PERIOD);
+                tokio::select! {
+                    _ = sleep(sleep_duration) => {}
+                    _ = refresh_trigger.notified() => {
+                        tracing::info!("Immediate bot token refresh signaled");
+                    }
+                }
+
+                let refresh_token = bot_token.read().await.refresh_token.clone();
+                match refresh_bot_token(&twitch_config, &refresh_token).await {
+                    Ok(new_token) => {
+                        *bot_token.write().await = new_token;
+                        tracing::info!("Chat bot access token refreshed");
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Chat bot token refresh failed. Retrying in 30s");
+                        sleep(Duration::from_secs(30)).await;
+                    }
+                }
+            }
+        });
+    }
+
     pub fn get_irc_server_url(&self) -> &str {
         &self.twitch_config.irc_server_url
     }
@@ -12,4 +12,8 @@ pub enum TwitchError {
     ActorComm(String),
     #[error("Twitch IRC connection error: {0}")]
     TwitchConnection(String),
+    #[error("Sending chat messages is unavailable: {0}")]
+    ChatUnavailable(String),
+    #[error("Chat message rate limit reached for channel '{channel}'")]
+    ChatRateLimited { channel: String },
 }
@@ -1,4 +1,4 @@
-use super::types::ParsedTwitchMessage;
+use super::types::{ChatModeration, ParsedTwitchMessage, ReplyParent, UserNotice, UserNoticeKind};
 use chrono::Utc;
 use std::collections::HashMap;
 use thiserror::Error;
@@ -13,6 +13,9 @@ pub const CMD_NICK: &str = "NICK";
 pub const CMD_NOTICE: &str = "NOTICE";
 pub const CMD_RECONNECT: &str = "RECONNECT";
 pub const CMD_CAP: &str = "CAP";
+pub const CMD_CLEARCHAT: &str = "CLEARCHAT";
+pub const CMD_CLEARMSG: &str = "CLEARMSG";
+pub const CMD_USERNOTICE: &str = "USERNOTICE";
 
 // IRC Replies
 pub const RPL_WELCOME: &str = "001";
@@ -159,6 +162,43 @@ impl<'a> IrcMessage<'a> {
         })
     }
 
+    /// Whether the `badges` tag lists `badge_name`, whatever its version.
+    pub fn has_badge(&self, badge_name: &str) -> bool {
+        self.get_tag_value("badges").is_some_and(|badges| {
+            badges
+                .split(',')
+                .any(|badge| badge.split('/').next() == Some(badge_name))
+        })
+    }
+
+    fn tag_flag(&self, key: &str) -> bool {
+        self.get_tag_value(key) == Some("1")
+    }
+
+    fn get_reply_parent(&self) -> Option<ReplyParent> {
+        let message_id = self.get_tag_value("reply-parent-msg-id")?.to_string();
+        let login = self
+            .get_tag_value("reply-parent-user-login")
+            .unwrap_or_default()
+            .to_string();
+        Some(ReplyParent {
+            message_id,
+            user_id: self
+                .get_tag_value("reply-parent-user-id")
+                .map(str::to_string),
+            display_name: self
+                .get_tag_value("reply-parent-display-name")
+                .filter(|name| !name.is_empty())
+                .map(unescape_tag_value)
+                .unwrap_or_else(|| login.clone()),
+            login,
+            text: self
+                .get_tag_value("reply-parent-msg-body")
+                .map(unescape_tag_value)
+                .unwrap_or_default(),
+        })
+    }
+
     pub fn get_display_name(&self) -> Option<&'a str> {
         self.get_tag_value("display-name")
     }
@@ -218,12 +258,14 @@ impl<'a> IrcMessage<'a> {
         let badges_str = self.get_tag_value("badges").map(str::to_string);
         let message_id = self.get_tag_value("id").map(str::to_string);
 
-        let is_moderator = self.get_tag_value("mod") == Some("1")
-            || badges_str.as_ref().is_some_and(|b| b.contains("moderator"));
-        let is_subscriber = self.get_tag_value("subscriber") == Some("1")
-            || self
-                .get_tag_value("badges")
-                .is_some_and(|b| b.contains("subscriber/"));
+        let is_moderator = self.tag_flag("mod") || self.has_badge("moderator");
+        let is_subscriber = self.tag_flag("subscriber") || self.has_badge("subscriber");
+        let is_vip = self.get_tag_value("vip").is_some() || self.has_badge("vip");
+        let is_broadcaster = self.has_badge("broadcaster");
+        let bits = self
+            .get_tag_value("bits")
+            .and_then(|bits| bits.parse().ok())
+            .unwrap_or(0);
 
         let mut raw_tags_map = HashMap::new();
         if let Some(tags_str) = self.tags {
@@ -243,7 +285,13 @@ impl<'a> IrcMessage<'a> {
             badges: badges_str,
             is_moderator,
             is_subscriber,
+            is_vip,
+            is_broadcaster,
+            bits,
+            is_first_message: self.tag_flag("first-msg"),
+            is_returning_chatter: self.tag_flag("returning-chatter"),
             message_id,
+            reply_parent: self.get_reply_parent(),
             raw_irc_tags: if raw_tags_map.is_empty() {
                 None
             } else {
@@ -252,6 +300,112 @@ impl<'a> IrcMessage<'a> {
             timestamp: Utc::now(),
         })
     }
+
+    /// Parses CLEARCHAT and CLEARMSG. A CLEARCHAT naming a user in its trailing
+    /// parameter clears that user; one without clears the whole chat.
+    pub fn to_chat_moderation(&self) -> Option<ChatModeration> {
+        match self.command? {
+            CMD_CLEARCHAT => match self.params.get(1) {
+                Some(login) => Some(ChatModeration::UserCleared {
+                    user_id: self.get_tag_value("target-user-id").map(str::to_string),
+                    login: login.to_lowercase(),
+                    ban_duration_seconds: self
+                        .get_tag_value("ban-duration")
+                        .and_then(|seconds| seconds.parse().ok()),
+                }),
+                None => Some(ChatModeration::ChatCleared),
+            },
+            CMD_CLEARMSG => Some(ChatModeration::MessageDeleted {
+                message_id: self.get_tag_value("target-msg-id")?.to_string(),
+                login: self.get_tag_value("login").unwrap_or_default().to_string(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Parses a USERNOTICE for `channel_name_str` into a typed event.
+    pub fn to_user_notice(&self, channel_name_str: &str) -> Option<UserNotice> {
+        if self.command != Some(CMD_USERNOTICE) {
+            return None;
+        }
+        let target_channel_in_msg = self.params.first()?.trim_start_matches('#');
+        if !target_channel_in_msg.eq_ignore_ascii_case(channel_name_str) {
+            return None;
+        }
+
+        let param = |name: &str| self.get_tag_value(&format!("msg-param-{}", name));
+        let count = |name: &str| {
+            param(name)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0)
+        };
+        let plan = || param("sub-plan").unwrap_or_default().to_string();
+        let msg_id = self.get_tag_value("msg-id")?;
+        let kind = match msg_id {
+            "sub" | "resub" => UserNoticeKind::Subscription {
+                plan: plan(),
+                cumulative_months: count("cumulative-months"),
+                is_resub: msg_id == "resub",
+            },
+            "subgift" | "anonsubgift" => UserNoticeKind::GiftSubscription {
+                plan: plan(),
+                recipient_login: param("recipient-user-name").unwrap_or_default().to_string(),
+                recipient_display_name: param("recipient-display-name")
+                    .unwrap_or_default()
+                    .to_string(),
+            },
+            "submysterygift" | "anonsubmysterygift" => UserNoticeKind::MysteryGift {
+                plan: plan(),
+                count: count("mass-gift-count"),
+            },
+            "raid" => UserNoticeKind::Raid {
+                viewer_count: count("viewerCount"),
+            },
+            other => UserNoticeKind::Other {
+                msg_id: other.to_string(),
+            },
+        };
+
+        let login = self
+            .get_tag_value("login")
+            .or_else(|| self.get_prefix_username())
+            .unwrap_or("unknown_user")
+            .to_string();
+        Some(UserNotice {
+            channel: channel_name_str.to_string(),
+            display_name: self
+                .get_display_name()
+                .filter(|name| !name.is_empty())
+                .unwrap_or(&login)
+                .to_string(),
+            login,
+            user_id: self.get_tag_value("user-id").map(str::to_string),
+            message: self.params.get(1).map(|text| text.trim().to_string()),
+            kind,
+            timestamp: Utc::now(),
+        })
+    }
+}
+
+/// Reverses the IRCv3 escaping of tag values (`\s` for space and so on).
+fn unescape_tag_value(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => unescaped.push(' '),
+            Some(':') => unescaped.push(';'),
+            Some('r') => unescaped.push('\r'),
+            Some('n') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+    unescaped
 }
 
 #[cfg(test)]
@@ -310,4 +464,116 @@ mod tests {
         assert!(IrcMessage::parse(":tmi.twitch.tv PONG tmi.twitch.tv :health-check").is_ok());
         assert!(IrcMessage::parse("@badge-info=;badges=;color=;display-name=TestUser;emotes=;first-msg=0;flags=;id=abc;mod=0;returning-chatter=0;room-id=123;subscriber=0;turbo=0;user-id=456;user-type= :testuser!testuser@testuser.tmi.twitch.tv PRIVMSG #channel :Hello World").is_ok());
     }
+
+    #[test]
+    fn test_clearchat_and_clearmsg() {
+        let timeout = IrcMessage::parse("@ban-duration=600;room-id=1;target-user-id=456;tmi-sent-ts=1 :tmi.twitch.tv CLEARCHAT #channel :SpamBot").unwrap();
+        assert_eq!(
+            timeout.to_chat_moderation(),
+            Some(ChatModeration::UserCleared {
+                user_id: Some("456".to_string()),
+                login: "spambot".to_string(),
+                ban_duration_seconds: Some(600),
+            })
+        );
+
+        let clear = IrcMessage::parse("@room-id=1;tmi-sent-ts=1 :tmi.twitch.tv CLEARCHAT #channel")
+            .unwrap();
+        assert_eq!(
+            clear.to_chat_moderation(),
+            Some(ChatModeration::ChatCleared)
+        );
+
+        let deleted = IrcMessage::parse("@login=spambot;room-id=;target-msg-id=abc-123;tmi-sent-ts=1 :tmi.twitch.tv CLEARMSG #channel :buy followers").unwrap();
+        assert_eq!(
+            deleted.to_chat_moderation(),
+            Some(ChatModeration::MessageDeleted {
+                message_id: "abc-123".to_string(),
+                login: "spambot".to_string(),
+            })
+        );
+
+        let privmsg =
+            IrcMessage::parse(":user!user@user.tmi.twitch.tv PRIVMSG #channel :hi").unwrap();
+        assert_eq!(privmsg.to_chat_moderation(), None);
+    }
+
+    #[test]
+    fn test_usernotice_kinds() {
+        let resub = IrcMessage::parse("@badge-info=subscriber/6;display-name=Ronni;login=ronni;msg-id=resub;msg-param-cumulative-months=6;msg-param-sub-plan=Prime;user-id=1337 :tmi.twitch.tv USERNOTICE #channel :Great stream -- keep it up!").unwrap();
+        let notice = resub.to_user_notice("channel").unwrap();
+        assert_eq!(notice.login, "ronni");
+        assert_eq!(notice.display_name, "Ronni");
+        assert_eq!(notice.user_id.as_deref(), Some("1337"));
+        assert_eq!(
+            notice.message.as_deref(),
+            Some("Great stream -- keep it up!")
+        );
+        assert_eq!(
+            notice.kind,
+            UserNoticeKind::Subscription {
+                plan: "Prime".to_string(),
+                cumulative_months: 6,
+                is_resub: true,
+            }
+        );
+
+        let raid = IrcMessage::parse("@display-name=TestChannel;login=testchannel;msg-id=raid;msg-param-displayName=TestChannel;msg-param-login=testchannel;msg-param-viewerCount=15 :tmi.twitch.tv USERNOTICE #channel").unwrap();
+        let notice = raid.to_user_notice("channel").unwrap();
+        assert_eq!(notice.kind, UserNoticeKind::Raid { viewer_count: 15 });
+        assert_eq!(notice.message, None);
+
+        let gift = IrcMessage::parse("@display-name=Gifter;login=gifter;msg-id=subgift;msg-param-recipient-display-name=Lucky;msg-param-recipient-user-name=lucky;msg-param-sub-plan=1000 :tmi.twitch.tv USERNOTICE #channel").unwrap();
+        assert_eq!(
+            gift.to_user_notice("channel").unwrap().kind,
+            UserNoticeKind::GiftSubscription {
+                plan: "1000".to_string(),
+                recipient_login: "lucky".to_string(),
+                recipient_display_name: "Lucky".to_string(),
+            }
+        );
+
+        let announcement = IrcMessage::parse(
+            "@login=mod;msg-id=announcement :tmi.twitch.tv USERNOTICE #channel :Hello",
+        )
+        .unwrap();
+        assert_eq!(
+            announcement.to_user_notice("channel").unwrap().kind,
+            UserNoticeKind::Other {
+                msg_id: "announcement".to_string()
+            }
+        );
+        assert!(announcement.to_user_notice("otherchannel").is_none());
+    }
+
+    #[test]
+    fn test_privmsg_bits_badges_and_reply() {
+        let cheer = IrcMessage::parse("@badges=vip/1,bits/100;bits=250;display-name=Cheerer;first-msg=1;id=abc;returning-chatter=0;user-id=7;vip=1 :cheerer!cheerer@cheerer.tmi.twitch.tv PRIVMSG #channel :Cheer250 vote B").unwrap();
+        let parsed = cheer.to_parsed_twitch_message("channel").unwrap();
+        assert_eq!(parsed.bits, 250);
+        assert!(parsed.is_vip);
+        assert!(parsed.is_first_message);
+        assert!(!parsed.is_returning_chatter);
+        assert!(!parsed.is_broadcaster);
+        assert!(!parsed.is_moderator);
+        assert_eq!(parsed.reply_parent, None);
+
+        let reply = IrcMessage::parse("@badges=broadcaster/1,subscriber/0;display-name=Host;id=def;reply-parent-display-name=Cheerer;reply-parent-msg-body=Cheer250\\svote\\sB;reply-parent-msg-id=abc;reply-parent-user-id=7;reply-parent-user-login=cheerer;returning-chatter=1 :host!host@host.tmi.twitch.tv PRIVMSG #channel :@Cheerer thanks!").unwrap();
+        let parsed = reply.to_parsed_twitch_message("channel").unwrap();
+        assert_eq!(parsed.bits, 0);
+        assert!(parsed.is_broadcaster);
+        assert!(parsed.is_subscriber);
+        assert!(parsed.is_returning_chatter);
+        assert!(!parsed.is_vip);
+        assert_eq!(
+            parsed.reply_parent,
+            Some(ReplyParent {
+                message_id: "abc".to_string(),
+                user_id: Some("7".to_string()),
+                login: "cheerer".to_string(),
+                display_name: "Cheerer".to_string(),
+                text: "Cheer250 vote B".to_string(),
+            })
+        );
+    }
 }
@@ -1,11 +1,17 @@
+pub mod announcements;
 pub mod auth;
 pub mod error;
 pub mod irc_parser;
+pub mod oauth;
 pub mod service;
 pub mod types;
 
 // Re-export the main types that external modules need
+pub use announcements::ChatAnnouncer;
 pub use auth::TokenProvider;
 pub use error::TwitchError;
 pub use service::TwitchServiceHandle;
-pub use types::{ParsedTwitchMessage, TwitchChannelConnectionStatus};
+pub use types::{
+    ChatMessageRef, ChatModeration, ParsedTwitchMessage, TwitchChannelConnectionStatus,
+    TwitchChannelEvent, UserNotice,
+};
@@ -0,0 +1,180 @@
+use super::error::TwitchError;
+use crate::config::TwitchConfig;
+use serde::Deserialize;
+
+/// Twitch account a broadcaster logged in with.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TwitchUser {
+    pub id: String,
+    /// Lowercase login name, which is also the channel name.
+    pub login: String,
+    pub display_name: String,
+}
+
+#[derive(Deserialize)]
+struct UserTokenResponse {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct HelixUsersResponse {
+    data: Vec<TwitchUser>,
+}
+
+/// URL to send the browser to for "Log in with Twitch". No scopes are requested;
+/// identifying the account is all the server needs.
+pub fn authorize_url(twitch_config: &TwitchConfig, state: &str) -> Result<String, TwitchError> {
+    let url = reqwest::Url::parse_with_params(
+        &format!("{}/oauth2/authorize", twitch_config.auth_base_url),
+        [
+            ("client_id", twitch_config.client_id.as_str()),
+            ("redirect_uri", twitch_config.login.redirect_url.as_str()),
+            ("response_type", "code"),
+            ("scope", ""),
+            ("state", state),
+        ],
+    )
+    .map_err(|e| TwitchError::TwitchAuth(format!("Invalid authorize URL: {}", e)))?;
+    Ok(url.into())
+}
+
+/// Completes the authorization code flow and returns the account that approved it.
+pub async fn login_user(
+    twitch_config: &TwitchConfig,
+    code: &str,
+) -> Result<TwitchUser, TwitchError> {
+    let access_token = exchange_code(twitch_config, code).await?;
+    fetch_user(twitch_config, &access_token).await
+}
+
+async fn exchange_code(twitch_config: &TwitchConfig, code: &str) -> Result<String, TwitchError> {
+    let url = format!("{}/oauth2/token", twitch_config.auth_base_url);
+    let params = [
+        ("client_id", twitch_config.client_id.as_str()),
+        ("client_secret", twitch_config.client_secret.as_str()),
+        ("code", code),
+        ("grant_type", "authorization_code"),
+        ("redirect_uri", twitch_config.login.redirect_url.as_str()),
+    ];
+    let response = reqwest::Client::new()
+        .post(url)
+        .form(&params)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        tracing::warn!(http.status = %status, response.body = %body, "Twitch rejected authorization code");
+        return Err(TwitchError::TwitchAuth(format!(
+            "Authorization code exchange failed with status {}",
+            status
+        )));
+    }
+    Ok(response.json::<UserTokenResponse>().await?.access_token)
+}
+
+async fn fetch_user(
+    twitch_config: &TwitchConfig,
+    access_token: &str,
+) -> Result<TwitchUser, TwitchError> {
+    let url = format!("{}/helix/users", twitch_config.api_base_url);
+    let response = reqwest::Client::new()
+        .get(url)
+        .bearer_auth(access_token)
+        .header("Client-Id", &twitch_config.client_id)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(TwitchError::TwitchAuth(format!(
+            "Fetching the logged in user failed with status {}",
+            response.status()
+        )));
+    }
+    response
+        .json::<HelixUsersResponse>()
+        .await?
+        .data
+        .into_iter()
+        .next()
+        .ok_or_else(|| TwitchError::TwitchAuth("Twitch returned no user for the token".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{TwitchChatConfig, TwitchLoginConfig};
+    use axum::{
+        Form, Json, Router,
+        http::HeaderMap,
+        routing::{get, post},
+    };
+    use std::collections::HashMap;
+
+    fn twitch_config(base_url: &str) -> TwitchConfig {
+        TwitchConfig {
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            anonymous: false,
+            irc_server_url: "irc.chat.twitch.tv:6667".to_string(),
+            auth_base_url: base_url.to_string(),
+            api_base_url: base_url.to_string(),
+            login: TwitchLoginConfig {
+                enabled: true,
+                redirect_url: "http://localhost:8080/api/auth/twitch/callback".to_string(),
+                ..TwitchLoginConfig::default()
+            },
+            chat: TwitchChatConfig::default(),
+        }
+    }
+
+    #[test]
+    fn test_authorize_url_carries_client_and_state() {
+        let url = authorize_url(&twitch_config("https://id.twitch.tv"), "abc").unwrap();
+        let url = reqwest::Url::parse(&url).unwrap();
+        let params: HashMap<_, _> = url.query_pairs().into_owned().collect();
+
+        assert_eq!(url.path(), "/oauth2/authorize");
+        assert_eq!(params["client_id"], "client");
+        assert_eq!(params["state"], "abc");
+        assert_eq!(params["response_type"], "code");
+        assert_eq!(
+            params["redirect_uri"],
+            "http://localhost:8080/api/auth/twitch/callback"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_login_user_exchanges_code_against_configured_endpoints() {
+        let app = Router::new()
+            .route(
+                "/oauth2/token",
+                post(|Form(params): Form<HashMap<String, String>>| async move {
+                    assert_eq!(params["grant_type"], "authorization_code");
+                    assert_eq!(params["code"], "the-code");
+                    Json(serde_json::json!({ "access_token": "user-token" }))
+                }),
+            )
+            .route(
+                "/helix/users",
+                get(|headers: HeaderMap| async move {
+                    assert_eq!(headers["authorization"], "Bearer user-token");
+                    assert_eq!(headers["client-id"], "client");
+                    Json(serde_json::json!({
+                        "data": [{ "id": "42", "login": "somestreamer", "display_name": "SomeStreamer" }]
+                    }))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let user = login_user(&twitch_config(&base_url), "the-code")
+            .await
+            .unwrap();
+
+        assert_eq!(user.login, "somestreamer");
+        assert_eq!(user.id, "42");
+    }
+}
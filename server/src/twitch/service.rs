@@ -1,7 +1,10 @@
 use std::collections::{HashMap, hash_map::Entry};
+use std::num::NonZeroU32;
 use std::pin::Pin;
 use std::time::Duration;
 
+use governor::{DefaultKeyedRateLimiter, Quota, RateLimiter};
+
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpStream;
 use tokio::sync::{mpsc, oneshot, watch};
@@ -12,22 +15,26 @@ use crate::twitch::auth::TokenProvider;
 use crate::twitch::error::TwitchError;
 use crate::twitch::irc_parser::{
     AUTH_ERROR_IMPROPERLY_FORMATTED, AUTH_ERROR_INVALID_NICK, AUTH_ERROR_LOGIN_FAILED, CMD_CAP,
-    CMD_JOIN, CMD_NICK, CMD_NOTICE, CMD_PASS, CMD_PING, CMD_PONG, CMD_PRIVMSG, CMD_RECONNECT,
-    IRC_ACK, IRC_NAK, IRC_WELCOME_TEXT, IrcMessage, RPL_WELCOME, TWITCH_CAPABILITIES,
+    CMD_CLEARCHAT, CMD_CLEARMSG, CMD_JOIN, CMD_NICK, CMD_NOTICE, CMD_PASS, CMD_PING, CMD_PONG,
+    CMD_PRIVMSG, CMD_RECONNECT, CMD_USERNOTICE, IRC_ACK, IRC_NAK, IRC_WELCOME_TEXT, IrcMessage,
+    RPL_WELCOME, TWITCH_CAPABILITIES,
 };
-use crate::twitch::types::{ParsedTwitchMessage, TwitchChannelConnectionStatus};
+use crate::twitch::types::{TwitchChannelConnectionStatus, TwitchChannelEvent};
 
 const DEFAULT_COMMAND_BUFFER: usize = 64;
 const DEFAULT_EVENT_BUFFER: usize = 512;
 const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(5);
 const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+const CHAT_RATE_LIMIT_WINDOW: Duration = Duration::from_secs(30);
+/// Twitch drops chat messages longer than this many characters.
+const MAX_CHAT_MESSAGE_CHARS: usize = 500;
 
 #[derive(Debug)]
 enum TwitchServiceCommand {
     Subscribe {
         channel_name: String,
         lobby_id: Uuid,
-        twitch_message_tx_for_lobby: mpsc::Sender<ParsedTwitchMessage>,
+        twitch_message_tx_for_lobby: mpsc::Sender<TwitchChannelEvent>,
         respond_to:
             oneshot::Sender<Result<watch::Receiver<TwitchChannelConnectionStatus>, TwitchError>>,
     },
@@ -36,6 +43,11 @@ enum TwitchServiceCommand {
         lobby_id: Uuid,
         respond_to: oneshot::Sender<Result<(), TwitchError>>,
     },
+    SendChatMessage {
+        channel_name: String,
+        message: String,
+        respond_to: oneshot::Sender<Result<(), TwitchError>>,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -63,7 +75,7 @@ impl TwitchServiceHandle {
         &self,
         channel_name: String,
         lobby_id: Uuid,
-        twitch_message_tx_for_lobby: mpsc::Sender<ParsedTwitchMessage>,
+        twitch_message_tx_for_lobby: mpsc::Sender<TwitchChannelEvent>,
     ) -> Result<watch::Receiver<TwitchChannelConnectionStatus>, TwitchError> {
         let (respond_to_tx, respond_to_rx) = oneshot::channel();
         self.sender
@@ -116,10 +128,41 @@ impl TwitchServiceHandle {
             ))
         })?
     }
+
+    /// Posts `message` to a channel some lobby is subscribed to, as the configured
+    /// chat bot. Fails without a bot account, before the channel is joined, or
+    /// when the channel's rate limit is exhausted; messages are never queued.
+    pub async fn send_chat_message(
+        &self,
+        channel_name: String,
+        message: String,
+    ) -> Result<(), TwitchError> {
+        let (respond_to_tx, respond_to_rx) = oneshot::channel();
+        self.sender
+            .send(TwitchServiceCommand::SendChatMessage {
+                channel_name,
+                message,
+                respond_to: respond_to_tx,
+            })
+            .await
+            .map_err(|e| {
+                TwitchError::ActorComm(format!(
+                    "Failed to send SendChatMessage command to TwitchService: {}",
+                    e
+                ))
+            })?;
+
+        respond_to_rx.await.map_err(|e| {
+            TwitchError::ActorComm(format!(
+                "TwitchService failed to respond to SendChatMessage command: {}",
+                e
+            ))
+        })?
+    }
 }
 
 struct ChannelState {
-    subscribers: HashMap<Uuid, mpsc::Sender<ParsedTwitchMessage>>,
+    subscribers: HashMap<Uuid, mpsc::Sender<TwitchChannelEvent>>,
     status_tx: watch::Sender<TwitchChannelConnectionStatus>,
     joined: bool,
 }
@@ -153,6 +196,8 @@ struct TwitchService {
     bot_nickname: Option<String>,
     event_buffer_size: usize,
     connection_attempt: u32,
+    /// Per-channel limit on messages sent by the chat bot.
+    chat_limiter: DefaultKeyedRateLimiter<String>,
 }
 
 impl TwitchService {
@@ -161,7 +206,13 @@ impl TwitchService {
         commands: mpsc::Receiver<TwitchServiceCommand>,
         event_buffer_size: usize,
     ) -> Self {
+        let messages_per_window = NonZeroU32::new(token_provider.chat_messages_per_30_seconds())
+            .unwrap_or(NonZeroU32::MIN);
+        let chat_quota = Quota::with_period(CHAT_RATE_LIMIT_WINDOW / messages_per_window.get())
+            .expect("chat rate limit period is non-zero")
+            .allow_burst(messages_per_window);
         TwitchService {
+            chat_limiter: RateLimiter::keyed(chat_quota),
             token_provider,
             commands,
             channel_states: HashMap::new(),
@@ -247,14 +298,59 @@ impl TwitchService {
                 let result = self.unsubscribe_lobby(&normalized_channel, lobby_id).await;
                 let _ = respond_to.send(result);
             }
+            TwitchServiceCommand::SendChatMessage {
+                channel_name,
+                message,
+                respond_to,
+            } => {
+                let normalized_channel = channel_name.to_lowercase();
+                let result = self.send_chat_message(&normalized_channel, &message).await;
+                let _ = respond_to.send(result);
+            }
         }
     }
 
+    async fn send_chat_message(&mut self, channel: &str, message: &str) -> Result<(), TwitchError> {
+        if !self.token_provider.can_send_chat() {
+            return Err(TwitchError::ChatUnavailable(
+                "no chat bot account is configured".to_string(),
+            ));
+        }
+        let joined = self
+            .channel_states
+            .get(channel)
+            .is_some_and(|state| state.joined);
+        let Some(conn) = self
+            .irc_connection
+            .as_ref()
+            .filter(|_| joined && self.connection_ready)
+        else {
+            return Err(TwitchError::TwitchConnection(format!(
+                "Not joined to channel '{}'",
+                channel
+            )));
+        };
+        if self.chat_limiter.check_key(&channel.to_string()).is_err() {
+            return Err(TwitchError::ChatRateLimited {
+                channel: channel.to_string(),
+            });
+        }
+
+        let text = sanitize_chat_message(message);
+        if text.is_empty() {
+            return Ok(());
+        }
+        conn.send_raw(format!("{} #{} :{}", CMD_PRIVMSG, channel, text))
+            .await?;
+        tracing::debug!(twitch.channel = %channel, "Sent chat message");
+        Ok(())
+    }
+
     async fn subscribe_lobby(
         &mut self,
         channel: String,
         lobby_id: Uuid,
-        subscriber_tx: mpsc::Sender<ParsedTwitchMessage>,
+        subscriber_tx: mpsc::Sender<TwitchChannelEvent>,
     ) -> (
         watch::Receiver<TwitchChannelConnectionStatus>,
         Result<(), TwitchError>,
@@ -414,8 +510,8 @@ impl TwitchService {
                         }
                     }
                 }
-                Some(CMD_PRIVMSG) => {
-                    self.dispatch_privmsg(&message).await;
+                Some(CMD_PRIVMSG | CMD_CLEARCHAT | CMD_CLEARMSG | CMD_USERNOTICE) => {
+                    self.dispatch_channel_event(&message).await;
                 }
                 _ => {
                     if line.contains(IRC_WELCOME_TEXT) {
@@ -429,7 +525,9 @@ impl TwitchService {
         }
     }
 
-    async fn dispatch_privmsg(&mut self, message: &IrcMessage<'_>) {
+    /// Forwards a chat message, moderator action or user notice to the lobbies
+    /// subscribed to its channel.
+    async fn dispatch_channel_event(&mut self, message: &IrcMessage<'_>) {
         let Some(target_channel) = message.params().first() else {
             return;
         };
@@ -437,12 +535,24 @@ impl TwitchService {
 
         let mut channel_should_close = false;
 
+        let event = match message.command() {
+            Some(CMD_PRIVMSG) => message
+                .to_parsed_twitch_message(&normalized_channel)
+                .map(TwitchChannelEvent::Message),
+            Some(CMD_USERNOTICE) => message
+                .to_user_notice(&normalized_channel)
+                .map(TwitchChannelEvent::Notice),
+            _ => message
+                .to_chat_moderation()
+                .map(TwitchChannelEvent::Moderation),
+        };
+
         if let Some(state) = self.channel_states.get_mut(&normalized_channel)
-            && let Some(parsed) = message.to_parsed_twitch_message(&normalized_channel)
+            && let Some(event) = event
         {
             let mut failed = Vec::new();
             for (lobby_id, tx) in &state.subscribers {
-                if tx.send(parsed.clone()).await.is_err() {
+                if tx.send(event.clone()).await.is_err() {
                     failed.push(*lobby_id);
                 }
             }
@@ -533,6 +643,20 @@ impl TwitchService {
     }
 }
 
+/// Flattens line breaks, which would end the IRC command early, and truncates to
+/// Twitch's message length limit.
+fn sanitize_chat_message(message: &str) -> String {
+    message
+        .split(['\r', '\n'])
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(MAX_CHAT_MESSAGE_CHARS)
+        .collect()
+}
+
 enum IrcEvent {
     Line(String),
     Closed(String),
@@ -611,17 +735,18 @@ impl IrcConnection {
             .send_raw(format!("{}\r\n", TWITCH_CAPABILITIES))
             .await?;
 
-        let token = token_provider.get_token().await;
-        connection
-            .send_raw(format!("{} oauth:{}\r\n", CMD_PASS, token))
-            .await?;
-
-        let nickname = format!("justinfan{}", rand::random::<u32>() % 80000 + 1000);
+        let login = token_provider.irc_login().await;
+        // Twitch accepts `justinfan` logins without a password.
+        if let Some(password) = login.password {
+            connection
+                .send_raw(format!("{} oauth:{}\r\n", CMD_PASS, password))
+                .await?;
+        }
         connection
-            .send_raw(format!("{} {}\r\n", CMD_NICK, nickname))
+            .send_raw(format!("{} {}\r\n", CMD_NICK, login.nickname))
             .await?;
 
-        Ok((connection, nickname))
+        Ok((connection, login.nickname))
     }
 
     async fn send_raw<S: Into<String>>(&self, line: S) -> Result<(), TwitchError> {
@@ -634,3 +759,197 @@ impl IrcConnection {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{TwitchChatConfig, TwitchConfig};
+    use axum::{Form, Json, Router, routing::post};
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+
+    /// Accepts one IRC connection, welcomes it once it logs in, forwards every
+    /// line the service sends and writes every line pushed to the returned sender.
+    async fn spawn_irc_server() -> (
+        String,
+        mpsc::UnboundedReceiver<String>,
+        mpsc::UnboundedSender<String>,
+    ) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let (line_tx, line_rx) = mpsc::unbounded_channel();
+        let (push_tx, mut push_rx) = mpsc::unbounded_channel::<String>();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = tokio::io::split(stream);
+            let mut lines = BufReader::new(reader).lines();
+            loop {
+                tokio::select! {
+                    line = lines.next_line() => {
+                        let Ok(Some(line)) = line else { break };
+                        if line.starts_with(CMD_NICK) {
+                            writer
+                                .write_all(b":tmi.twitch.tv 001 kolmodinbot :Welcome, GLHF!\r\n")
+                                .await
+                                .unwrap();
+                        }
+                        let _ = line_tx.send(line);
+                    }
+                    Some(pushed) = push_rx.recv() => {
+                        writer.write_all(format!("{pushed}\r\n").as_bytes()).await.unwrap();
+                    }
+                }
+            }
+        });
+        (addr, line_rx, push_tx)
+    }
+
+    /// Answers both the app token and the bot token refresh requests.
+    async fn spawn_token_endpoint() -> String {
+        let app = Router::new().route(
+            "/oauth2/token",
+            post(|Form(_): Form<HashMap<String, String>>| async {
+                Json(serde_json::json!({
+                    "access_token": "bot-token",
+                    "refresh_token": "next-refresh-token",
+                    "expires_in": 14400
+                }))
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        base_url
+    }
+
+    async fn next_line_starting_with(
+        lines: &mut mpsc::UnboundedReceiver<String>,
+        prefix: &str,
+    ) -> String {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let line = lines.recv().await.expect("IRC connection closed");
+                if line.starts_with(prefix) {
+                    return line;
+                }
+            }
+        })
+        .await
+        .expect("timed out waiting for IRC line")
+    }
+
+    #[tokio::test]
+    async fn test_send_chat_message_posts_to_channel_until_rate_limited() {
+        let (irc_addr, mut irc_lines, _irc_push) = spawn_irc_server().await;
+        let base_url = spawn_token_endpoint().await;
+        let twitch_config = TwitchConfig {
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            anonymous: false,
+            irc_server_url: irc_addr,
+            auth_base_url: base_url.clone(),
+            api_base_url: base_url,
+            login: Default::default(),
+            chat: TwitchChatConfig {
+                enabled: true,
+                bot_login: "KolmodinBot".to_string(),
+                refresh_token: "refresh-token".to_string(),
+                messages_per_30_seconds: 2,
+                ..TwitchChatConfig::default()
+            },
+        };
+        let token_provider = TokenProvider::new(Arc::new(twitch_config)).await.unwrap();
+        let service = TwitchServiceHandle::spawn(token_provider, 0, 0);
+
+        let (event_tx, _event_rx) = mpsc::channel(8);
+        let mut status = service
+            .subscribe_to_channel("SomeStreamer".to_string(), Uuid::new_v4(), event_tx)
+            .await
+            .unwrap();
+        assert_eq!(
+            next_line_starting_with(&mut irc_lines, CMD_PASS).await,
+            "PASS oauth:bot-token"
+        );
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            status.wait_for(|s| *s == TwitchChannelConnectionStatus::Connected),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        next_line_starting_with(&mut irc_lines, CMD_JOIN).await;
+
+        service
+            .send_chat_message(
+                "SomeStreamer".to_string(),
+                "Game on!\nGood luck".to_string(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            next_line_starting_with(&mut irc_lines, CMD_PRIVMSG).await,
+            "PRIVMSG #somestreamer :Game on! Good luck"
+        );
+        service
+            .send_chat_message("somestreamer".to_string(), "Second".to_string())
+            .await
+            .unwrap();
+        next_line_starting_with(&mut irc_lines, CMD_PRIVMSG).await;
+
+        let result = service
+            .send_chat_message("somestreamer".to_string(), "Third".to_string())
+            .await;
+        assert!(matches!(
+            result,
+            Err(TwitchError::ChatRateLimited { channel }) if channel == "somestreamer"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_user_notices_reach_subscribed_lobbies() {
+        let (irc_addr, mut irc_lines, irc_push) = spawn_irc_server().await;
+        let twitch_config = TwitchConfig {
+            client_id: String::new(),
+            client_secret: String::new(),
+            anonymous: true,
+            irc_server_url: irc_addr,
+            auth_base_url: String::new(),
+            api_base_url: String::new(),
+            login: Default::default(),
+            chat: TwitchChatConfig::default(),
+        };
+        let token_provider = TokenProvider::new(Arc::new(twitch_config)).await.unwrap();
+        let service = TwitchServiceHandle::spawn(token_provider, 0, 0);
+
+        let (event_tx, mut event_rx) = mpsc::channel(8);
+        let mut status = service
+            .subscribe_to_channel("SomeStreamer".to_string(), Uuid::new_v4(), event_tx)
+            .await
+            .unwrap();
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            status.wait_for(|s| *s == TwitchChannelConnectionStatus::Connected),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        next_line_starting_with(&mut irc_lines, CMD_JOIN).await;
+
+        irc_push
+            .send("@display-name=TestChannel;login=testchannel;msg-id=raid;msg-param-displayName=TestChannel;msg-param-login=testchannel;msg-param-viewerCount=15 :tmi.twitch.tv USERNOTICE #somestreamer".to_string())
+            .unwrap();
+        let event = tokio::time::timeout(Duration::from_secs(5), event_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let TwitchChannelEvent::Notice(notice) = event else {
+            panic!("expected a user notice, got {event:?}");
+        };
+        assert_eq!(notice.channel, "somestreamer");
+        assert_eq!(notice.login, "testchannel");
+        assert_eq!(
+            notice.kind,
+            crate::twitch::types::UserNoticeKind::Raid { viewer_count: 15 }
+        );
+    }
+}
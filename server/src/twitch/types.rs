@@ -33,8 +33,139 @@ pub struct ParsedTwitchMessage {
     pub badges: Option<String>,
     pub is_moderator: bool,
     pub is_subscriber: bool,
+    pub is_vip: bool,
+    pub is_broadcaster: bool,
+    /// Bits cheered with this message, 0 for a regular message.
+    pub bits: u32,
+    /// The sender's first message ever in this channel.
+    pub is_first_message: bool,
+    /// Twitch flags the sender as a returning chatter.
+    pub is_returning_chatter: bool,
     pub message_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_parent: Option<ReplyParent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub raw_irc_tags: Option<HashMap<String, String>>,
     pub timestamp: DateTime<Utc>,
 }
+
+impl ParsedTwitchMessage {
+    pub fn message_ref(&self) -> ChatMessageRef {
+        ChatMessageRef {
+            message_id: self.message_id.clone(),
+            user_id: self.sender_user_id.clone(),
+            username: self.sender_username.clone(),
+        }
+    }
+}
+
+/// The message a chat reply responds to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplyParent {
+    pub message_id: String,
+    pub user_id: Option<String>,
+    pub login: String,
+    pub display_name: String,
+    pub text: String,
+}
+
+/// The chat message behind a guess, vote or submission, kept so the game can take
+/// it back when a moderator removes the message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatMessageRef {
+    pub message_id: Option<String>,
+    pub user_id: Option<String>,
+    /// Display name of the sender, which matches the login apart from case for
+    /// most accounts.
+    pub username: String,
+}
+
+/// A moderator removing messages from a channel's chat.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatModeration {
+    /// CLEARCHAT for a user: a timeout (`ban_duration_seconds` set) or a permanent
+    /// ban. Removes all of the user's messages.
+    UserCleared {
+        user_id: Option<String>,
+        login: String,
+        ban_duration_seconds: Option<u64>,
+    },
+    /// CLEARCHAT without a user, i.e. `/clear`. Tidies chat but punishes no one,
+    /// so games keep everything it removed.
+    ChatCleared,
+    /// CLEARMSG: a single message was deleted.
+    MessageDeleted { message_id: String, login: String },
+}
+
+impl ChatModeration {
+    /// Whether this action removed `message`, voiding whatever it did in a game.
+    pub fn removes(&self, message: &ChatMessageRef) -> bool {
+        match self {
+            ChatModeration::UserCleared { user_id, login, .. } => {
+                match (user_id, &message.user_id) {
+                    (Some(cleared), Some(sender)) => cleared == sender,
+                    _ => message.username.eq_ignore_ascii_case(login),
+                }
+            }
+            ChatModeration::ChatCleared => false,
+            ChatModeration::MessageDeleted { message_id, .. } => {
+                message.message_id.as_deref() == Some(message_id.as_str())
+            }
+        }
+    }
+}
+
+/// A USERNOTICE: a subscription, gifted subscriptions, a raid or a similar channel
+/// event announced in chat.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UserNotice {
+    pub channel: String,
+    /// Login of the subscriber, gifter or raider.
+    pub login: String,
+    pub display_name: String,
+    pub user_id: Option<String>,
+    /// Text the user attached, e.g. to a resub.
+    pub message: Option<String>,
+    pub kind: UserNoticeKind,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// The `msg-id` of a USERNOTICE with its parameters. `plan` is Twitch's sub plan:
+/// `Prime`, `1000`, `2000` or `3000`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum UserNoticeKind {
+    /// `sub` and `resub`.
+    Subscription {
+        plan: String,
+        cumulative_months: u32,
+        is_resub: bool,
+    },
+    /// `subgift`: one subscription gifted to `recipient_login`.
+    GiftSubscription {
+        plan: String,
+        recipient_login: String,
+        recipient_display_name: String,
+    },
+    /// `submysterygift`: `count` subscriptions gifted to random viewers. Each of
+    /// them is also announced as a `GiftSubscription`.
+    MysteryGift {
+        plan: String,
+        count: u32,
+    },
+    Raid {
+        viewer_count: u32,
+    },
+    /// Any other `msg-id`, such as `announcement`.
+    Other {
+        msg_id: String,
+    },
+}
+
+/// What the Twitch service forwards to the lobbies subscribed to a channel.
+#[derive(Debug, Clone)]
+pub enum TwitchChannelEvent {
+    Message(ParsedTwitchMessage),
+    Moderation(ChatModeration),
+    Notice(UserNotice),
+}
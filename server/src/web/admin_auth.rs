@@ -0,0 +1,196 @@
+use axum::extract::FromRequestParts;
+use http::{HeaderMap, request::Parts};
+use sha2::{Digest, Sha256};
+use std::marker::PhantomData;
+
+use super::error::{Result as WebResult, WebError};
+use crate::config::{AdminKeyConfig, AdminScope};
+use crate::state::AppState;
+
+/// Name under which the legacy `server.admin_api_key` shows up in audit logs.
+const LEGACY_KEY_NAME: &str = "default";
+
+/// Ties an [`AdminAuth`] extractor to the scope its endpoint requires.
+pub trait RequiredScope {
+    const SCOPE: AdminScope;
+}
+
+pub mod scope {
+    use super::{AdminScope, RequiredScope};
+
+    pub struct ContentRefresh;
+    pub struct LobbyManagement;
+    pub struct StreamerManagement;
+    pub struct Metrics;
+
+    impl RequiredScope for ContentRefresh {
+        const SCOPE: AdminScope = AdminScope::ContentRefresh;
+    }
+    impl RequiredScope for LobbyManagement {
+        const SCOPE: AdminScope = AdminScope::LobbyManagement;
+    }
+    impl RequiredScope for StreamerManagement {
+        const SCOPE: AdminScope = AdminScope::StreamerManagement;
+    }
+    impl RequiredScope for Metrics {
+        const SCOPE: AdminScope = AdminScope::Metrics;
+    }
+}
+
+/// Admin caller authenticated with `Authorization: ApiKey <key>` for a key holding
+/// scope `S`. Rejects with 401 for a missing or unknown key and 403 when the key
+/// lacks the scope. Every decision is logged with `audit = true`.
+pub struct AdminAuth<S> {
+    /// Configured name of the key that made the request.
+    pub key_name: String,
+    _scope: PhantomData<S>,
+}
+
+impl<S> FromRequestParts<AppState> for AdminAuth<S>
+where
+    S: RequiredScope + Send + Sync,
+{
+    type Rejection = WebError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> WebResult<Self> {
+        let settings = state.settings.current();
+        let result = authorize(
+            &parts.headers,
+            &settings.server.admin_api_key,
+            &settings.server.admin_keys,
+            S::SCOPE,
+        );
+        match &result {
+            Ok(key_name) => tracing::info!(
+                audit = true,
+                admin.key = %key_name,
+                admin.scope = S::SCOPE.as_str(),
+                http.method = %parts.method,
+                http.path = %parts.uri.path(),
+                "Admin request authorized"
+            ),
+            Err(e) => tracing::warn!(
+                audit = true,
+                admin.scope = S::SCOPE.as_str(),
+                http.method = %parts.method,
+                http.path = %parts.uri.path(),
+                reason = %e,
+                "Admin request rejected"
+            ),
+        }
+        result.map(|key_name| AdminAuth {
+            key_name,
+            _scope: PhantomData,
+        })
+    }
+}
+
+/// Returns the name of the key in the `Authorization` header if it holds `scope`.
+fn authorize(
+    headers: &HeaderMap,
+    admin_api_key: &str,
+    admin_keys: &[AdminKeyConfig],
+    scope: AdminScope,
+) -> WebResult<String> {
+    let header = headers
+        .get(http::header::AUTHORIZATION)
+        .ok_or_else(|| WebError::Unauthorized("Missing Authorization header".to_string()))?;
+    let provided_key = header
+        .to_str()
+        .ok()
+        .and_then(|value| value.strip_prefix("ApiKey "))
+        .map(str::trim)
+        .ok_or_else(|| {
+            WebError::Unauthorized(
+                "Invalid Authorization header format. Expected 'ApiKey <key>'".to_string(),
+            )
+        })?;
+
+    // Compare fixed-length digests without stopping at the first match, so timing
+    // reveals neither the key contents nor which entry matched.
+    let provided_digest = Sha256::digest(provided_key.as_bytes());
+    let candidates = std::iter::once((LEGACY_KEY_NAME, admin_api_key, None)).chain(
+        admin_keys.iter().map(|admin_key| {
+            (
+                admin_key.name.as_str(),
+                admin_key.key.as_str(),
+                Some(&admin_key.scopes),
+            )
+        }),
+    );
+    let mut matched = None;
+    for (name, key, scopes) in candidates {
+        let is_match = digests_equal(&provided_digest, &Sha256::digest(key.as_bytes()));
+        if is_match && !key.is_empty() && matched.is_none() {
+            matched = Some((name, scopes));
+        }
+    }
+
+    let (name, scopes) =
+        matched.ok_or_else(|| WebError::Unauthorized("Invalid API key".to_string()))?;
+    if scopes.is_some_and(|scopes| !scopes.contains(&scope)) {
+        return Err(WebError::Forbidden(format!(
+            "API key '{}' lacks the '{}' scope",
+            name,
+            scope.as_str()
+        )));
+    }
+    Ok(name.to_string())
+}
+
+pub(crate) fn digests_equal(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(http::header::AUTHORIZATION, authorization.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_checks_key_and_scope() {
+        let admin_keys = vec![AdminKeyConfig {
+            name: "ci".to_string(),
+            key: "ci-key".to_string(),
+            scopes: HashSet::from([AdminScope::ContentRefresh]),
+        }];
+        let check = |authorization: &str, scope| {
+            authorize(&headers(authorization), "legacy-key", &admin_keys, scope)
+        };
+
+        assert_eq!(
+            check("ApiKey ci-key", AdminScope::ContentRefresh).unwrap(),
+            "ci"
+        );
+        assert!(matches!(
+            check("ApiKey ci-key", AdminScope::LobbyManagement),
+            Err(WebError::Forbidden(_))
+        ));
+        assert_eq!(
+            check("ApiKey legacy-key", AdminScope::Metrics).unwrap(),
+            LEGACY_KEY_NAME
+        );
+        assert!(matches!(
+            check("ApiKey wrong", AdminScope::ContentRefresh),
+            Err(WebError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            check("Bearer ci-key", AdminScope::ContentRefresh),
+            Err(WebError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_empty_legacy_key_never_matches() {
+        assert!(matches!(
+            authorize(&headers("ApiKey "), "", &[], AdminScope::Metrics),
+            Err(WebError::Unauthorized(_))
+        ));
+    }
+}
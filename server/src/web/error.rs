@@ -3,6 +3,9 @@ use axum::response::{IntoResponse, Json, Response};
 use serde_json::json;
 use thiserror::Error;
 
+use crate::error::{PresetError, StreamerError};
+use crate::lobby::{LobbyCommandError, LobbyCreationError};
+
 #[derive(Debug, Error)]
 pub enum WebError {
     #[error("Internal server error: {0}")]
@@ -11,21 +14,108 @@ pub enum WebError {
     JsonSerialization(#[from] serde_json::Error),
     #[error("Unauthorized: {0}")] // New error
     Unauthorized(String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Lobby creation failed: {0}")]
+    LobbyCreation(#[from] LobbyCreationError),
+    #[error("Preset error: {0}")]
+    Preset(#[from] PresetError),
+    #[error("Streamer error: {0}")]
+    Streamer(#[from] StreamerError),
+    #[error("Lobby command rejected: {0}")]
+    LobbyCommand(#[from] LobbyCommandError),
+}
+
+impl WebError {
+    fn status_and_code(&self) -> (StatusCode, &'static str) {
+        match self {
+            WebError::InternalServerError(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "internal_error")
+            }
+            WebError::JsonSerialization(_) => (StatusCode::INTERNAL_SERVER_ERROR, "json_error"),
+            WebError::Unauthorized(_) => (StatusCode::UNAUTHORIZED, "unauthorized"),
+            WebError::Forbidden(_) => (StatusCode::FORBIDDEN, "forbidden"),
+            WebError::NotFound(_) => (StatusCode::NOT_FOUND, "not_found"),
+            WebError::LobbyCreation(err) => {
+                let status = match err {
+                    LobbyCreationError::ChannelNotAllowed { .. }
+                    | LobbyCreationError::GameNotAllowedForStreamer { .. }
+                    | LobbyCreationError::GameTypeDisabled { .. }
+                    | LobbyCreationError::ObsDisabled
+                    | LobbyCreationError::LobbyWebhooksDisabled => StatusCode::FORBIDDEN,
+                    LobbyCreationError::UnknownGameType { .. }
+                    | LobbyCreationError::InvalidSettings { .. }
+                    | LobbyCreationError::InvalidSchedule { .. }
+                    | LobbyCreationError::InvalidObsConfig { .. }
+                    | LobbyCreationError::InvalidWebhook { .. } => StatusCode::BAD_REQUEST,
+                    LobbyCreationError::MissingYouTubeConfig => StatusCode::UNPROCESSABLE_ENTITY,
+                    LobbyCreationError::InvalidStreamerApiKey { .. }
+                    | LobbyCreationError::TwitchLoginRequired { .. } => StatusCode::UNAUTHORIZED,
+                    LobbyCreationError::QuotaExceeded { .. }
+                    | LobbyCreationError::StreamerQuotaExceeded { .. } => {
+                        StatusCode::TOO_MANY_REQUESTS
+                    }
+                    LobbyCreationError::PresetNotFound { .. } => StatusCode::NOT_FOUND,
+                };
+                (status, err.code())
+            }
+            WebError::LobbyCommand(err) => {
+                let status = match err {
+                    LobbyCommandError::InvalidToken => StatusCode::UNAUTHORIZED,
+                    LobbyCommandError::PermissionDenied { .. } => StatusCode::FORBIDDEN,
+                    LobbyCommandError::WrongGameType { .. }
+                    | LobbyCommandError::InvalidCommand(_)
+                    | LobbyCommandError::UnsupportedMessage => StatusCode::BAD_REQUEST,
+                    LobbyCommandError::Rejected(_) => StatusCode::CONFLICT,
+                    LobbyCommandError::LobbyUnavailable => StatusCode::GONE,
+                };
+                (status, err.code())
+            }
+            WebError::Preset(err) => match err {
+                PresetError::InvalidName(_) => (StatusCode::BAD_REQUEST, "invalid_preset_name"),
+                PresetError::NotFound(_) => (StatusCode::NOT_FOUND, "preset_not_found"),
+                PresetError::FileRead { .. }
+                | PresetError::FileWrite { .. }
+                | PresetError::Parse(_) => {
+                    (StatusCode::INTERNAL_SERVER_ERROR, "preset_storage_error")
+                }
+            },
+            WebError::Streamer(err) => match err {
+                StreamerError::InvalidChannel(_) => (StatusCode::BAD_REQUEST, "invalid_channel"),
+                StreamerError::UnknownContentPack(_) => {
+                    (StatusCode::BAD_REQUEST, "unknown_content_pack")
+                }
+                StreamerError::NotFound(_) => (StatusCode::NOT_FOUND, "streamer_not_found"),
+                StreamerError::FileRead { .. }
+                | StreamerError::FileWrite { .. }
+                | StreamerError::Parse(_) => {
+                    (StatusCode::INTERNAL_SERVER_ERROR, "streamer_storage_error")
+                }
+            },
+        }
+    }
 }
 
 impl IntoResponse for WebError {
     fn into_response(self) -> Response {
-        let (status, error_message) = match &self {
-            WebError::InternalServerError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
-            WebError::JsonSerialization(err) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("JSON error: {}", err),
-            ),
-            WebError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg.clone()), // New mapping
+        let (status, code) = self.status_and_code();
+        let error_message = match &self {
+            WebError::InternalServerError(msg) => msg.clone(),
+            WebError::JsonSerialization(err) => format!("JSON error: {}", err),
+            WebError::Unauthorized(msg) => msg.clone(), // New mapping
+            WebError::Forbidden(msg) => msg.clone(),
+            WebError::NotFound(msg) => msg.clone(),
+            WebError::LobbyCreation(err) => err.to_string(),
+            WebError::Preset(err) => err.to_string(),
+            WebError::Streamer(err) => err.to_string(),
+            WebError::LobbyCommand(err) => err.to_string(),
         };
 
         let body = Json(json!({
             "error": error_message,
+            "code": code,
             "status": status.as_u16()
         }));
 
@@ -34,3 +124,112 @@ impl IntoResponse for WebError {
 }
 
 pub type Result<T, E = WebError> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lobby_creation_errors_map_to_client_errors() {
+        let cases = [
+            (
+                LobbyCreationError::ChannelNotAllowed {
+                    channel: "somechannel".to_string(),
+                },
+                StatusCode::FORBIDDEN,
+                "channel_not_allowed",
+            ),
+            (
+                LobbyCreationError::GameTypeDisabled {
+                    game_type: "quiz".to_string(),
+                },
+                StatusCode::FORBIDDEN,
+                "game_type_disabled",
+            ),
+            (
+                LobbyCreationError::UnknownGameType {
+                    requested: "chess".to_string(),
+                },
+                StatusCode::BAD_REQUEST,
+                "unknown_game_type",
+            ),
+            (
+                LobbyCreationError::MissingYouTubeConfig,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "youtube_not_configured",
+            ),
+            (
+                LobbyCreationError::TwitchLoginRequired {
+                    channel: "somechannel".to_string(),
+                },
+                StatusCode::UNAUTHORIZED,
+                "twitch_login_required",
+            ),
+            (
+                LobbyCreationError::QuotaExceeded { limit: 5 },
+                StatusCode::TOO_MANY_REQUESTS,
+                "lobby_quota_exceeded",
+            ),
+            (
+                LobbyCreationError::StreamerQuotaExceeded {
+                    channel: "somechannel".to_string(),
+                    limit: 2,
+                },
+                StatusCode::TOO_MANY_REQUESTS,
+                "streamer_quota_exceeded",
+            ),
+            (
+                LobbyCreationError::PresetNotFound {
+                    name: "weekly".to_string(),
+                },
+                StatusCode::NOT_FOUND,
+                "preset_not_found",
+            ),
+        ];
+
+        for (err, expected_status, expected_code) in cases {
+            let (status, code) = WebError::from(err).status_and_code();
+            assert_eq!(status, expected_status);
+            assert_eq!(code, expected_code);
+        }
+    }
+
+    #[test]
+    fn test_lobby_command_errors_map_to_client_errors() {
+        let cases = [
+            (
+                LobbyCommandError::InvalidToken,
+                StatusCode::UNAUTHORIZED,
+                "invalid_lobby_token",
+            ),
+            (
+                LobbyCommandError::PermissionDenied {
+                    permission: crate::cohost::HostPermission::ResetGame,
+                },
+                StatusCode::FORBIDDEN,
+                "permission_denied",
+            ),
+            (
+                LobbyCommandError::InvalidCommand("unknown variant".to_string()),
+                StatusCode::BAD_REQUEST,
+                "invalid_command",
+            ),
+            (
+                LobbyCommandError::Rejected("The game has already started".to_string()),
+                StatusCode::CONFLICT,
+                "command_rejected",
+            ),
+            (
+                LobbyCommandError::LobbyUnavailable,
+                StatusCode::GONE,
+                "lobby_unavailable",
+            ),
+        ];
+
+        for (err, expected_status, expected_code) in cases {
+            let (status, code) = WebError::from(err).status_and_code();
+            assert_eq!(status, expected_status);
+            assert_eq!(code, expected_code);
+        }
+    }
+}
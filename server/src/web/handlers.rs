@@ -1,18 +1,37 @@
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
     http::{HeaderMap, StatusCode},
     response::Json,
 };
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+use super::admin_auth::{AdminAuth, scope};
 use super::error::{Result as WebResult, WebError};
-use crate::lobby::LobbyDetails;
+use crate::game_logic::{ClientToServerMessage, GameType};
+use crate::lobby::{
+    LobbyCreationError, LobbyCreationOptions, LobbyDetails, LobbyStateSnapshot, ScheduledStart,
+};
+use crate::obs::ObsLobbyConfig;
+use crate::presets::{LobbyPreset, validate_preset_name};
 use crate::state::AppState;
+use crate::streamers::{StreamerAccount, StreamerSummary};
+use crate::webhooks::WebhookTarget;
 
 #[derive(Deserialize, Debug, Default)]
 pub struct CreateLobbyRequest {
     pub game_type: Option<String>,
     pub twitch_channel: Option<String>,
+    /// Game-specific initial settings, validated by the game before the lobby starts.
+    pub settings: Option<serde_json::Value>,
+    /// Optional automatic start, either `{"at": <unix seconds>}` or `{"in_minutes": <n>}`.
+    pub scheduled_start: Option<ScheduledStart>,
+    /// Optional OBS WebSocket integration driven by game phase changes.
+    pub obs: Option<ObsLobbyConfig>,
+    /// Webhook targets for this lobby, in addition to the server-wide ones.
+    pub webhooks: Option<Vec<WebhookTarget>>,
+    /// Name of a saved preset. Any field set explicitly in the request overrides the preset.
+    pub preset: Option<String>,
 }
 
 #[derive(Serialize, Debug)]
@@ -20,24 +39,143 @@ pub struct AllowedChannelsResponse {
     pub channels: Vec<String>,
 }
 
-#[tracing::instrument(skip(app_state), fields(
+#[derive(Deserialize, Debug, Default)]
+pub struct ListPresetsQuery {
+    pub twitch_channel: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PresetsResponse {
+    pub presets: Vec<LobbyPreset>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct StreamersResponse {
+    pub streamers: Vec<StreamerSummary>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct StreamerApiKeyResponse {
+    pub channel: String,
+    /// Shown only once; the server keeps just a hash.
+    pub api_key: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AdminMetricsResponse {
+    pub active_lobbies: usize,
+    pub max_active_lobbies: Option<usize>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CommandAckResponse {
+    pub lobby_id: Uuid,
+    pub accepted: bool,
+}
+
+/// Extracts the lobby token from an `Authorization: Bearer <admin_id or co-host token>` header.
+fn lobby_bearer_token(headers: &HeaderMap) -> WebResult<Uuid> {
+    let header = headers
+        .get(http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| WebError::Unauthorized("Missing Authorization header".to_string()))?;
+    header
+        .strip_prefix("Bearer ")
+        .and_then(|token| Uuid::parse_str(token.trim()).ok())
+        .ok_or_else(|| {
+            WebError::Unauthorized(
+                "Invalid Authorization header format. Expected 'Bearer <lobby token>'".to_string(),
+            )
+        })
+}
+
+/// Reads the optional `Authorization: ApiKey <key>` a streamer sends with
+/// create-lobby requests for a channel that has an API key.
+fn streamer_api_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("ApiKey "))
+        .map(str::trim)
+}
+
+/// With Twitch login enabled, a lobby may only be bound to a channel by its
+/// broadcaster: either logged in as that channel, or holding its streamer API key.
+async fn verify_channel_owner(
+    app_state: &AppState,
+    headers: &HeaderMap,
+    channel: &str,
+) -> WebResult<()> {
+    if !app_state.settings.current().twitch.login.enabled {
+        return Ok(());
+    }
+    let logged_in_as_channel = app_state
+        .login_sessions
+        .current_user(headers)
+        .is_some_and(|user| user.login.eq_ignore_ascii_case(channel.trim()));
+    if logged_in_as_channel {
+        return Ok(());
+    }
+    if let Some(api_key) = streamer_api_key(headers)
+        && app_state
+            .streamer_store
+            .verify_api_key(channel, api_key)
+            .await
+    {
+        return Ok(());
+    }
+    Err(WebError::LobbyCreation(
+        LobbyCreationError::TwitchLoginRequired {
+            channel: channel.to_string(),
+        },
+    ))
+}
+
+#[tracing::instrument(skip(app_state, headers), fields(
     http.method = "POST",
     http.path = "/api/create-lobby",
     request.game_type = ?payload.game_type,
-    request.twitch_channel = ?payload.twitch_channel
+    request.twitch_channel = ?payload.twitch_channel,
+    request.preset = ?payload.preset
 ))]
 pub async fn create_lobby_handler(
     State(app_state): State<AppState>,
+    headers: HeaderMap,
     Json(payload): Json<CreateLobbyRequest>,
 ) -> WebResult<Json<LobbyDetails>> {
     tracing::debug!("Processing create lobby request");
 
+    let mut options = LobbyCreationOptions {
+        game_type: payload.game_type,
+        twitch_channel: payload.twitch_channel,
+        settings: payload.settings,
+        scheduled_start: payload.scheduled_start,
+        obs: payload.obs,
+        webhooks: payload.webhooks,
+    };
+
+    if let Some(preset_name) = payload.preset {
+        let preset = app_state
+            .preset_store
+            .get(&preset_name)
+            .await
+            .ok_or_else(|| {
+                tracing::warn!(preset.name = %preset_name, "Requested lobby preset not found");
+                WebError::LobbyCreation(LobbyCreationError::PresetNotFound { name: preset_name })
+            })?;
+        options = preset.merged_with(options);
+    }
+
+    if let Some(channel) = options.twitch_channel.as_deref() {
+        verify_channel_owner(&app_state, &headers, channel).await?;
+    }
+
     let details = app_state
-        .create_lobby(payload.game_type, payload.twitch_channel)
+        .create_lobby(options, streamer_api_key(&headers))
         .await
         .map_err(|e| {
-            tracing::error!(error = %e, "Failed to create lobby");
-            WebError::InternalServerError(e)
+            tracing::warn!(error = %e, error.code = e.code(), "Failed to create lobby");
+            WebError::LobbyCreation(e)
         })?;
 
     tracing::info!(
@@ -50,51 +188,17 @@ pub async fn create_lobby_handler(
     Ok(Json(details))
 }
 
-#[tracing::instrument(skip(app_state, headers), fields(
+#[tracing::instrument(skip(app_state, admin), fields(
     http.method = "GET",
-    http.path = "/api/refresh-words"
+    http.path = "/api/refresh-words",
+    admin.key = %admin.key_name
 ))]
 pub async fn refresh_words_handler(
     State(app_state): State<AppState>,
-    headers: HeaderMap,
+    admin: AdminAuth<scope::ContentRefresh>,
 ) -> WebResult<StatusCode> {
     tracing::debug!("Processing refresh words request");
 
-    let expected_key = &app_state.server_config.admin_api_key;
-
-    match headers.get(http::header::AUTHORIZATION) {
-        Some(auth_header_val) => {
-            let auth_header_str = auth_header_val.to_str().unwrap_or("");
-            if let Some(provided_key) = auth_header_str.strip_prefix("ApiKey ") {
-                if provided_key.trim() != expected_key.as_str() {
-                    tracing::warn!(
-                        reason = "invalid_api_key",
-                        "Unauthorized attempt to refresh words"
-                    );
-                    return Err(WebError::Unauthorized("Invalid API key".to_string()));
-                }
-                tracing::debug!("Admin API key validated successfully");
-            } else {
-                tracing::warn!(
-                    reason = "invalid_auth_format",
-                    "Unauthorized attempt to refresh words: Authorization header format incorrect"
-                );
-                return Err(WebError::Unauthorized(
-                    "Invalid Authorization header format. Expected 'ApiKey <key>'".to_string(),
-                ));
-            }
-        }
-        None => {
-            tracing::warn!(
-                reason = "missing_auth_header",
-                "Unauthorized attempt to refresh words"
-            );
-            return Err(WebError::Unauthorized(
-                "Missing Authorization header".to_string(),
-            ));
-        }
-    }
-
     app_state
         .game_content_cache
         .refresh_all_content()
@@ -108,6 +212,21 @@ pub async fn refresh_words_handler(
     Ok(StatusCode::OK)
 }
 
+#[tracing::instrument(skip(app_state, admin), fields(
+    http.method = "GET",
+    http.path = "/api/admin/metrics",
+    admin.key = %admin.key_name
+))]
+pub async fn admin_metrics_handler(
+    State(app_state): State<AppState>,
+    admin: AdminAuth<scope::Metrics>,
+) -> WebResult<Json<AdminMetricsResponse>> {
+    Ok(Json(AdminMetricsResponse {
+        active_lobbies: app_state.active_lobbies.len(),
+        max_active_lobbies: app_state.settings.current().server.max_active_lobbies,
+    }))
+}
+
 #[tracing::instrument(skip(app_state), fields(
     http.method = "GET",
     http.path = "/api/allowed-channels"
@@ -117,13 +236,7 @@ pub async fn get_allowed_channels_handler(
 ) -> WebResult<Json<AllowedChannelsResponse>> {
     tracing::debug!("Processing get allowed channels request");
 
-    let channels: Vec<String> = app_state
-        .game_content_cache
-        .twitch_whitelist()
-        .await
-        .iter()
-        .cloned()
-        .collect();
+    let channels = app_state.streamer_store.enabled_channels().await;
 
     tracing::debug!(
         channels.count = channels.len(),
@@ -132,3 +245,206 @@ pub async fn get_allowed_channels_handler(
 
     Ok(Json(AllowedChannelsResponse { channels }))
 }
+
+#[tracing::instrument(skip(app_state, admin), fields(
+    http.method = "GET",
+    http.path = "/api/presets",
+    admin.key = %admin.key_name
+))]
+pub async fn list_presets_handler(
+    State(app_state): State<AppState>,
+    admin: AdminAuth<scope::LobbyManagement>,
+    Query(query): Query<ListPresetsQuery>,
+) -> WebResult<Json<PresetsResponse>> {
+    let presets: Vec<LobbyPreset> = app_state
+        .preset_store
+        .list(query.twitch_channel.as_deref())
+        .await
+        .iter()
+        .map(LobbyPreset::redacted)
+        .collect();
+
+    tracing::debug!(presets.count = presets.len(), "Retrieved lobby presets");
+
+    Ok(Json(PresetsResponse { presets }))
+}
+
+#[tracing::instrument(skip(app_state, admin, options), fields(
+    http.method = "PUT",
+    http.path = "/api/presets/{name}",
+    admin.key = %admin.key_name,
+    preset.game_type = ?options.game_type,
+    preset.twitch_channel = ?options.twitch_channel
+))]
+pub async fn upsert_preset_handler(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+    admin: AdminAuth<scope::LobbyManagement>,
+    Json(options): Json<LobbyCreationOptions>,
+) -> WebResult<Json<LobbyPreset>> {
+    validate_preset_name(&name)?;
+    app_state.validate_creation_options(&options).await?;
+
+    let preset = LobbyPreset { name, options };
+    app_state.preset_store.upsert(preset.clone()).await?;
+
+    tracing::info!(preset.name = %preset.name, "Lobby preset saved");
+    Ok(Json(preset.redacted()))
+}
+
+#[tracing::instrument(skip(app_state, admin), fields(
+    http.method = "DELETE",
+    http.path = "/api/presets/{name}",
+    admin.key = %admin.key_name
+))]
+pub async fn delete_preset_handler(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+    admin: AdminAuth<scope::LobbyManagement>,
+) -> WebResult<StatusCode> {
+    app_state.preset_store.remove(&name).await?;
+
+    tracing::info!(preset.name = %name, "Lobby preset deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(skip(app_state, admin), fields(
+    http.method = "GET",
+    http.path = "/api/streamers",
+    admin.key = %admin.key_name
+))]
+pub async fn list_streamers_handler(
+    State(app_state): State<AppState>,
+    admin: AdminAuth<scope::StreamerManagement>,
+) -> WebResult<Json<StreamersResponse>> {
+    let streamers = app_state.streamer_store.list().await;
+    tracing::debug!(
+        streamers.count = streamers.len(),
+        "Retrieved streamer accounts"
+    );
+    Ok(Json(StreamersResponse { streamers }))
+}
+
+#[tracing::instrument(skip(app_state, admin, account), fields(
+    http.method = "PUT",
+    http.path = "/api/streamers/{channel}",
+    admin.key = %admin.key_name,
+    streamer.enabled = account.enabled
+))]
+pub async fn upsert_streamer_handler(
+    State(app_state): State<AppState>,
+    Path(channel): Path<String>,
+    admin: AdminAuth<scope::StreamerManagement>,
+    Json(mut account): Json<StreamerAccount>,
+) -> WebResult<Json<StreamerSummary>> {
+    for game_type in &account.enabled_games {
+        if GameType::from_alias(game_type).is_none() {
+            return Err(WebError::LobbyCreation(
+                LobbyCreationError::UnknownGameType {
+                    requested: game_type.clone(),
+                },
+            ));
+        }
+    }
+    account.enabled_games = account
+        .enabled_games
+        .iter()
+        .filter_map(|game_type| GameType::from_alias(game_type))
+        .map(|game_type| game_type.primary_id().to_string())
+        .collect();
+    account.channel = channel;
+
+    let summary = app_state.streamer_store.upsert(account).await?;
+
+    tracing::info!(
+        twitch.channel = %summary.account.channel,
+        "Streamer account saved"
+    );
+    Ok(Json(summary))
+}
+
+#[tracing::instrument(skip(app_state, admin), fields(
+    http.method = "DELETE",
+    http.path = "/api/streamers/{channel}",
+    admin.key = %admin.key_name
+))]
+pub async fn delete_streamer_handler(
+    State(app_state): State<AppState>,
+    Path(channel): Path<String>,
+    admin: AdminAuth<scope::StreamerManagement>,
+) -> WebResult<StatusCode> {
+    app_state.streamer_store.remove(&channel).await?;
+
+    tracing::info!(twitch.channel = %channel, "Streamer account deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[tracing::instrument(skip(app_state, admin), fields(
+    http.method = "POST",
+    http.path = "/api/streamers/{channel}/api-key",
+    admin.key = %admin.key_name
+))]
+pub async fn issue_streamer_api_key_handler(
+    State(app_state): State<AppState>,
+    Path(channel): Path<String>,
+    admin: AdminAuth<scope::StreamerManagement>,
+) -> WebResult<Json<StreamerApiKeyResponse>> {
+    let api_key = app_state.streamer_store.issue_api_key(&channel).await?;
+
+    tracing::info!(twitch.channel = %channel, "Issued streamer API key");
+    Ok(Json(StreamerApiKeyResponse {
+        channel: channel.to_lowercase(),
+        api_key,
+    }))
+}
+
+#[tracing::instrument(skip(app_state, headers, message), fields(
+    http.method = "POST",
+    http.path = "/api/lobby/{id}/command",
+    lobby.id = %lobby_id
+))]
+pub async fn lobby_command_handler(
+    State(app_state): State<AppState>,
+    Path(lobby_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(message): Json<ClientToServerMessage>,
+) -> WebResult<Json<CommandAckResponse>> {
+    let admin_token = lobby_bearer_token(&headers)?;
+    let lobby_handle = app_state
+        .get_lobby_handle(lobby_id)
+        .ok_or_else(|| WebError::NotFound(format!("Lobby {} not found.", lobby_id)))?;
+
+    lobby_handle.execute_command(admin_token, message).await?;
+
+    tracing::debug!("Lobby command accepted");
+    Ok(Json(CommandAckResponse {
+        lobby_id,
+        accepted: true,
+    }))
+}
+
+#[tracing::instrument(skip(app_state, headers), fields(
+    http.method = "GET",
+    http.path = "/api/lobby/{id}/state",
+    lobby.id = %lobby_id
+))]
+pub async fn lobby_state_handler(
+    State(app_state): State<AppState>,
+    Path(lobby_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> WebResult<Json<LobbyStateSnapshot>> {
+    // Anonymous callers get the public view; a token unlocks host-only fields.
+    let admin_token = if headers.contains_key(http::header::AUTHORIZATION) {
+        Some(lobby_bearer_token(&headers)?)
+    } else {
+        None
+    };
+    let lobby_handle = app_state
+        .get_lobby_handle(lobby_id)
+        .ok_or_else(|| WebError::NotFound(format!("Lobby {} not found.", lobby_id)))?;
+
+    let snapshot = lobby_handle.state_snapshot(admin_token).await?;
+
+    tracing::debug!(state.redacted = snapshot.redacted, "Served lobby state");
+    Ok(Json(snapshot))
+}
@@ -1,11 +1,22 @@
 use axum::{
     Router,
-    routing::{any, get, post},
+    extract::Request,
+    response::Response,
+    routing::{any, get, post, put},
 };
+use governor::middleware::NoOpMiddleware;
 use http::HeaderValue;
-use std::{net::SocketAddr, sync::Arc};
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex, RwLock},
+};
 use tokio::time::Duration as TokioDuration;
-use tower_governor::{GovernorLayer, governor::GovernorConfigBuilder};
+use tower::ServiceExt;
+use tower_governor::{
+    GovernorLayer,
+    governor::{GovernorConfig, GovernorConfigBuilder},
+    key_extractor::PeerIpKeyExtractor,
+};
 use tower_http::compression::CompressionLevel;
 use tower_http::{compression::CompressionLayer, cors::CorsLayer, trace::TraceLayer};
 
@@ -13,17 +24,113 @@ use crate::config::ServerConfig;
 use crate::error::Result as AppResult;
 use crate::state::AppState;
 
+pub mod admin_auth;
 pub mod error;
 pub mod handlers;
+pub mod sse;
+pub mod static_files;
+pub mod tls;
+pub mod twitch_login;
 pub mod ws;
 
 pub use self::error::WebError;
 
-#[tracing::instrument(skip(app_state, server_config), fields(
-    server.port = server_config.port,
+type IpRateLimitConfig = GovernorConfig<PeerIpKeyExtractor, NoOpMiddleware>;
+
+/// A per-IP limiter and the quota it was built with.
+#[derive(Clone)]
+struct IpRateLimit {
+    per_millisecond: u64,
+    burst_size: u32,
+    config: Arc<IpRateLimitConfig>,
+}
+
+/// The router's per-IP limiters. They outlive a rebuilt router, so reloading the
+/// configuration only starts a limiter's counts over when its own quota changed.
+#[derive(Clone)]
+struct RateLimiters {
+    global: IpRateLimit,
+    create_lobby: IpRateLimit,
+}
+
+impl RateLimiters {
+    fn for_config(server_config: &ServerConfig, previous: Option<&RateLimiters>) -> Self {
+        let rate_limit = &server_config.rate_limit;
+        Self {
+            global: ip_rate_limit(
+                "global",
+                rate_limit.per_millisecond,
+                rate_limit.burst_size,
+                previous.map(|limiters| &limiters.global),
+            ),
+            create_lobby: ip_rate_limit(
+                "create_lobby",
+                rate_limit.create_lobby.per_millisecond,
+                rate_limit.create_lobby.burst_size,
+                previous.map(|limiters| &limiters.create_lobby),
+            ),
+        }
+    }
+}
+
+/// The router currently serving requests. Reloading the configuration swaps in a
+/// freshly built router, so CORS and rate-limit changes apply without restarting
+/// the listener; requests already in flight finish on the old one.
+#[derive(Clone)]
+pub struct RouterHandle {
+    current: Arc<RwLock<Router>>,
+    rate_limiters: Arc<Mutex<RateLimiters>>,
+}
+
+impl RouterHandle {
+    pub fn new(app_state: AppState, server_config: &ServerConfig) -> Self {
+        let rate_limiters = RateLimiters::for_config(server_config, None);
+        let router = build_router(app_state, server_config, &rate_limiters);
+        Self {
+            current: Arc::new(RwLock::new(router)),
+            rate_limiters: Arc::new(Mutex::new(rate_limiters)),
+        }
+    }
+
+    /// Builds a new router for `server_config`, keeping every rate limiter whose
+    /// quota is unchanged.
+    pub fn rebuild(&self, app_state: AppState, server_config: &ServerConfig) {
+        let mut rate_limiters = match self.rate_limiters.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *rate_limiters = RateLimiters::for_config(server_config, Some(&rate_limiters));
+        let router = build_router(app_state, server_config, &rate_limiters);
+        match self.current.write() {
+            Ok(mut guard) => *guard = router,
+            Err(poisoned) => *poisoned.into_inner() = router,
+        }
+    }
+
+    fn current(&self) -> Router {
+        match self.current.read() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    async fn route(self, request: Request) -> Response {
+        let router = self.current();
+        router
+            .oneshot(request)
+            .await
+            .unwrap_or_else(|never| match never {})
+    }
+}
+
+#[tracing::instrument(skip(app_state, server_config, rate_limiters), fields(
     cors.origins.count = server_config.cors_origins.len()
 ))]
-pub async fn run_server(app_state: AppState, server_config: ServerConfig) -> AppResult<()> {
+fn build_router(
+    app_state: AppState,
+    server_config: &ServerConfig,
+    rate_limiters: &RateLimiters,
+) -> Router {
     let cors_origins_result: Result<Vec<HeaderValue>, _> = server_config
         .cors_origins
         .iter()
@@ -45,7 +152,12 @@ pub async fn run_server(app_state: AppState, server_config: ServerConfig) -> App
             "CORS configured with allowed origins"
         );
         CorsLayer::new()
-            .allow_methods(vec![http::Method::GET, http::Method::POST])
+            .allow_methods(vec![
+                http::Method::GET,
+                http::Method::POST,
+                http::Method::PUT,
+                http::Method::DELETE,
+            ])
             .allow_origin(cors_origins)
             .allow_credentials(true)
             .allow_headers(vec![
@@ -58,27 +170,113 @@ pub async fn run_server(app_state: AppState, server_config: ServerConfig) -> App
         CorsLayer::new()
     };
 
+    let mut app = Router::new()
+        .route(
+            "/api/create-lobby",
+            post(handlers::create_lobby_handler).layer(GovernorLayer {
+                config: Arc::clone(&rate_limiters.create_lobby.config),
+            }),
+        )
+        .route("/api/refresh-words", get(handlers::refresh_words_handler))
+        .route("/api/admin/metrics", get(handlers::admin_metrics_handler))
+        .route(
+            "/api/allowed-channels",
+            get(handlers::get_allowed_channels_handler),
+        )
+        .route("/api/presets", get(handlers::list_presets_handler))
+        .route(
+            "/api/auth/twitch/login",
+            get(twitch_login::twitch_login_handler),
+        )
+        .route(
+            "/api/auth/twitch/callback",
+            get(twitch_login::twitch_callback_handler),
+        )
+        .route("/api/auth/me", get(twitch_login::current_user_handler))
+        .route("/api/auth/logout", post(twitch_login::logout_handler))
+        .route("/api/streamers", get(handlers::list_streamers_handler))
+        .route(
+            "/api/streamers/{channel}",
+            put(handlers::upsert_streamer_handler).delete(handlers::delete_streamer_handler),
+        )
+        .route(
+            "/api/streamers/{channel}/api-key",
+            post(handlers::issue_streamer_api_key_handler),
+        )
+        .route(
+            "/api/presets/{name}",
+            put(handlers::upsert_preset_handler).delete(handlers::delete_preset_handler),
+        )
+        .route("/api/lobby/{id}/events", get(sse::lobby_events_handler))
+        .route("/api/lobby/{id}/state", get(handlers::lobby_state_handler))
+        .route(
+            "/api/lobby/{id}/command",
+            post(handlers::lobby_command_handler),
+        )
+        .route("/ws", any(ws::ws_handler))
+        // A route layer leaves the static fallback below out of the per-IP budget,
+        // so loading the frontend's assets cannot exhaust it.
+        .route_layer(GovernorLayer {
+            config: Arc::clone(&rate_limiters.global.config),
+        })
+        .with_state(app_state);
+
+    if let Some(static_dir) = server_config.static_dir.as_deref() {
+        tracing::info!(static_dir = %static_dir, "Serving frontend from static directory");
+        app = app.fallback_service(static_files::static_router(static_dir));
+    }
+
+    app.layer(TraceLayer::new_for_http())
+        .layer(
+            CompressionLayer::new()
+                .quality(CompressionLevel::Default)
+                .gzip(true),
+        )
+        .layer(cors)
+}
+
+/// Builds a per-IP limiter and a task that prunes its storage, or reuses
+/// `previous` when it was built with the same quota.
+fn ip_rate_limit(
+    name: &'static str,
+    per_millisecond: u64,
+    burst_size: u32,
+    previous: Option<&IpRateLimit>,
+) -> IpRateLimit {
+    if let Some(previous) = previous
+        && previous.per_millisecond == per_millisecond
+        && previous.burst_size == burst_size
+    {
+        return previous.clone();
+    }
+
     let governor_conf = Arc::new(
         GovernorConfigBuilder::default()
-            .per_millisecond(500)
-            .burst_size(30)
+            .per_millisecond(per_millisecond)
+            .burst_size(burst_size)
             .finish()
-            .unwrap(),
+            .expect("rate limit is validated by load_settings to be non-zero"),
     );
     tracing::info!(
-        rate_limit.per_ms = 500,
-        rate_limit.burst_size = 30,
+        rate_limit.name = name,
+        rate_limit.per_ms = per_millisecond,
+        rate_limit.burst_size = burst_size,
         "Rate limiter configured"
     );
 
-    let governor_limiter = governor_conf.limiter().clone();
+    // Only hold the limiter weakly so the task ends once a reload replaces it.
+    let governor_limiter = Arc::downgrade(governor_conf.limiter());
 
     tokio::spawn(async move {
         loop {
             tokio::time::sleep(TokioDuration::from_secs(60)).await;
+            let Some(governor_limiter) = governor_limiter.upgrade() else {
+                break;
+            };
             let limiter_size = governor_limiter.len();
             if limiter_size > 1_000_000 {
                 tracing::warn!(
+                    rate_limit.name = name,
                     rate_limiter.storage_size = limiter_size,
                     "Rate limiting storage size is large"
                 );
@@ -87,33 +285,114 @@ pub async fn run_server(app_state: AppState, server_config: ServerConfig) -> App
         }
     });
 
-    let app = Router::new()
-        .route("/api/create-lobby", post(handlers::create_lobby_handler))
-        .route("/api/refresh-words", get(handlers::refresh_words_handler))
-        .route(
-            "/api/allowed-channels",
-            get(handlers::get_allowed_channels_handler),
-        )
-        .route("/ws", any(ws::ws_handler))
-        .with_state(app_state)
-        .layer(TraceLayer::new_for_http())
-        .layer(
-            CompressionLayer::new()
-                .quality(CompressionLevel::Default)
-                .gzip(true),
-        )
-        .layer(GovernorLayer {
-            config: governor_conf,
-        })
-        .layer(cors);
+    IpRateLimit {
+        per_millisecond,
+        burst_size,
+        config: governor_conf,
+    }
+}
+
+#[tracing::instrument(skip(router, server_config), fields(server.port = server_config.port))]
+pub async fn run_server(router: RouterHandle, server_config: ServerConfig) -> AppResult<()> {
+    let app = Router::new().fallback(move |request: Request| router.clone().route(request));
 
     let addr = SocketAddr::from(([0, 0, 0, 0], server_config.port));
-    tracing::info!(server.address = %addr, "HTTP server starting");
-
-    axum::serve(
-        tokio::net::TcpListener::bind(addr).await?,
-        app.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .await
-    .map_err(Into::into)
+    let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+
+    match server_config.tls {
+        Some(tls_config) => {
+            let rustls_config = tls::load_rustls_config(&tls_config).await?;
+            tls::spawn_certificate_watcher(rustls_config.clone(), tls_config);
+            tracing::info!(server.address = %addr, "HTTPS server starting");
+
+            axum_server::bind_rustls(addr, rustls_config)
+                .serve(make_service)
+                .await
+                .map_err(Into::into)
+        }
+        None => {
+            tracing::info!(server.address = %addr, "HTTP server starting");
+
+            axum::serve(tokio::net::TcpListener::bind(addr).await?, make_service)
+                .await
+                .map_err(Into::into)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{RateLimitConfig, WebSocketConfig};
+    use axum::body::Body;
+    use http::StatusCode;
+
+    fn server_config(rate_limit: RateLimitConfig) -> ServerConfig {
+        ServerConfig {
+            port: 3000,
+            cors_origins: Vec::new(),
+            admin_api_key: "test_key".to_string(),
+            admin_keys: Vec::new(),
+            max_active_lobbies: None,
+            static_dir: None,
+            rate_limit,
+            websocket: WebSocketConfig::default(),
+            tls: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reloads_keep_limiters_whose_quota_is_unchanged() {
+        let initial = RateLimiters::for_config(&server_config(RateLimitConfig::default()), None);
+
+        let mut changed = RateLimitConfig::default();
+        changed.burst_size += 1;
+        let reloaded = RateLimiters::for_config(&server_config(changed), Some(&initial));
+
+        assert!(!Arc::ptr_eq(
+            &initial.global.config,
+            &reloaded.global.config
+        ));
+        assert!(Arc::ptr_eq(
+            &initial.create_lobby.config,
+            &reloaded.create_lobby.config
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_static_assets_do_not_count_against_the_api_limit() {
+        let static_dir =
+            std::env::temp_dir().join(format!("kolmodin-static-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&static_dir).unwrap();
+        std::fs::write(static_dir.join("index.html"), "<html>app</html>").unwrap();
+        std::fs::write(static_dir.join("app.abc123.js"), "console.log(1)").unwrap();
+
+        let rate_limit = RateLimitConfig {
+            burst_size: 5,
+            ..RateLimitConfig::default()
+        };
+        let mut config = server_config(rate_limit);
+        config.static_dir = Some(static_dir.to_str().unwrap().to_string());
+        let rate_limiters = RateLimiters::for_config(&config, None);
+        let router = build_router(AppState::for_tests().await, &config, &rate_limiters);
+        let peer = axum::extract::ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000)));
+        let send = |uri: &str| {
+            let mut request = Request::get(uri).body(Body::empty()).unwrap();
+            request.extensions_mut().insert(peer);
+            router.clone().oneshot(request)
+        };
+
+        for _ in 0..20 {
+            let asset = send("/app.abc123.js").await.unwrap();
+            assert_eq!(asset.status(), StatusCode::OK);
+        }
+        for _ in 0..5 {
+            let api = send("/api/auth/me").await.unwrap();
+            assert_ne!(api.status(), StatusCode::TOO_MANY_REQUESTS);
+        }
+        let limited = send("/api/auth/me").await.unwrap();
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        std::fs::remove_dir_all(static_dir).unwrap();
+    }
 }
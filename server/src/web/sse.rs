@@ -0,0 +1,169 @@
+use axum::extract::{Path, State, ws};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::Stream;
+use futures_util::stream;
+use std::convert::Infallible;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::error::{Result as WebResult, WebError};
+use crate::lobby::LobbyActorHandle;
+use crate::state::AppState;
+
+/// Unregisters the SSE subscriber from its lobby when the response stream is dropped.
+struct SseSubscription {
+    lobby_handle: LobbyActorHandle,
+    client_id: Uuid,
+}
+
+impl Drop for SseSubscription {
+    fn drop(&mut self) {
+        let lobby_handle = self.lobby_handle.clone();
+        let client_id = self.client_id;
+        tokio::spawn(async move {
+            lobby_handle.client_disconnected(client_id).await;
+        });
+    }
+}
+
+/// Streams a lobby's public events as Server-Sent Events. Subscribers join as
+/// viewers, so they get the redacted initial state followed by every broadcast,
+/// and the stream ends when the lobby closes.
+#[tracing::instrument(skip(app_state), fields(
+    http.method = "GET",
+    http.path = "/api/lobby/{id}/events",
+    lobby.id = %lobby_id
+))]
+pub async fn lobby_events_handler(
+    State(app_state): State<AppState>,
+    Path(lobby_id): Path<Uuid>,
+) -> WebResult<Sse<impl Stream<Item = Result<Event, Infallible>>>> {
+    let lobby_handle = app_state.get_lobby_handle(lobby_id).ok_or_else(|| {
+        tracing::debug!("SSE subscription requested for unknown lobby");
+        WebError::NotFound(format!("Lobby {} not found.", lobby_id))
+    })?;
+
+    let client_id = Uuid::new_v4();
+    let (client_tx, client_rx) = mpsc::channel::<ws::Message>(32);
+    lobby_handle
+        .client_connected(client_id, client_tx, None)
+        .await;
+
+    tracing::info!(client.id = %client_id, "SSE subscriber connected");
+
+    let subscription = SseSubscription {
+        lobby_handle,
+        client_id,
+    };
+
+    let events = stream::unfold(
+        (client_rx, subscription),
+        |(mut client_rx, subscription)| async move {
+            loop {
+                match client_rx.recv().await? {
+                    ws::Message::Text(text) => {
+                        let event = Event::default().data(text.as_str());
+                        return Some((Ok(event), (client_rx, subscription)));
+                    }
+                    ws::Message::Close(_) => return None,
+                    _ => continue,
+                }
+            }
+        },
+    );
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_logic::ClientToServerMessage;
+    use crate::lobby::LobbyCreationOptions;
+    use axum::response::IntoResponse;
+    use futures_util::StreamExt;
+    use serde_json::Value as JsonValue;
+    use std::time::Duration;
+
+    /// Opens the event stream and returns the JSON of each `data:` event,
+    /// skipping keep-alive comments.
+    async fn subscribe(
+        app_state: &AppState,
+        lobby_id: Uuid,
+    ) -> impl Stream<Item = JsonValue> + Unpin + use<> {
+        let response = lobby_events_handler(State(app_state.clone()), Path(lobby_id))
+            .await
+            .unwrap()
+            .into_response();
+        Box::pin(
+            response
+                .into_body()
+                .into_data_stream()
+                .filter_map(|frame| async move {
+                    let frame = String::from_utf8(frame.unwrap().to_vec()).unwrap();
+                    let data = frame.strip_prefix("data: ")?.trim_end();
+                    Some(serde_json::from_str(data).unwrap())
+                }),
+        )
+    }
+
+    async fn create_started_quiz(app_state: &AppState) -> (Uuid, LobbyActorHandle) {
+        let details = app_state
+            .create_lobby(
+                LobbyCreationOptions {
+                    game_type: Some("quiz".to_string()),
+                    ..LobbyCreationOptions::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        let lobby = app_state.get_lobby_handle(details.lobby_id).unwrap();
+        let start = ClientToServerMessage::GameSpecificCommand {
+            game_type_id: "Quiz".to_string(),
+            command_data: serde_json::json!({ "command": "StartGame" }),
+        };
+        lobby
+            .execute_command(details.admin_id, start)
+            .await
+            .unwrap();
+        (details.admin_id, lobby)
+    }
+
+    #[tokio::test]
+    async fn test_lobby_events_stream() {
+        let app_state = AppState::for_tests().await;
+
+        // The first event is the full state with the answer left out.
+        let (admin_id, lobby) = create_started_quiz(&app_state).await;
+        let host_state = lobby.state_snapshot(Some(admin_id)).await.unwrap().state;
+        assert_eq!(
+            host_state["data"]["phase"]["data"]["current_answer"],
+            "Oslo"
+        );
+        let mut events = subscribe(&app_state, lobby.lobby_id).await;
+        let first = events.next().await.unwrap();
+        assert_eq!(first["messageType"], "GameSpecificEvent");
+        let state = &first["payload"]["event_data"];
+        assert_eq!(state["event_type"], "FullStateUpdate");
+        assert_eq!(state["data"]["phase"]["type"], "Playing");
+        assert!(state["data"]["phase"]["data"]["current_answer"].is_null());
+
+        // Dropping the stream disconnects the subscriber, which empties and closes the lobby.
+        drop(events);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while app_state.get_lobby_handle(lobby.lobby_id).is_some() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // A lobby closing under a subscriber, here through inactivity, ends its stream.
+        let (_, lobby) = create_started_quiz(&app_state).await;
+        let mut events = subscribe(&app_state, lobby.lobby_id).await;
+        tokio::time::pause();
+        while events.next().await.is_some() {}
+        assert!(app_state.get_lobby_handle(lobby.lobby_id).is_none());
+    }
+}
@@ -0,0 +1,95 @@
+use axum::{
+    Router,
+    extract::Request,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use http::{HeaderValue, StatusCode, header::CACHE_CONTROL};
+use std::path::Path;
+use tower_http::services::{ServeDir, ServeFile};
+
+/// SvelteKit writes content-hashed build output here, so these files never change.
+const IMMUTABLE_ASSET_PREFIX: &str = "/_app/immutable/";
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+const REVALIDATE_CACHE_CONTROL: &str = "no-cache";
+
+/// Serves the built frontend from `static_dir`. Paths that match no file fall back
+/// to `index.html` so client-side routes survive a reload.
+pub fn static_router(static_dir: &str) -> Router {
+    let index_path = Path::new(static_dir).join("index.html");
+    if !index_path.is_file() {
+        tracing::warn!(
+            static_dir = %static_dir,
+            "Static directory has no index.html; did you build the frontend?"
+        );
+    }
+
+    let serve_dir = ServeDir::new(static_dir).fallback(ServeFile::new(index_path));
+    Router::new()
+        .fallback_service(serve_dir)
+        .layer(middleware::from_fn(static_response_policy))
+}
+
+async fn static_response_policy(request: Request, next: Next) -> Response {
+    let path = request.uri().path();
+    // Unknown API routes must stay 404s rather than receiving the SPA shell.
+    if path.starts_with("/api/") {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let cache_control = if path.starts_with(IMMUTABLE_ASSET_PREFIX) {
+        IMMUTABLE_CACHE_CONTROL
+    } else {
+        REVALIDATE_CACHE_CONTROL
+    };
+
+    let mut response = next.run(request).await;
+    if response.status().is_success() || response.status() == StatusCode::NOT_MODIFIED {
+        response
+            .headers_mut()
+            .insert(CACHE_CONTROL, HeaderValue::from_static(cache_control));
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_serves_assets_with_spa_fallback() {
+        let static_dir =
+            std::env::temp_dir().join(format!("kolmodin-static-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(static_dir.join("_app/immutable")).unwrap();
+        std::fs::write(static_dir.join("index.html"), "<html>app</html>").unwrap();
+        std::fs::write(
+            static_dir.join("_app/immutable/app.abc123.js"),
+            "console.log(1)",
+        )
+        .unwrap();
+
+        let app = static_router(static_dir.to_str().unwrap());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let asset = reqwest::get(format!("{base}/_app/immutable/app.abc123.js"))
+            .await
+            .unwrap();
+        assert_eq!(asset.status(), StatusCode::OK);
+        assert_eq!(asset.headers()[CACHE_CONTROL], IMMUTABLE_CACHE_CONTROL);
+
+        let client_route = reqwest::get(format!("{base}/lobby/1234")).await.unwrap();
+        assert_eq!(client_route.status(), StatusCode::OK);
+        assert_eq!(
+            client_route.headers()[CACHE_CONTROL],
+            REVALIDATE_CACHE_CONTROL
+        );
+        assert_eq!(client_route.text().await.unwrap(), "<html>app</html>");
+
+        let unknown_api = reqwest::get(format!("{base}/api/unknown")).await.unwrap();
+        assert_eq!(unknown_api.status(), StatusCode::NOT_FOUND);
+
+        std::fs::remove_dir_all(static_dir).unwrap();
+    }
+}
@@ -0,0 +1,67 @@
+use axum_server::tls_rustls::RustlsConfig;
+use std::time::SystemTime;
+use tokio::time::Duration as TokioDuration;
+
+use crate::config::TlsConfig;
+use crate::error::{ConfigError, Result as AppResult};
+
+pub async fn load_rustls_config(tls: &TlsConfig) -> AppResult<RustlsConfig> {
+    RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path)
+        .await
+        .map_err(|e| {
+            ConfigError::InvalidValue(format!(
+                "Failed to load TLS certificate '{}' and key '{}': {}",
+                tls.cert_path, tls.key_path, e
+            ))
+            .into()
+        })
+}
+
+/// Polls the certificate and key files and swaps them into `rustls_config` when
+/// either changes, so renewed certificates are served without a restart. A failed
+/// reload keeps the previous certificate.
+pub fn spawn_certificate_watcher(rustls_config: RustlsConfig, tls: TlsConfig) {
+    tokio::spawn(async move {
+        let mut last_modified = modified_times(&tls).await;
+        let mut interval =
+            tokio::time::interval(TokioDuration::from_secs(tls.reload_interval_secs.max(1)));
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            let modified = modified_times(&tls).await;
+            if modified == last_modified {
+                continue;
+            }
+
+            match rustls_config
+                .reload_from_pem_file(&tls.cert_path, &tls.key_path)
+                .await
+            {
+                Ok(()) => {
+                    tracing::info!(tls.cert_path = %tls.cert_path, "Reloaded TLS certificate");
+                    last_modified = modified;
+                }
+                Err(e) => {
+                    // Certificate and key are often replaced one after the other; retry
+                    // on the next tick rather than recording the half-updated pair.
+                    tracing::warn!(
+                        error = %e,
+                        tls.cert_path = %tls.cert_path,
+                        "Failed to reload TLS certificate, keeping the current one"
+                    );
+                }
+            }
+        }
+    });
+}
+
+async fn modified_times(tls: &TlsConfig) -> (Option<SystemTime>, Option<SystemTime>) {
+    async fn modified(path: &str) -> Option<SystemTime> {
+        tokio::fs::metadata(path).await.ok()?.modified().ok()
+    }
+    (
+        modified(&tls.cert_path).await,
+        modified(&tls.key_path).await,
+    )
+}
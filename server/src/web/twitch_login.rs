@@ -0,0 +1,336 @@
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{AppendHeaders, IntoResponse, Json, Redirect, Response},
+};
+use dashmap::DashMap;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use super::error::{Result as WebResult, WebError};
+use crate::config::TwitchConfig;
+use crate::state::AppState;
+use crate::twitch::oauth::{self, TwitchUser};
+
+const SESSION_COOKIE: &str = "kolmodin_session";
+const OAUTH_STATE_COOKIE: &str = "kolmodin_oauth_state";
+/// How long a browser has to come back from the Twitch consent screen.
+const OAUTH_STATE_TTL: Duration = Duration::from_secs(10 * 60);
+/// Logins in progress kept at once. Past this, starting a login drops the oldest
+/// one, so hitting the login route cannot grow memory without bound.
+const MAX_PENDING_LOGINS: usize = 1024;
+
+struct LoginSession {
+    user: TwitchUser,
+    expires_at: Instant,
+}
+
+/// Broadcasters logged in with Twitch, keyed by the session cookie, and the OAuth
+/// `state` values of logins still in progress. Held in memory, so a restart logs
+/// everyone out.
+#[derive(Default)]
+pub struct LoginSessions {
+    sessions: DashMap<String, LoginSession>,
+    pending_states: DashMap<String, Instant>,
+}
+
+impl LoginSessions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn begin_login(&self) -> String {
+        let now = Instant::now();
+        self.pending_states
+            .retain(|_, expires_at| *expires_at > now);
+        while self.pending_states.len() >= MAX_PENDING_LOGINS {
+            let oldest = self
+                .pending_states
+                .iter()
+                .min_by_key(|entry| *entry.value())
+                .map(|entry| entry.key().clone());
+            match oldest {
+                Some(oldest) => self.pending_states.remove(&oldest),
+                None => break,
+            };
+        }
+        let state = random_token();
+        self.pending_states
+            .insert(state.clone(), now + OAUTH_STATE_TTL);
+        state
+    }
+
+    /// Consumes `state`, returning whether it was issued by `begin_login` and is
+    /// still valid.
+    fn finish_login(&self, state: &str) -> bool {
+        self.pending_states
+            .remove(state)
+            .is_some_and(|(_, expires_at)| expires_at > Instant::now())
+    }
+
+    fn create_session(&self, user: TwitchUser, ttl: Duration) -> String {
+        let now = Instant::now();
+        self.sessions.retain(|_, session| session.expires_at > now);
+        let token = random_token();
+        self.sessions.insert(
+            token.clone(),
+            LoginSession {
+                user,
+                expires_at: now + ttl,
+            },
+        );
+        token
+    }
+
+    /// The Twitch account logged in through the session cookie in `headers`, if any.
+    pub fn current_user(&self, headers: &HeaderMap) -> Option<TwitchUser> {
+        let token = cookie_value(headers, SESSION_COOKIE)?;
+        let session = self.sessions.get(token)?;
+        (session.expires_at > Instant::now()).then(|| session.user.clone())
+    }
+
+    fn end_session(&self, headers: &HeaderMap) {
+        if let Some(token) = cookie_value(headers, SESSION_COOKIE) {
+            self.sessions.remove(token);
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LoggedInUserResponse {
+    pub login: String,
+    pub display_name: String,
+}
+
+fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn cookie_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// `Set-Cookie` value for an HttpOnly cookie. Pass a zero `max_age` to clear it.
+fn cookie(twitch_config: &TwitchConfig, name: &str, value: &str, max_age: Duration) -> String {
+    let secure = if twitch_config.login.redirect_url.starts_with("https://") {
+        "; Secure"
+    } else {
+        ""
+    };
+    format!(
+        "{}={}; Path=/; HttpOnly; SameSite=Lax; Max-Age={}{}",
+        name,
+        value,
+        max_age.as_secs(),
+        secure
+    )
+}
+
+fn login_config(app_state: &AppState) -> WebResult<TwitchConfig> {
+    let twitch_config = app_state.settings.current().twitch.clone();
+    if !twitch_config.login.enabled {
+        return Err(WebError::NotFound(
+            "Twitch login is not enabled on this server".to_string(),
+        ));
+    }
+    Ok(twitch_config)
+}
+
+#[tracing::instrument(skip(app_state), fields(
+    http.method = "GET",
+    http.path = "/api/auth/twitch/login"
+))]
+pub async fn twitch_login_handler(State(app_state): State<AppState>) -> WebResult<Response> {
+    let twitch_config = login_config(&app_state)?;
+    let state = app_state.login_sessions.begin_login();
+    let authorize_url = oauth::authorize_url(&twitch_config, &state)
+        .map_err(|e| WebError::InternalServerError(e.to_string()))?;
+
+    Ok((
+        AppendHeaders([(
+            header::SET_COOKIE,
+            cookie(&twitch_config, OAUTH_STATE_COOKIE, &state, OAUTH_STATE_TTL),
+        )]),
+        Redirect::to(&authorize_url),
+    )
+        .into_response())
+}
+
+#[tracing::instrument(skip(app_state, headers, query), fields(
+    http.method = "GET",
+    http.path = "/api/auth/twitch/callback"
+))]
+pub async fn twitch_callback_handler(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<CallbackQuery>,
+) -> WebResult<Response> {
+    let twitch_config = login_config(&app_state)?;
+    if let Some(error) = query.error {
+        tracing::info!(oauth.error = %error, "Twitch login was not approved");
+        return Err(WebError::Unauthorized(format!(
+            "Twitch login was not approved: {}",
+            error
+        )));
+    }
+    let (Some(code), Some(state)) = (query.code, query.state) else {
+        return Err(WebError::Unauthorized(
+            "Missing code or state in Twitch callback".to_string(),
+        ));
+    };
+    // The state must both be one we issued and belong to this browser.
+    let state_matches_browser = cookie_value(&headers, OAUTH_STATE_COOKIE) == Some(state.as_str());
+    if !app_state.login_sessions.finish_login(&state) || !state_matches_browser {
+        tracing::warn!("Twitch callback with unknown or expired state");
+        return Err(WebError::Unauthorized(
+            "Twitch login expired or was started elsewhere. Please try again".to_string(),
+        ));
+    }
+
+    let user = oauth::login_user(&twitch_config, &code)
+        .await
+        .map_err(|e| {
+            tracing::warn!(error = %e, "Twitch login failed");
+            WebError::Unauthorized("Twitch login failed".to_string())
+        })?;
+
+    let session_ttl = Duration::from_secs(twitch_config.login.session_ttl_hours * 60 * 60);
+    tracing::info!(twitch.login = %user.login, twitch.user_id = %user.id, "Broadcaster logged in with Twitch");
+    let token = app_state.login_sessions.create_session(user, session_ttl);
+
+    Ok((
+        AppendHeaders([
+            (
+                header::SET_COOKIE,
+                cookie(&twitch_config, SESSION_COOKIE, &token, session_ttl),
+            ),
+            (
+                header::SET_COOKIE,
+                cookie(&twitch_config, OAUTH_STATE_COOKIE, "", Duration::ZERO),
+            ),
+        ]),
+        Redirect::to(&twitch_config.login.post_login_redirect),
+    )
+        .into_response())
+}
+
+#[tracing::instrument(skip(app_state, headers), fields(
+    http.method = "GET",
+    http.path = "/api/auth/me"
+))]
+pub async fn current_user_handler(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+) -> WebResult<Json<LoggedInUserResponse>> {
+    login_config(&app_state)?;
+    let user = app_state
+        .login_sessions
+        .current_user(&headers)
+        .ok_or_else(|| WebError::Unauthorized("Not logged in".to_string()))?;
+    Ok(Json(LoggedInUserResponse {
+        login: user.login,
+        display_name: user.display_name,
+    }))
+}
+
+#[tracing::instrument(skip(app_state, headers), fields(
+    http.method = "POST",
+    http.path = "/api/auth/logout"
+))]
+pub async fn logout_handler(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+) -> WebResult<Response> {
+    let twitch_config = login_config(&app_state)?;
+    app_state.login_sessions.end_session(&headers);
+    Ok((
+        StatusCode::NO_CONTENT,
+        AppendHeaders([(
+            header::SET_COOKIE,
+            cookie(&twitch_config, SESSION_COOKIE, "", Duration::ZERO),
+        )]),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers_with_cookie(cookie: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::COOKIE, cookie.parse().unwrap());
+        headers
+    }
+
+    fn user(login: &str) -> TwitchUser {
+        TwitchUser {
+            id: "1".to_string(),
+            login: login.to_string(),
+            display_name: login.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_finds_cookie_among_others() {
+        let headers = headers_with_cookie("theme=dark; kolmodin_session=abc; other=1");
+        assert_eq!(cookie_value(&headers, SESSION_COOKIE), Some("abc"));
+        assert_eq!(cookie_value(&headers, OAUTH_STATE_COOKIE), None);
+    }
+
+    #[test]
+    fn test_sessions_resolve_until_expired_or_ended() {
+        let sessions = LoginSessions::new();
+        let token = sessions.create_session(user("somestreamer"), Duration::from_secs(60));
+        let headers = headers_with_cookie(&format!("{}={}", SESSION_COOKIE, token));
+        assert_eq!(
+            sessions.current_user(&headers).map(|user| user.login),
+            Some("somestreamer".to_string())
+        );
+
+        sessions.end_session(&headers);
+        assert!(sessions.current_user(&headers).is_none());
+
+        let expired = sessions.create_session(user("other"), Duration::ZERO);
+        let headers = headers_with_cookie(&format!("{}={}", SESSION_COOKIE, expired));
+        assert!(sessions.current_user(&headers).is_none());
+    }
+
+    #[test]
+    fn test_oauth_state_is_single_use() {
+        let sessions = LoginSessions::new();
+        let state = sessions.begin_login();
+        assert!(sessions.finish_login(&state));
+        assert!(!sessions.finish_login(&state));
+        assert!(!sessions.finish_login("forged"));
+    }
+
+    #[test]
+    fn test_pending_logins_are_capped_oldest_first() {
+        let sessions = LoginSessions::new();
+        let first = sessions.begin_login();
+        std::thread::sleep(Duration::from_millis(1));
+        for _ in 0..MAX_PENDING_LOGINS {
+            sessions.begin_login();
+        }
+        assert_eq!(sessions.pending_states.len(), MAX_PENDING_LOGINS);
+        assert!(!sessions.finish_login(&first));
+    }
+}
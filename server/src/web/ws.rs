@@ -2,23 +2,156 @@ use axum::extract::{
     State,
     ws::{self, WebSocket, WebSocketUpgrade},
 };
-use axum::response::IntoResponse;
-use futures_util::{SinkExt, StreamExt};
+use axum::response::{IntoResponse, Response};
+use futures_util::{SinkExt, StreamExt, stream::SplitSink};
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use http::HeaderMap;
+use std::num::NonZeroU32;
 use tokio::sync::mpsc;
+use tokio::time::Duration as TokioDuration;
 use uuid::Uuid;
 
+use super::error::WebError;
+use crate::config::WebSocketRateLimit;
 use crate::game_logic::messages::{
     ClientToServerMessage, ServerToClientMessage, parse_client_ws_message,
 };
 use crate::lobby::LobbyActorHandle;
 use crate::state::AppState;
 
+/// How long a connection closed by the server waits for its close frame to be sent.
+const CLOSE_FLUSH_TIMEOUT: TokioDuration = TokioDuration::from_secs(1);
+/// Application close code sent when the requested lobby does not exist.
+const CLOSE_LOBBY_NOT_FOUND: u16 = 4004;
+
+/// Token bucket for the messages a single client sends.
+struct MessageRateLimiter {
+    limiter: DefaultDirectRateLimiter,
+    max_rejected_messages: u32,
+    /// Messages dropped in the current flood; starts over once a message gets
+    /// through again.
+    rejected_messages: u32,
+    limited: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum MessageVerdict {
+    Accept,
+    /// Drop the message. `notify` is set for the first message of a flood so the
+    /// client is warned once rather than once per message.
+    Reject {
+        notify: bool,
+    },
+    Disconnect,
+}
+
+impl MessageRateLimiter {
+    fn new(config: &WebSocketRateLimit) -> Self {
+        let quota = Quota::with_period(TokioDuration::from_millis(config.per_millisecond))
+            .expect("websocket rate limit is validated by load_settings to be non-zero")
+            .allow_burst(
+                NonZeroU32::new(config.burst_size)
+                    .expect("websocket rate limit is validated by load_settings to be non-zero"),
+            );
+        Self {
+            limiter: RateLimiter::direct(quota),
+            max_rejected_messages: config.max_rejected_messages,
+            rejected_messages: 0,
+            limited: false,
+        }
+    }
+
+    fn check(&mut self) -> MessageVerdict {
+        if self.limiter.check().is_ok() {
+            self.limited = false;
+            self.rejected_messages = 0;
+            return MessageVerdict::Accept;
+        }
+        self.rejected_messages += 1;
+        if self.rejected_messages >= self.max_rejected_messages {
+            return MessageVerdict::Disconnect;
+        }
+        let notify = !self.limited;
+        self.limited = true;
+        MessageVerdict::Reject { notify }
+    }
+}
+
 pub async fn ws_handler(
     ws_upgrade: WebSocketUpgrade,
     State(app_state): State<AppState>,
-) -> impl IntoResponse {
+    headers: HeaderMap,
+) -> Response {
     tracing::info!("WebSocket: Connection attempt to generic /ws endpoint");
-    ws_upgrade.on_upgrade(move |socket| handle_connection(socket, app_state))
+
+    let settings = app_state.settings.current();
+    let origin = headers
+        .get(http::header::ORIGIN)
+        .and_then(|value| value.to_str().ok());
+    let host = headers
+        .get(http::header::HOST)
+        .and_then(|value| value.to_str().ok());
+    if !origin_allowed(origin, host, &settings.server.cors_origins) {
+        tracing::warn!(
+            http.origin = ?origin,
+            "WebSocket: Rejected upgrade from an origin that is not allowed"
+        );
+        return WebError::Forbidden("Origin not allowed".to_string()).into_response();
+    }
+
+    let max_message_bytes = settings.server.websocket.max_message_bytes;
+    ws_upgrade
+        .max_message_size(max_message_bytes)
+        .max_frame_size(max_message_bytes)
+        .on_upgrade(move |socket| handle_connection(socket, app_state))
+}
+
+/// Browsers always send `Origin` on WebSocket upgrades, so a page may only open a
+/// socket if it is served from one of `allowed_origins` or from this server itself.
+/// Requests without `Origin` come from non-browser clients and are let through.
+fn origin_allowed(origin: Option<&str>, host: Option<&str>, allowed_origins: &[String]) -> bool {
+    let Some(origin) = origin else {
+        return true;
+    };
+    let origin = origin.trim_end_matches('/');
+    if allowed_origins
+        .iter()
+        .any(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+    {
+        return true;
+    }
+    let origin_authority = origin
+        .strip_prefix("https://")
+        .or_else(|| origin.strip_prefix("http://"));
+    matches!((origin_authority, host), (Some(authority), Some(host)) if authority.eq_ignore_ascii_case(host))
+}
+
+fn is_message_too_large(error: &axum::Error) -> bool {
+    std::error::Error::source(error)
+        .and_then(|source| source.downcast_ref::<tokio_tungstenite::tungstenite::Error>())
+        .is_some_and(|error| matches!(error, tokio_tungstenite::tungstenite::Error::Capacity(_)))
+}
+
+fn close_message(code: u16, reason: &'static str) -> ws::Message {
+    ws::Message::Close(Some(ws::CloseFrame {
+        code,
+        reason: reason.into(),
+    }))
+}
+
+/// Tells a client why its connection is refused, then closes it with `code`.
+async fn reject_connection(
+    ws_sender: &mut SplitSink<WebSocket, ws::Message>,
+    message: String,
+    code: u16,
+    reason: &'static str,
+) {
+    let error_response = ServerToClientMessage::SystemError { message };
+    if let Ok(ws_msg) = error_response.to_ws_text() {
+        let _ = ws_sender.send(ws_msg).await;
+    }
+    let _ = ws_sender.send(close_message(code, reason)).await;
+    let _ = ws_sender.close().await;
 }
 
 pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
@@ -26,6 +159,7 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
 
     let lobby_handle: LobbyActorHandle;
     let client_id: Uuid;
+    let admin_token: Option<Uuid>;
 
     match ws_receiver.next().await {
         Some(Ok(ws::Message::Text(text_msg))) => {
@@ -33,8 +167,10 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
             match parse_client_ws_message(&text_msg) {
                 Ok(ClientToServerMessage::ConnectToLobby {
                     lobby_id: received_lobby_id,
+                    admin_token: received_admin_token,
                 }) => {
                     client_id = Uuid::new_v4();
+                    admin_token = received_admin_token;
                     tracing::info!(
                         "WebSocket: Client {} attempting to connect to lobby {} via initial message",
                         client_id,
@@ -50,13 +186,13 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
                                 received_lobby_id,
                                 client_id
                             );
-                            let error_response = ServerToClientMessage::SystemError {
-                                message: format!("Lobby {} not found.", received_lobby_id),
-                            };
-                            if let Ok(ws_msg) = error_response.to_ws_text() {
-                                let _ = ws_sender.send(ws_msg).await;
-                            }
-                            let _ = ws_sender.close().await;
+                            reject_connection(
+                                &mut ws_sender,
+                                format!("Lobby {} not found.", received_lobby_id),
+                                CLOSE_LOBBY_NOT_FOUND,
+                                "Lobby not found",
+                            )
+                            .await;
                             return;
                         }
                     }
@@ -66,14 +202,13 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
                         "WebSocket: Initial message was not ConnectToLobby. Received: {:?}. Closing.",
                         other_msg
                     );
-                    let error_response = ServerToClientMessage::SystemError {
-                        message: "Invalid initial message type. Expected ConnectToLobby."
-                            .to_string(),
-                    };
-                    if let Ok(ws_msg) = error_response.to_ws_text() {
-                        let _ = ws_sender.send(ws_msg).await;
-                    }
-                    let _ = ws_sender.close().await;
+                    reject_connection(
+                        &mut ws_sender,
+                        "Invalid initial message type. Expected ConnectToLobby.".to_string(),
+                        ws::close_code::POLICY,
+                        "Expected ConnectToLobby",
+                    )
+                    .await;
                     return;
                 }
                 Err(e) => {
@@ -82,13 +217,13 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
                         e,
                         text_msg
                     );
-                    let error_response = ServerToClientMessage::SystemError {
-                        message: format!("Invalid initial connection message format: {}", e),
-                    };
-                    if let Ok(ws_msg) = error_response.to_ws_text() {
-                        let _ = ws_sender.send(ws_msg).await;
-                    }
-                    let _ = ws_sender.close().await;
+                    reject_connection(
+                        &mut ws_sender,
+                        format!("Invalid initial connection message format: {}", e),
+                        ws::close_code::POLICY,
+                        "Invalid ConnectToLobby message",
+                    )
+                    .await;
                     return;
                 }
             }
@@ -98,13 +233,23 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
                 "WS: Client sent non-text initial message: {:?}. Closing.",
                 other_type_msg
             );
-            let error_response = ServerToClientMessage::SystemError {
-                message: "Initial message must be a text JSON message (ConnectToLobby)."
-                    .to_string(),
-            };
-            if let Ok(ws_msg) = error_response.to_ws_text() {
-                let _ = ws_sender.send(ws_msg).await;
-            }
+            reject_connection(
+                &mut ws_sender,
+                "Initial message must be a text JSON message (ConnectToLobby).".to_string(),
+                ws::close_code::UNSUPPORTED,
+                "Expected a text message",
+            )
+            .await;
+            return;
+        }
+        Some(Err(e)) if is_message_too_large(&e) => {
+            tracing::warn!(
+                "WS: Initial message exceeded the size limit: {}. Closing.",
+                e
+            );
+            let _ = ws_sender
+                .send(close_message(ws::close_code::SIZE, "Message too large"))
+                .await;
             let _ = ws_sender.close().await;
             return;
         }
@@ -126,15 +271,21 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
     );
 
     let (actor_to_client_tx, mut actor_to_client_rx) = mpsc::channel::<ws::Message>(32);
+    // Only a weak handle stays with the connection: the socket closes once the lobby
+    // drops the client's sender, e.g. on co-host revocation or lobby shutdown.
+    let server_to_client_tx = actor_to_client_tx.downgrade();
+    let mut rate_limiter =
+        MessageRateLimiter::new(&app_state.settings.current().server.rate_limit.websocket);
 
     lobby_handle
-        .client_connected(client_id, actor_to_client_tx)
+        .client_connected(client_id, actor_to_client_tx, admin_token)
         .await;
 
     let lobby_id_clone_send = lobby_handle.lobby_id;
     let client_id_clone_send = client_id;
     let mut send_task = tokio::spawn(async move {
         while let Some(message_to_send) = actor_to_client_rx.recv().await {
+            let is_close = matches!(message_to_send, ws::Message::Close(_));
             if ws_sender.send(message_to_send).await.is_err() {
                 tracing::info!(
                     "Client {} in lobby {}: WS send error (from actor), client likely disconnected.",
@@ -143,6 +294,9 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
                 );
                 break;
             }
+            if is_close {
+                break;
+            }
         }
         tracing::debug!(
             "Client {} in lobby {}: Send task from actor to WS client terminating.",
@@ -156,59 +310,109 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
     let client_id_clone_recv = client_id;
     let lobby_id_clone_recv = lobby_handle.lobby_id;
     let mut recv_task = tokio::spawn(async move {
-        loop {
+        let send_from_server = |message: ws::Message| {
+            if let Some(tx) = server_to_client_tx.upgrade() {
+                let _ = tx.try_send(message);
+            }
+        };
+        let closed_by_server = loop {
             match ws_receiver.next().await {
-                Some(Ok(msg)) => match msg {
-                    ws::Message::Text(text_msg) => {
-                        tracing::debug!(
-                            "Client {} in lobby {}: Received text from WS: {:?}",
-                            client_id_clone_recv,
-                            lobby_id_clone_recv,
-                            text_msg
-                        );
-                        if let Err(e) = lobby_handle_clone_recv
-                            .forward_client_event(client_id_clone_recv, text_msg.to_string())
-                            .await
-                        {
-                            tracing::error!(
-                                "Client {} in lobby {}: Error sending event to actor: {}",
+                Some(Ok(msg)) => {
+                    if matches!(msg, ws::Message::Text(_) | ws::Message::Binary(_)) {
+                        match rate_limiter.check() {
+                            MessageVerdict::Accept => {}
+                            MessageVerdict::Reject { notify } => {
+                                tracing::debug!(
+                                    "Client {} in lobby {}: Dropped message over the rate limit",
+                                    client_id_clone_recv,
+                                    lobby_id_clone_recv
+                                );
+                                if notify {
+                                    let warning = ServerToClientMessage::SystemError {
+                                        message: "Too many messages; slow down.".to_string(),
+                                    };
+                                    if let Ok(ws_msg) = warning.to_ws_text() {
+                                        send_from_server(ws_msg);
+                                    }
+                                }
+                                continue;
+                            }
+                            MessageVerdict::Disconnect => {
+                                tracing::warn!(
+                                    "Client {} in lobby {}: Kept flooding past the rate limit. Disconnecting.",
+                                    client_id_clone_recv,
+                                    lobby_id_clone_recv
+                                );
+                                send_from_server(close_message(
+                                    ws::close_code::POLICY,
+                                    "Rate limit exceeded",
+                                ));
+                                break true;
+                            }
+                        }
+                    }
+                    match msg {
+                        ws::Message::Text(text_msg) => {
+                            tracing::debug!(
+                                "Client {} in lobby {}: Received text from WS: {:?}",
                                 client_id_clone_recv,
                                 lobby_id_clone_recv,
-                                e
+                                text_msg
                             );
+                            if let Err(e) = lobby_handle_clone_recv
+                                .forward_client_event(client_id_clone_recv, text_msg.to_string())
+                                .await
+                            {
+                                tracing::error!(
+                                    "Client {} in lobby {}: Error sending event to actor: {}",
+                                    client_id_clone_recv,
+                                    lobby_id_clone_recv,
+                                    e
+                                );
+                            }
+                        }
+                        ws::Message::Binary(_) => {
+                            tracing::debug!(
+                                "Client {} in lobby {}: Received binary message (ignored)",
+                                client_id_clone_recv,
+                                lobby_id_clone_recv
+                            );
+                        }
+                        ws::Message::Ping(ping_data) => {
+                            tracing::trace!(
+                                "Client {} in lobby {}: Received Ping from client (data: {:?}). Axum will auto-respond with Pong.",
+                                client_id_clone_recv,
+                                lobby_id_clone_recv,
+                                ping_data
+                            );
+                        }
+                        ws::Message::Pong(_) => {
+                            tracing::trace!(
+                                "Client {} in lobby {}: Received Pong from client.",
+                                client_id_clone_recv,
+                                lobby_id_clone_recv
+                            );
+                        }
+                        ws::Message::Close(_) => {
+                            tracing::info!(
+                                "Client {} in lobby {}: WebSocket closed by client (recv).",
+                                client_id_clone_recv,
+                                lobby_id_clone_recv
+                            );
+                            break false;
                         }
                     }
-                    ws::Message::Binary(_) => {
-                        tracing::debug!(
-                            "Client {} in lobby {}: Received binary message (ignored)",
-                            client_id_clone_recv,
-                            lobby_id_clone_recv
-                        );
-                    }
-                    ws::Message::Ping(ping_data) => {
-                        tracing::trace!(
-                            "Client {} in lobby {}: Received Ping from client (data: {:?}). Axum will auto-respond with Pong.",
-                            client_id_clone_recv,
-                            lobby_id_clone_recv,
-                            ping_data
-                        );
-                    }
-                    ws::Message::Pong(_) => {
-                        tracing::trace!(
-                            "Client {} in lobby {}: Received Pong from client.",
-                            client_id_clone_recv,
-                            lobby_id_clone_recv
-                        );
-                    }
-                    ws::Message::Close(_) => {
-                        tracing::info!(
-                            "Client {} in lobby {}: WebSocket closed by client (recv).",
-                            client_id_clone_recv,
-                            lobby_id_clone_recv
-                        );
-                        break;
-                    }
-                },
+                }
+                Some(Err(e)) if is_message_too_large(&e) => {
+                    tracing::warn!(
+                        "Client {} in lobby {}: Message exceeded the size limit: {}. Disconnecting.",
+                        client_id_clone_recv,
+                        lobby_id_clone_recv,
+                        e
+                    );
+                    send_from_server(close_message(ws::close_code::SIZE, "Message too large"));
+                    break true;
+                }
                 Some(Err(e)) => {
                     tracing::warn!(
                         "Client {} in lobby {}: WebSocket error (recv): {}",
@@ -216,7 +420,7 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
                         lobby_id_clone_recv,
                         e
                     );
-                    break;
+                    break false;
                 }
                 None => {
                     tracing::info!(
@@ -224,15 +428,16 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
                         client_id_clone_recv,
                         lobby_id_clone_recv
                     );
-                    break;
+                    break false;
                 }
             }
-        }
+        };
         tracing::debug!(
             "Client {} in lobby {}: Receive task from WS client to actor terminating.",
             client_id_clone_recv,
             lobby_id_clone_recv
         );
+        closed_by_server
     });
 
     // Wait for either task to complete, then abort the other.
@@ -241,9 +446,14 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
             tracing::debug!("Client {} in lobby {}: Send task finished or aborted, aborting recv_task.", client_id, lobby_handle.lobby_id);
             recv_task.abort();
         },
-        _ = (&mut recv_task) => {
+        closed_by_server = (&mut recv_task) => {
             tracing::debug!("Client {} in lobby {}: Recv task finished or aborted, aborting send_task.", client_id, lobby_handle.lobby_id);
-            send_task.abort();
+            // Let the send task deliver the close frame the server just queued.
+            if !matches!(closed_by_server, Ok(true))
+                || tokio::time::timeout(CLOSE_FLUSH_TIMEOUT, &mut send_task).await.is_err()
+            {
+                send_task.abort();
+            }
         },
     }
 
@@ -255,3 +465,59 @@ pub async fn handle_connection(socket: WebSocket, app_state: AppState) {
         lobby_handle.lobby_id
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checks_upgrade_origin() {
+        let allowed = vec!["https://kolmodin.example".to_string()];
+        assert!(origin_allowed(None, Some("api.example"), &allowed));
+        assert!(origin_allowed(
+            Some("https://kolmodin.example"),
+            Some("api.example"),
+            &allowed
+        ));
+        assert!(origin_allowed(
+            Some("http://localhost:8080"),
+            Some("localhost:8080"),
+            &[]
+        ));
+        assert!(!origin_allowed(
+            Some("https://evil.example"),
+            Some("api.example"),
+            &allowed
+        ));
+        assert!(!origin_allowed(Some("null"), Some("api.example"), &allowed));
+    }
+
+    #[test]
+    fn test_drops_excess_messages_then_disconnects() {
+        let mut limiter = MessageRateLimiter::new(&WebSocketRateLimit {
+            per_millisecond: 60_000,
+            burst_size: 2,
+            max_rejected_messages: 3,
+        });
+        assert_eq!(limiter.check(), MessageVerdict::Accept);
+        assert_eq!(limiter.check(), MessageVerdict::Accept);
+        assert_eq!(limiter.check(), MessageVerdict::Reject { notify: true });
+        assert_eq!(limiter.check(), MessageVerdict::Reject { notify: false });
+        assert_eq!(limiter.check(), MessageVerdict::Disconnect);
+    }
+
+    #[test]
+    fn test_separate_bursts_do_not_add_up_to_a_disconnect() {
+        let mut limiter = MessageRateLimiter::new(&WebSocketRateLimit {
+            per_millisecond: 20,
+            burst_size: 1,
+            max_rejected_messages: 3,
+        });
+        for _ in 0..3 {
+            assert_eq!(limiter.check(), MessageVerdict::Accept);
+            assert_eq!(limiter.check(), MessageVerdict::Reject { notify: true });
+            assert_eq!(limiter.check(), MessageVerdict::Reject { notify: false });
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::game_logic::GameLifecycleEvent;
+
+/// Upper bound on webhook targets a single lobby may register.
+pub const MAX_LOBBY_WEBHOOKS: usize = 5;
+
+const SIGNATURE_HEADER: &str = "X-Kolmodin-Signature";
+const EVENT_HEADER: &str = "X-Kolmodin-Event";
+const DELIVERY_TIMEOUT: StdDuration = StdDuration::from_secs(10);
+const INITIAL_RETRY_DELAY: StdDuration = StdDuration::from_secs(1);
+const MAX_RETRY_DELAY: StdDuration = StdDuration::from_secs(60);
+const TARGET_QUEUE_SIZE: usize = 64;
+
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookTarget {
+    pub url: String,
+    /// Key for the `X-Kolmodin-Signature` HMAC. Payloads are sent unsigned without it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+impl std::fmt::Debug for WebhookTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookTarget")
+            .field("url", &self.url)
+            .field("has_secret", &self.secret.is_some())
+            .finish()
+    }
+}
+
+impl WebhookTarget {
+    pub fn validate(&self) -> Result<(), String> {
+        let url = reqwest::Url::parse(&self.url)
+            .map_err(|e| format!("Invalid webhook URL '{}': {}", self.url, e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("Webhook URL '{}' must use http or https", self.url));
+        }
+        Ok(())
+    }
+}
+
+pub fn validate_lobby_targets(targets: &[WebhookTarget]) -> Result<(), String> {
+    if targets.len() > MAX_LOBBY_WEBHOOKS {
+        return Err(format!(
+            "A lobby can have at most {} webhooks",
+            MAX_LOBBY_WEBHOOKS
+        ));
+    }
+    targets.iter().try_for_each(WebhookTarget::validate)
+}
+
+/// Returns `sha256=<hex HMAC-SHA256 of body>`, sent as `X-Kolmodin-Signature`.
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("sha256={}", hex)
+}
+
+/// Identifies the lobby in every payload.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookContext {
+    pub lobby_id: Uuid,
+    pub game_type_id: String,
+    pub twitch_channel: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    #[serde(flatten)]
+    context: &'a WebhookContext,
+    sent_at: u64,
+    #[serde(flatten)]
+    event: &'a GameLifecycleEvent,
+}
+
+#[derive(Debug)]
+struct WebhookDelivery {
+    event_name: &'static str,
+    body: Vec<u8>,
+}
+
+/// Posts a lobby's lifecycle events to its webhook targets. Every target has its
+/// own delivery task, so a slow or failing target neither blocks the lobby nor
+/// delays the others. The tasks stop once the lobby drops the handle.
+#[derive(Clone, Debug)]
+pub struct WebhookHandle {
+    context: Arc<WebhookContext>,
+    senders: Vec<mpsc::Sender<Arc<WebhookDelivery>>>,
+}
+
+impl WebhookHandle {
+    pub fn spawn(context: WebhookContext, targets: Vec<WebhookTarget>, max_attempts: u32) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .build()
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Failed to build webhook HTTP client, using defaults");
+                reqwest::Client::new()
+            });
+        let senders = targets
+            .into_iter()
+            .map(|target| {
+                let (sender, receiver) = mpsc::channel(TARGET_QUEUE_SIZE);
+                tokio::spawn(run_target_worker(
+                    context.lobby_id,
+                    client.clone(),
+                    target,
+                    max_attempts,
+                    receiver,
+                ));
+                sender
+            })
+            .collect();
+        Self {
+            context: Arc::new(context),
+            senders,
+        }
+    }
+
+    /// Queues `event` for every target. Never blocks; if a target is falling
+    /// behind, the event is dropped for that target.
+    pub fn dispatch(&self, event: &GameLifecycleEvent) {
+        let payload = WebhookPayload {
+            context: &self.context,
+            sent_at: crate::lobby::unix_now_secs(),
+            event,
+        };
+        let body = match serde_json::to_vec(&payload) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!(error = %e, webhook.event = event.name(), "Failed to serialize webhook payload");
+                return;
+            }
+        };
+        let delivery = Arc::new(WebhookDelivery {
+            event_name: event.name(),
+            body,
+        });
+        for sender in &self.senders {
+            if let Err(e) = sender.try_send(Arc::clone(&delivery)) {
+                tracing::warn!(error = %e, webhook.event = delivery.event_name, "Dropped webhook event");
+            }
+        }
+    }
+}
+
+#[tracing::instrument(skip(client, target, receiver), fields(lobby.id = %lobby_id, webhook.url = %target.url))]
+async fn run_target_worker(
+    lobby_id: Uuid,
+    client: reqwest::Client,
+    target: WebhookTarget,
+    max_attempts: u32,
+    mut receiver: mpsc::Receiver<Arc<WebhookDelivery>>,
+) {
+    while let Some(delivery) = receiver.recv().await {
+        deliver(
+            &client,
+            &target,
+            &delivery,
+            max_attempts,
+            INITIAL_RETRY_DELAY,
+        )
+        .await;
+    }
+    tracing::debug!("Webhook worker stopped");
+}
+
+/// Posts one delivery, retrying network errors, 429s and 5xx responses with
+/// exponential backoff. Returns whether the target accepted it.
+async fn deliver(
+    client: &reqwest::Client,
+    target: &WebhookTarget,
+    delivery: &WebhookDelivery,
+    max_attempts: u32,
+    initial_retry_delay: StdDuration,
+) -> bool {
+    let signature = target
+        .secret
+        .as_deref()
+        .map(|secret| sign_payload(secret, &delivery.body));
+    let max_attempts = max_attempts.max(1);
+    let mut retry_delay = initial_retry_delay;
+
+    for attempt in 1..=max_attempts {
+        let mut request = client
+            .post(&target.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, delivery.event_name)
+            .body(delivery.body.clone());
+        if let Some(signature) = signature.as_deref() {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+
+        let retryable = match request.send().await {
+            Ok(response) if response.status().is_success() => {
+                tracing::debug!(
+                    webhook.event = delivery.event_name,
+                    attempt,
+                    "Delivered webhook"
+                );
+                return true;
+            }
+            Ok(response) => {
+                let status = response.status();
+                tracing::warn!(
+                    webhook.event = delivery.event_name,
+                    http.status = status.as_u16(),
+                    attempt,
+                    "Webhook target rejected delivery"
+                );
+                status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    webhook.event = delivery.event_name,
+                    attempt,
+                    "Webhook delivery failed"
+                );
+                true
+            }
+        };
+
+        if !retryable || attempt == max_attempts {
+            break;
+        }
+        tokio::time::sleep(retry_delay).await;
+        retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+    }
+
+    tracing::error!(
+        webhook.event = delivery.event_name,
+        "Giving up on webhook delivery"
+    );
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, extract::State, http::HeaderMap, http::StatusCode, routing::post};
+    use std::sync::Mutex;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_signs_with_hmac_sha256() {
+        // RFC 4231, test case 2.
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_validates_lobby_targets() {
+        let target = |url: &str| WebhookTarget {
+            url: url.to_string(),
+            secret: None,
+        };
+        assert!(validate_lobby_targets(&[target("https://discord.com/api/webhooks/1/x")]).is_ok());
+        assert!(validate_lobby_targets(&[target("ftp://example.com")]).is_err());
+        assert!(validate_lobby_targets(&[target("not a url")]).is_err());
+        assert!(validate_lobby_targets(&vec![target("https://example.com"); 6]).is_err());
+    }
+
+    #[derive(Default)]
+    struct Receiver {
+        attempts: u32,
+        received: Vec<(HeaderMap, Vec<u8>)>,
+    }
+
+    async fn flaky_endpoint(
+        State(receiver): State<Arc<Mutex<Receiver>>>,
+        headers: HeaderMap,
+        body: axum::body::Bytes,
+    ) -> StatusCode {
+        let mut receiver = receiver.lock().unwrap();
+        receiver.attempts += 1;
+        if receiver.attempts == 1 {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        }
+        receiver.received.push((headers, body.to_vec()));
+        StatusCode::NO_CONTENT
+    }
+
+    #[tokio::test]
+    async fn test_retries_and_signs_deliveries() {
+        let receiver = Arc::new(Mutex::new(Receiver::default()));
+        let app = Router::new()
+            .route("/hook", post(flaky_endpoint))
+            .with_state(Arc::clone(&receiver));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let target = WebhookTarget {
+            url: format!("http://{}/hook", addr),
+            secret: Some("s3cret".to_string()),
+        };
+        let delivery = WebhookDelivery {
+            event_name: "game_started",
+            body: br#"{"event":"game_started"}"#.to_vec(),
+        };
+        let delivered = deliver(
+            &reqwest::Client::new(),
+            &target,
+            &delivery,
+            3,
+            StdDuration::from_millis(10),
+        )
+        .await;
+
+        assert!(delivered);
+        let receiver = receiver.lock().unwrap();
+        assert_eq!(receiver.attempts, 2);
+        let (headers, body) = &receiver.received[0];
+        assert_eq!(body, &delivery.body);
+        assert_eq!(headers[EVENT_HEADER], "game_started");
+        assert_eq!(
+            headers[SIGNATURE_HEADER],
+            sign_payload("s3cret", &delivery.body).as_str()
+        );
+    }
+
+    #[test]
+    fn test_payload_includes_lobby_context() {
+        let context = WebhookContext {
+            lobby_id: Uuid::nil(),
+            game_type_id: "Quiz".to_string(),
+            twitch_channel: Some("kolmodin".to_string()),
+        };
+        let event = GameLifecycleEvent::PlayerScored {
+            player: "alice".to_string(),
+            points: 3,
+        };
+        let payload = serde_json::to_value(WebhookPayload {
+            context: &context,
+            sent_at: 1_700_000_000,
+            event: &event,
+        })
+        .unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "lobby_id": Uuid::nil(),
+                "game_type_id": "Quiz",
+                "twitch_channel": "kolmodin",
+                "sent_at": 1_700_000_000,
+                "event": "player_scored",
+                "data": { "player": "alice", "points": 3 }
+            })
+        );
+    }
+}
@@ -10,7 +10,7 @@ import urllib.request
 import urllib.parse
 from typing import Dict, Any
 
-API_BASE_URL = "http://127.0.0.1:8080"
+API_BASE_URL = "http://127.0.0.1:8081"
 SEND_MESSAGE_ENDPOINT = f"{API_BASE_URL}/send_message"
 
 
@@ -52,7 +52,7 @@ def send_message(channel: str, username: str, message: str) -> bool:
         return False
     except urllib.error.URLError as e:
         print(f"❌ Connection Error: {e.reason}")
-        print("Make sure the spoof IRC server is running on localhost:8080")
+        print("Make sure the spoof IRC server is running on localhost:8081")
         return False
     except json.JSONDecodeError as e:
         print(f"❌ JSON Error: {e}")
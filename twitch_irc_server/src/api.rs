@@ -1,10 +1,20 @@
 // src/api.rs
 
-use axum::{Router, extract::State, http::StatusCode, response::Json, routing::post};
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+};
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
-use crate::irc_server::CustomMessage;
+use crate::irc_server::{CustomMessage, ReceivedChatMessage, ReceivedMessages};
+use crate::twitch_api;
+
+// Kolmodin itself defaults to port 8080.
+const API_ADDR: &str = "127.0.0.1:8081";
 
 #[derive(Debug, Deserialize)]
 pub struct SendMessageRequest {
@@ -19,10 +29,21 @@ pub struct SendMessageResponse {
     pub message: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ReceivedMessagesQuery {
+    pub channel: Option<String>,
+}
+
 pub type CustomMessageSender = mpsc::Sender<CustomMessage>;
 
+#[derive(Clone)]
+struct ApiState {
+    custom_msg_tx: CustomMessageSender,
+    received_messages: ReceivedMessages,
+}
+
 async fn send_message_handler(
-    State(custom_msg_tx): State<CustomMessageSender>,
+    State(ApiState { custom_msg_tx, .. }): State<ApiState>,
     Json(payload): Json<SendMessageRequest>,
 ) -> Result<Json<SendMessageResponse>, StatusCode> {
     // Create a custom message with default values for missing fields
@@ -50,20 +71,62 @@ async fn send_message_handler(
     }
 }
 
-pub fn create_router(custom_msg_tx: CustomMessageSender) -> Router {
+/// Chat messages clients sent through IRC, optionally only those for `?channel=`.
+async fn received_messages_handler(
+    State(ApiState {
+        received_messages, ..
+    }): State<ApiState>,
+    Query(query): Query<ReceivedMessagesQuery>,
+) -> Json<Vec<ReceivedChatMessage>> {
+    let channel = query
+        .channel
+        .map(|channel| channel.trim_start_matches('#').to_lowercase());
+    let received = received_messages.read().await;
+    Json(
+        received
+            .iter()
+            .filter(|message| channel.as_ref().is_none_or(|c| message.channel == *c))
+            .cloned()
+            .collect(),
+    )
+}
+
+async fn clear_received_messages_handler(
+    State(ApiState {
+        received_messages, ..
+    }): State<ApiState>,
+) -> StatusCode {
+    received_messages.write().await.clear();
+    StatusCode::NO_CONTENT
+}
+
+pub fn create_router(
+    custom_msg_tx: CustomMessageSender,
+    received_messages: ReceivedMessages,
+) -> Router {
     Router::new()
         .route("/send_message", post(send_message_handler))
-        .with_state(custom_msg_tx)
+        .route(
+            "/received_messages",
+            get(received_messages_handler).delete(clear_received_messages_handler),
+        )
+        .with_state(ApiState {
+            custom_msg_tx,
+            received_messages,
+        })
+        .merge(twitch_api::create_router())
 }
 
 pub async fn run_api_server(
     custom_msg_tx: CustomMessageSender,
+    received_messages: ReceivedMessages,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let app = create_router(custom_msg_tx);
+    let app = create_router(custom_msg_tx, received_messages);
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await?;
-    println!("API server listening on http://127.0.0.1:8080");
-    println!("Send POST requests to http://127.0.0.1:8080/send_message");
+    let listener = tokio::net::TcpListener::bind(API_ADDR).await?;
+    println!("API server listening on http://{}", API_ADDR);
+    println!("Send POST requests to http://{}/send_message", API_ADDR);
+    println!("Mock Twitch OAuth and Helix endpoints are served on the same address");
 
     axum::serve(listener, app).await?;
     Ok(())
@@ -1,7 +1,8 @@
 // src/irc_server.rs
 
 use chrono::Utc;
-use std::collections::{BTreeMap, HashMap};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::error::Error;
 use std::fmt;
 use std::net::SocketAddr;
@@ -16,6 +17,8 @@ const SPOOF_HOST: &str = "127.0.0.1";
 const SPOOF_PORT: u16 = 6667;
 pub const SERVER_NAME: &str = "tmi.twitch.tv";
 pub const SERVER_VERSION: &str = "spoof-tui-0.1";
+/// How many chat messages sent by clients are kept for inspection.
+const MAX_RECEIVED_MESSAGES: usize = 1000;
 
 // --- Types for Communication with UI ---
 #[derive(Debug, Clone)]
@@ -27,6 +30,18 @@ pub enum ServerLog {
     ClientDisconnected(SocketAddr),
 }
 
+/// A PRIVMSG a connected client (e.g. a chat bot) sent to a channel.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReceivedChatMessage {
+    pub channel: String,
+    pub nick: String,
+    pub message: String,
+    pub received_at: String,
+}
+
+/// Chat messages sent by clients, oldest first.
+pub type ReceivedMessages = Arc<RwLock<VecDeque<ReceivedChatMessage>>>;
+
 #[derive(Debug, Clone)]
 pub struct CustomMessage {
     pub channel: String,
@@ -87,17 +102,17 @@ pub mod irc {
 
     impl fmt::Display for IrcMessage {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            if let Some(tags) = &self.tags {
-                if !tags.is_empty() {
-                    write!(f, "@")?;
-                    for (i, (k, v)) in tags.iter().enumerate() {
-                        if i > 0 {
-                            write!(f, ";")?;
-                        }
-                        write!(f, "{}={}", k, v)?;
+            if let Some(tags) = &self.tags
+                && !tags.is_empty()
+            {
+                write!(f, "@")?;
+                for (i, (k, v)) in tags.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ";")?;
                     }
-                    write!(f, " ")?;
+                    write!(f, "{}={}", k, v)?;
                 }
+                write!(f, " ")?;
             }
             if let Some(prefix) = &self.prefix {
                 write!(f, ":{} ", prefix)?;
@@ -165,6 +180,7 @@ pub mod irc {
             // or allow CustomMessage to carry a user_id. For now, derived.
             tags.insert(TAG_USER_ID.to_string(), format!("spoofed-{}", login_name));
             tags.insert(TAG_ROOM_ID.to_string(), room_id_guess.to_string());
+            tags.insert(TAG_BADGE_INFO.to_string(), "".to_string());
             tags.insert(TAG_BADGES.to_string(), "".to_string()); // Default no badges
             tags.insert(TAG_EMOTES.to_string(), "".to_string());
             tags.insert(TAG_MOD.to_string(), "0".to_string());
@@ -192,17 +208,15 @@ struct ClientSession {
     nick: Option<String>,
     channel: Option<String>,
     state: ClientHandlerState,
-    addr: SocketAddr,
     message_sender_to_client: mpsc::Sender<irc::IrcMessage>,
 }
 
 impl ClientSession {
-    fn new(addr: SocketAddr, message_sender_to_client: mpsc::Sender<irc::IrcMessage>) -> Self {
+    fn new(message_sender_to_client: mpsc::Sender<irc::IrcMessage>) -> Self {
         ClientSession {
             nick: None,
             channel: None,
             state: ClientHandlerState::Connected,
-            addr,
             message_sender_to_client,
         }
     }
@@ -216,6 +230,7 @@ async fn handle_client(
     log_tx: mpsc::Sender<ServerLog>, // This is already a clone specific to this handler
     clients: SharedClients,
     mut broadcast_rx_for_this_client: broadcast::Receiver<irc::IrcMessage>,
+    received_messages: ReceivedMessages,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
     let _ = log_tx.send(ServerLog::ClientConnected(addr)).await;
 
@@ -228,10 +243,7 @@ async fn handle_client(
 
     {
         let mut clients_guard = clients.write().await;
-        clients_guard.insert(
-            addr,
-            ClientSession::new(addr, to_this_client_direct_tx.clone()),
-        );
+        clients_guard.insert(addr, ClientSession::new(to_this_client_direct_tx.clone()));
     }
 
     let log_tx_writer = log_tx.clone(); // Clone for the writer task
@@ -286,68 +298,82 @@ async fn handle_client(
                         let msg_sender = session.message_sender_to_client.clone();
 
                         match command_str.as_str() {
-                            irc::CMD_CAP => {
-                                if parts.len() >= 3 && parts[1].to_uppercase() == "REQ" {
-                                    let caps = parts[2..].join(" ").trim_start_matches(':').to_string();
-                                    let msg = irc::IrcMessage::new(irc::CMD_CAP, vec!["*", "ACK", &format!(":{}", caps)]);
-                                    if msg_sender.send(msg).await.is_err() {break;}
-                                    session.state = ClientHandlerState::CapabilitiesSent;
-                                }
+                            irc::CMD_CAP if parts.len() >= 3 && parts[1].to_uppercase() == "REQ" => {
+                                let caps = parts[2..].join(" ").trim_start_matches(':').to_string();
+                                let msg = irc::IrcMessage::new(irc::CMD_CAP, vec!["*", "ACK", &format!(":{}", caps)]);
+                                if msg_sender.send(msg).await.is_err() {break;}
+                                session.state = ClientHandlerState::CapabilitiesSent;
                             }
                             irc::CMD_PASS => {}
-                            irc::CMD_NICK => {
-                                if parts.len() > 1 && session.state == ClientHandlerState::CapabilitiesSent {
-                                    let nick = parts[1].to_string();
-                                    session.nick = Some(nick.clone());
-                                    let msgs = vec![
-                                        irc::IrcMessage::new(irc::RPL_WELCOME, vec![&nick, ":Welcome, GLHF!"]),
-                                        irc::IrcMessage::new(irc::RPL_YOURHOST, vec![&nick, &format!(":Your host is {}, running version {}", SERVER_NAME, SERVER_VERSION)]),
-                                        irc::IrcMessage::new(irc::RPL_CREATED, vec![&nick, ":This server was created on an arbitrary date."]),
-                                        irc::IrcMessage::new(irc::RPL_MYINFO, vec![&nick, SERVER_NAME, SERVER_VERSION, "aoits", ""]),
-                                        irc::IrcMessage::new(irc::RPL_MOTDSTART, vec![&nick, &format!(":- {} Message of the Day -", SERVER_NAME)]),
-                                        irc::IrcMessage::new(irc::RPL_MOTD, vec![&nick, ":This is a spoof TUI IRC server."]),
-                                        irc::IrcMessage::new(irc::RPL_ENDOFMOTD, vec![&nick, ":>"]),
-                                    ];
-                                    for msg in msgs { if msg_sender.send(msg).await.is_err() {break;} }
-                                    if msg_sender.is_closed() { break; }
-                                    session.state = ClientHandlerState::Authenticated;
-                                }
+                            irc::CMD_NICK if parts.len() > 1 && session.state == ClientHandlerState::CapabilitiesSent => {
+                                let nick = parts[1].to_string();
+                                session.nick = Some(nick.clone());
+                                let msgs = vec![
+                                    irc::IrcMessage::new(irc::RPL_WELCOME, vec![&nick, ":Welcome, GLHF!"]),
+                                    irc::IrcMessage::new(irc::RPL_YOURHOST, vec![&nick, &format!(":Your host is {}, running version {}", SERVER_NAME, SERVER_VERSION)]),
+                                    irc::IrcMessage::new(irc::RPL_CREATED, vec![&nick, ":This server was created on an arbitrary date."]),
+                                    irc::IrcMessage::new(irc::RPL_MYINFO, vec![&nick, SERVER_NAME, SERVER_VERSION, "aoits", ""]),
+                                    irc::IrcMessage::new(irc::RPL_MOTDSTART, vec![&nick, &format!(":- {} Message of the Day -", SERVER_NAME)]),
+                                    irc::IrcMessage::new(irc::RPL_MOTD, vec![&nick, ":This is a spoof TUI IRC server."]),
+                                    irc::IrcMessage::new(irc::RPL_ENDOFMOTD, vec![&nick, ":>"]),
+                                ];
+                                for msg in msgs { if msg_sender.send(msg).await.is_err() {break;} }
+                                if msg_sender.is_closed() { break; }
+                                session.state = ClientHandlerState::Authenticated;
                             }
-                            irc::CMD_JOIN => {
-                                if session.state == ClientHandlerState::Authenticated && parts.len() > 1 {
-                                    let chan_name = parts[1].trim_start_matches('#').to_string();
-                                    session.channel = Some(chan_name.clone());
-                                    let nick = session.nick.as_ref().cloned().unwrap_or_default();
-
-                                    let join_echo = irc::IrcMessage {
-                                        tags: None, prefix: Some(format!("{}!{}@{}.{}", nick, nick, nick, SERVER_NAME)),
-                                        command: irc::CMD_JOIN.to_string(), params: vec![format!("#{}", chan_name)],
+                            irc::CMD_JOIN if session.state == ClientHandlerState::Authenticated && parts.len() > 1 => {
+                                let chan_name = parts[1].trim_start_matches('#').to_string();
+                                session.channel = Some(chan_name.clone());
+                                let nick = session.nick.as_ref().cloned().unwrap_or_default();
+
+                                let join_echo = irc::IrcMessage {
+                                    tags: None, prefix: Some(format!("{}!{}@{}.{}", nick, nick, nick, SERVER_NAME)),
+                                    command: irc::CMD_JOIN.to_string(), params: vec![format!("#{}", chan_name)],
+                                };
+                                if msg_sender.send(join_echo).await.is_err() {break;}
+
+                                let room_id = format!("room-for-{}", chan_name);
+                                let roomstate = irc::IrcMessage::new(irc::CMD_ROOMSTATE, vec![format!("#{}", chan_name)])
+                                    .add_tag(irc::TAG_ROOM_ID, &room_id);
+                                if msg_sender.send(roomstate).await.is_err() {break;}
+
+                                let names_prefix = format!("{}.{}", nick, SERVER_NAME);
+                                let users = vec![nick.as_str(), "SpoofedUser1"];
+                                for user in users {
+                                    let namreply = irc::IrcMessage {
+                                        tags: None, prefix: Some(names_prefix.clone()), command: irc::RPL_NAMREPLY.to_string(),
+                                        params: vec![nick.clone(), "=".to_string(), format!("#{}", chan_name), format!(":{}", user)],
                                     };
-                                    if msg_sender.send(join_echo).await.is_err() {break;}
-
-                                    let room_id = format!("room-for-{}", chan_name);
-                                    let roomstate = irc::IrcMessage::new(irc::CMD_ROOMSTATE, vec![format!("#{}", chan_name)])
-                                        .add_tag(irc::TAG_ROOM_ID, &room_id);
-                                    if msg_sender.send(roomstate).await.is_err() {break;}
-
-                                    let names_prefix = format!("{}.{}", nick, SERVER_NAME);
-                                    let users = vec![nick.as_str(), "SpoofedUser1"];
-                                    for user in users {
-                                        let namreply = irc::IrcMessage {
-                                            tags: None, prefix: Some(names_prefix.clone()), command: irc::RPL_NAMREPLY.to_string(),
-                                            params: vec![nick.clone(), "=".to_string(), format!("#{}", chan_name), format!(":{}", user)],
-                                        };
-                                        if msg_sender.send(namreply).await.is_err() {break;}
-                                    }
-                                    if msg_sender.is_closed() { break; }
-
-                                    let endofnames = irc::IrcMessage {
-                                        tags: None, prefix: Some(names_prefix), command: irc::RPL_ENDOFNAMES.to_string(),
-                                        params: vec![nick, format!("#{}", chan_name), ":End of /NAMES list".to_string()],
-                                    };
-                                    if msg_sender.send(endofnames).await.is_err() {break;}
-                                    session.state = ClientHandlerState::Joined;
+                                    if msg_sender.send(namreply).await.is_err() {break;}
+                                }
+                                if msg_sender.is_closed() { break; }
+
+                                let endofnames = irc::IrcMessage {
+                                    tags: None, prefix: Some(names_prefix), command: irc::RPL_ENDOFNAMES.to_string(),
+                                    params: vec![nick, format!("#{}", chan_name), ":End of /NAMES list".to_string()],
+                                };
+                                if msg_sender.send(endofnames).await.is_err() {break;}
+                                session.state = ClientHandlerState::Joined;
+                            }
+                            irc::CMD_PRIVMSG if parts.len() > 2 => {
+                                // Recorded rather than relayed; Twitch does not echo a client's own messages.
+                                let channel = parts[1].trim_start_matches('#').to_string();
+                                let message = incoming_msg_raw
+                                    .split_once(" :")
+                                    .map(|(_, text)| text.to_string())
+                                    .unwrap_or_else(|| parts[2..].join(" "));
+                                let nick = session.nick.clone().unwrap_or_default();
+                                let _ = log_tx.send(ServerLog::Internal(format!("{} -> #{}: {}", nick, channel, message))).await;
+                                let mut received = received_messages.write().await;
+                                if received.len() >= MAX_RECEIVED_MESSAGES {
+                                    received.pop_front();
                                 }
+                                received.push_back(ReceivedChatMessage {
+                                    channel,
+                                    nick,
+                                    message,
+                                    received_at: Utc::now().to_rfc3339(),
+                                });
                             }
                             irc::CMD_PING => {
                                 let payload = if parts.len() > 1 { parts[1] } else { "" };
@@ -377,6 +403,7 @@ async fn handle_client(
 pub async fn run_server(
     log_tx_main: mpsc::Sender<ServerLog>, // Renamed for clarity, this is the original log_tx
     mut custom_msg_rx_from_ui: mpsc::Receiver<CustomMessage>,
+    received_messages: ReceivedMessages,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
     let listener_addr = format!("{}:{}", SPOOF_HOST, SPOOF_PORT);
     let listener = TcpListener::bind(&listener_addr).await?;
@@ -422,6 +449,7 @@ pub async fn run_server(
         let log_tx_for_client = log_tx_main.clone(); // Clone for each new client
         let clients_clone_for_client = Arc::clone(&clients);
         let broadcast_rx_for_client = broadcast_tx.subscribe();
+        let received_messages_for_client = Arc::clone(&received_messages);
 
         tokio::spawn(async move {
             // Use the log_tx_for_client for this specific handler's error reporting
@@ -431,6 +459,7 @@ pub async fn run_server(
                 log_tx_for_client.clone(),
                 clients_clone_for_client,
                 broadcast_rx_for_client,
+                received_messages_for_client,
             )
             .await
             {
@@ -2,6 +2,7 @@
 
 mod api;
 mod irc_server;
+mod twitch_api;
 mod ui;
 
 use color_eyre::Result;
@@ -32,11 +33,17 @@ async fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?; // Clear screen before first draw
 
+    // Chat messages clients send, shared between the IRC server and the HTTP API
+    let received_messages = irc_server::ReceivedMessages::default();
+
     // --- Spawn IRC Server Task ---
     let server_log_tx_clone = log_tx.clone(); // Clone for the server task
     let custom_msg_tx_for_api = custom_msg_tx.clone(); // Clone for the API server
+    let received_messages_for_api = received_messages.clone();
     tokio::spawn(async move {
-        if let Err(e) = irc_server::run_server(server_log_tx_clone, custom_msg_rx).await {
+        if let Err(e) =
+            irc_server::run_server(server_log_tx_clone, custom_msg_rx, received_messages).await
+        {
             // Log server error (e.g., using log_tx if UI is still running, or eprintln)
             // For simplicity, sending to log_tx, assuming UI might catch it.
             let _ = log_tx
@@ -51,7 +58,8 @@ async fn main() -> Result<()> {
 
     // --- Spawn HTTP API Server Task ---
     tokio::spawn(async move {
-        if let Err(e) = api::run_api_server(custom_msg_tx_for_api).await {
+        if let Err(e) = api::run_api_server(custom_msg_tx_for_api, received_messages_for_api).await
+        {
             eprintln!("API Server exited with error: {}", e);
         }
     });
@@ -68,7 +76,7 @@ async fn main() -> Result<()> {
     // Handle UI result
     if let Err(e) = ui_result {
         eprintln!("UI exited with error: {}", e);
-        return Err(e.into());
+        return Err(e);
     }
 
     Ok(())
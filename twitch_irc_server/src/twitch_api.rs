@@ -0,0 +1,193 @@
+// src/twitch_api.rs
+//
+// Stand-ins for the parts of id.twitch.tv and api.twitch.tv the Kolmodin server
+// talks to, so it can run without internet access. Nothing is verified: any
+// client id and secret get a token, and every login exists.
+
+use axum::{
+    Form, Router,
+    extract::{Query, RawQuery},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Json, Redirect, Response},
+    routing::{get, post},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+const APP_ACCESS_TOKEN: &str = "mock-app-access-token";
+const USER_TOKEN_PREFIX: &str = "mock-user-token-";
+const DEFAULT_LOGIN: &str = "mockstreamer";
+// Real app tokens last about two months. Kolmodin refreshes an hour before
+// expiry, so a short lifetime here would make it refetch in a loop.
+const APP_TOKEN_EXPIRES_IN_SECS: u64 = 5_000_000;
+const USER_TOKEN_EXPIRES_IN_SECS: u64 = 4 * 60 * 60;
+
+#[derive(Debug, Deserialize)]
+struct TokenRequest {
+    grant_type: String,
+    code: Option<String>,
+    refresh_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuthorizeQuery {
+    redirect_uri: String,
+    state: Option<String>,
+    /// Not part of the Twitch API: picks the account to log in as.
+    login: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct HelixUser {
+    id: String,
+    login: String,
+    display_name: String,
+    #[serde(rename = "type")]
+    user_type: String,
+    broadcaster_type: String,
+    created_at: String,
+}
+
+impl HelixUser {
+    fn new(login: &str) -> Self {
+        let login = sanitize_login(login);
+        Self {
+            // Matches the `user-id` tag on spoofed chat messages.
+            id: format!("spoofed-{}", login),
+            display_name: login.clone(),
+            login,
+            user_type: String::new(),
+            broadcaster_type: String::new(),
+            created_at: "2020-01-01T00:00:00Z".to_string(),
+        }
+    }
+}
+
+fn sanitize_login(login: &str) -> String {
+    let login: String = login
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .take(25)
+        .collect();
+    if login.is_empty() {
+        DEFAULT_LOGIN.to_string()
+    } else {
+        login
+    }
+}
+
+async fn token_handler(Form(request): Form<TokenRequest>) -> Response {
+    match request.grant_type.as_str() {
+        "client_credentials" => Json(json!({
+            "access_token": APP_ACCESS_TOKEN,
+            "expires_in": APP_TOKEN_EXPIRES_IN_SECS,
+            "token_type": "bearer",
+        }))
+        .into_response(),
+        // The code, and the refresh token handed out with it, is the login itself.
+        // Any refresh token works, so a chat bot can be configured with its login.
+        "authorization_code" | "refresh_token" => {
+            let login = sanitize_login(
+                request
+                    .code
+                    .or(request.refresh_token)
+                    .as_deref()
+                    .unwrap_or_default(),
+            );
+            Json(json!({
+                "access_token": format!("{}{}", USER_TOKEN_PREFIX, login),
+                "expires_in": USER_TOKEN_EXPIRES_IN_SECS,
+                "refresh_token": login,
+                "scope": ["chat:read", "chat:edit"],
+                "token_type": "bearer",
+            }))
+            .into_response()
+        }
+        other => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "status": 400,
+                "message": format!("unsupported grant_type '{}'", other),
+            })),
+        )
+            .into_response(),
+    }
+}
+
+/// Skips the consent screen and sends the browser straight back with a code,
+/// which is simply the login to sign in as.
+async fn authorize_handler(Query(query): Query<AuthorizeQuery>) -> Redirect {
+    let login = sanitize_login(query.login.as_deref().unwrap_or_default());
+    let separator = if query.redirect_uri.contains('?') {
+        '&'
+    } else {
+        '?'
+    };
+    let mut location = format!("{}{}code={}", query.redirect_uri, separator, login);
+    if let Some(state) = query.state {
+        location.push_str(&format!("&state={}", state));
+    }
+    Redirect::to(&location)
+}
+
+/// `GET /helix/users`: looks up `?login=` parameters, or the owner of a user
+/// token when there are none.
+async fn users_handler(headers: HeaderMap, RawQuery(query): RawQuery) -> Response {
+    let Some(token) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({ "status": 401, "message": "OAuth token is missing" })),
+        )
+            .into_response();
+    };
+    Json(json!({ "data": lookup_users(token, query.as_deref()) })).into_response()
+}
+
+fn lookup_users(token: &str, query: Option<&str>) -> Vec<HelixUser> {
+    let logins: Vec<&str> = query
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| pair.strip_prefix("login="))
+        .collect();
+    if !logins.is_empty() {
+        return logins.into_iter().map(HelixUser::new).collect();
+    }
+    token
+        .strip_prefix(USER_TOKEN_PREFIX)
+        .map(HelixUser::new)
+        .into_iter()
+        .collect()
+}
+
+pub fn create_router() -> Router {
+    Router::new()
+        .route("/oauth2/token", post(token_handler))
+        .route("/oauth2/authorize", get(authorize_handler))
+        .route("/helix/users", get(users_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_token_resolves_to_its_login() {
+        let users = lookup_users("mock-user-token-somestreamer", None);
+        assert_eq!(users, vec![HelixUser::new("somestreamer")]);
+        assert_eq!(users[0].id, "spoofed-somestreamer");
+    }
+
+    #[test]
+    fn test_login_parameters_take_precedence() {
+        let users = lookup_users(APP_ACCESS_TOKEN, Some("login=First&login=second"));
+        let logins: Vec<_> = users.iter().map(|user| user.login.as_str()).collect();
+        assert_eq!(logins, ["first", "second"]);
+        assert!(lookup_users(APP_ACCESS_TOKEN, None).is_empty());
+    }
+}
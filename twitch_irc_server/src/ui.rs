@@ -113,10 +113,10 @@ impl App {
 
             terminal.draw(|f| self.draw_ui(f))?;
 
-            if event::poll(std::time::Duration::from_millis(50))? {
-                if let CrosstermEvent::Key(key_event) = event::read()? {
-                    self.handle_key_event(key_event).await;
-                }
+            if event::poll(std::time::Duration::from_millis(50))?
+                && let CrosstermEvent::Key(key_event) = event::read()?
+            {
+                self.handle_key_event(key_event).await;
             }
         }
         Ok(())
@@ -156,12 +156,11 @@ impl App {
                         InputFocus::Message => self.input_message.push(c),
                     }
                     // Auto-update display name if username was changed AND display name was tied to it or empty
-                    if matches!(self.current_focus, InputFocus::Username) {
-                        if self.input_display_name.is_empty()
-                            || self.input_display_name == titlecase(&old_username_val)
-                        {
-                            self.input_display_name = titlecase(&self.input_username);
-                        }
+                    if matches!(self.current_focus, InputFocus::Username)
+                        && (self.input_display_name.is_empty()
+                            || self.input_display_name == titlecase(&old_username_val))
+                    {
+                        self.input_display_name = titlecase(&self.input_username);
                     }
                 }
                 KeyCode::Backspace => {